@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::input::{inputs_dir, named_path};
+use crate::parse::{parse_at, ParseError};
+
+pub const MANIFEST_FILE: &str = "answers.txt";
+
+// Expected answers for every day, input and part, kept next to the inputs they belong to
+#[derive(Debug, Default, PartialEq)]
+pub struct Manifest {
+    answers: BTreeMap<(u32, String, u32), String>,
+}
+
+impl Manifest {
+    pub fn path() -> PathBuf {
+        inputs_dir().join(MANIFEST_FILE)
+    }
+
+    pub fn load() -> Result<Manifest, String> {
+        let path = Manifest::path();
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read answers {}: {}", path.display(), err))?;
+        Manifest::parse(&text).map_err(|err| {
+            format!(
+                "Could not parse answers {} at {}",
+                path.display(),
+                err.render(&text)
+            )
+        })
+    }
+
+    // One answer per line: "<day> <input> <part> <answer>", lines starting with # are comments
+    pub fn parse(text: &str) -> Result<Manifest, ParseError> {
+        let mut manifest = Manifest::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let end = &line[line.len()..];
+            let mut fields = line.split_whitespace();
+            let mut next = |expected: &str| {
+                fields
+                    .next()
+                    .ok_or_else(|| ParseError::at(text, end, expected))
+            };
+            let day_text = next("a day")?;
+            let day = match parse_at(text, day_text, "a day")? {
+                day @ 1..=25 => day,
+                _ => return Err(ParseError::at(text, day_text, "a day from 1 to 25")),
+            };
+            let input = next("an input name")?;
+            if named_path(day, input).is_none() {
+                let expected = "full, simple, or an extra input like simple-<name>";
+                return Err(ParseError::at(text, input, expected));
+            }
+            let part_text = next("a part")?;
+            let part = match parse_at(text, part_text, "a part")? {
+                part @ 1..=2 => part,
+                _ => return Err(ParseError::at(text, part_text, "part 1 or 2")),
+            };
+            let answer = next("an answer")?;
+            if let Some(extra) = fields.next() {
+                return Err(ParseError::at(text, extra, "the end of the line"));
+            }
+
+            let key = (day, input.to_owned(), part);
+            if manifest.answers.insert(key, answer.to_owned()).is_some() {
+                return Err(ParseError::at(
+                    text,
+                    answer,
+                    "no second answer to the same part",
+                ));
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn get(&self, day: u32, input: &str, part: u32) -> Option<&str> {
+        self.answers
+            .get(&(day, input.to_owned(), part))
+            .map(String::as_str)
+    }
+
+    // Names of the inputs of a day that have any answers
+    pub fn inputs(&self, day: u32) -> Vec<&str> {
+        let mut inputs: Vec<&str> = self
+            .answers
+            .keys()
+            .filter(|key| key.0 == day)
+            .map(|key| key.1.as_str())
+            .collect();
+        inputs.dedup();
+        inputs
+    }
+}
+
+// For the tests of the days, panics if the answer is missing or of a different type
+pub fn expected<T>(day: u32, input: &str, part: u32) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let manifest = Manifest::load().unwrap_or_else(|err| panic!("{}", err));
+    let answer = manifest
+        .get(day, input, part)
+        .unwrap_or_else(|| panic!("No answer for day {} {} part {}!", day, input, part));
+    answer.parse().unwrap_or_else(|err| {
+        panic!(
+            "Answer {} for day {} {} part {} has the wrong type: {:?}",
+            answer, day, input, part, err
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest() {
+        let text = "# Comment\n7 simple 1 4\n\n10 simple-small 2 8\n21 full 2 a,b\n";
+        let manifest = Manifest::parse(text).unwrap();
+        assert_eq!(manifest.get(7, "simple", 1), Some("4"));
+        assert_eq!(manifest.get(7, "simple", 2), None);
+        assert_eq!(manifest.get(10, "simple-small", 2), Some("8"));
+        assert_eq!(manifest.get(21, "full", 2), Some("a,b"));
+        assert_eq!(manifest.inputs(10), vec!["simple-small"]);
+    }
+
+    #[test]
+    fn invalid_manifest() {
+        let err = Manifest::parse("7 simple 1 4\n7 sample 1 4").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "sample"));
+        let err = Manifest::parse("7 simple 3 4").unwrap_err();
+        assert_eq!(err.expected, "part 1 or 2");
+        assert!(Manifest::parse("7 simple 1").is_err());
+        assert!(Manifest::parse("7 simple 1 4 5").is_err());
+        assert!(Manifest::parse("7 simple 1 4\n7 simple 1 5").is_err());
+    }
+
+    #[test]
+    fn workspace_manifest() {
+        let manifest = Manifest::load().unwrap();
+        assert_eq!(manifest.inputs(10), vec!["full", "simple", "simple-small"]);
+        assert_eq!(expected::<u32>(7, "simple", 2), 32);
+        assert_eq!(expected::<String>(21, "simple", 2), "mxmxvkd,sqjhc,fvjkl");
+    }
+}
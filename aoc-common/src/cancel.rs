@@ -0,0 +1,85 @@
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+// Checkpoints only look at the clock every so often, so hot loops can call them on every
+// iteration without slowing down much
+const CHECK_EVERY: u32 = 1024;
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    static UNCHECKED: Cell<u32> = const { Cell::new(0) };
+}
+
+// A solver was stopped because it ran past its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+// Runs `solve` on this thread until it returns or calls `checkpoint` after the deadline.
+// Other panics are passed on untouched.
+pub fn with_deadline<T>(deadline: Instant, solve: impl FnOnce() -> T) -> Result<T, Cancelled> {
+    let previous = DEADLINE.with(|current| current.replace(Some(deadline)));
+    let result = panic::catch_unwind(AssertUnwindSafe(solve));
+    DEADLINE.with(|current| current.set(previous));
+    match result {
+        Ok(value) => Ok(value),
+        Err(payload) if payload.is::<Cancelled>() => Err(Cancelled),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+// Called from loops that can run for a long time. Unwinds back to `with_deadline` once the
+// deadline has passed, without running the panic hook, and does nothing without a deadline.
+pub fn checkpoint() {
+    let deadline = match DEADLINE.with(Cell::get) {
+        Some(deadline) => deadline,
+        None => return,
+    };
+    let unchecked = UNCHECKED.with(|unchecked| {
+        let count = (unchecked.get() + 1) % CHECK_EVERY;
+        unchecked.set(count);
+        count
+    });
+    if unchecked == 0 && Instant::now() >= deadline {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn cancel_after_the_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(10);
+        let result = with_deadline(deadline, || loop {
+            checkpoint();
+        });
+        assert_eq!(result, Err::<(), _>(Cancelled));
+        assert!(Instant::now() >= deadline);
+        // The deadline is gone again afterwards
+        for _ in 0..2 * CHECK_EVERY {
+            checkpoint();
+        }
+    }
+
+    #[test]
+    fn finish_before_the_deadline() {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let result = with_deadline(deadline, || {
+            (0..10 * CHECK_EVERY).fold(0, |sum, x| {
+                checkpoint();
+                sum + x
+            })
+        });
+        assert_eq!(result, Ok((0..10 * CHECK_EVERY).sum()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a timeout!")]
+    fn other_panics_pass() {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let _ = with_deadline(deadline, || panic!("Not a timeout!"));
+    }
+}
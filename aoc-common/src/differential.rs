@@ -0,0 +1,279 @@
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::rng::Rng;
+
+// Seed used when AOC_SEED isn't set, so test runs are reproducible by default
+pub const DEFAULT_SEED: u64 = 2020;
+// Upper bound on shrinking steps, in case a shrink doesn't make its input any smaller
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+// A randomly generated, valid puzzle input used to compare implementations with each other
+pub trait Case: Clone + fmt::Debug {
+    fn generate(rng: &mut Rng) -> Self;
+
+    // Smaller variants of this input, which all still have to be valid inputs
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+pub type Implementation<T, A> = (&'static str, fn(&T) -> A);
+
+// What an implementation did with an input, a panic counts as an answer of its own
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<A> {
+    Answer(A),
+    Panic(String),
+}
+
+impl<A: fmt::Debug> fmt::Display for Outcome<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{:?}", answer),
+            Outcome::Panic(message) => write!(f, "panicked with {:?}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disagreement<T, A> {
+    pub seed: u64,
+    pub case: usize, // Index of the generated input, starting at 0
+    pub original: T,
+    pub shrunk: T,
+    pub outcomes: Vec<(&'static str, Outcome<A>)>, // For the shrunk input
+}
+
+impl<T: fmt::Debug, A: fmt::Debug> fmt::Display for Disagreement<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Implementations disagree on case {} of seed {} (rerun with AOC_SEED={})",
+            self.case, self.seed, self.seed
+        )?;
+        writeln!(f, "Shrunk input: {:?}", self.shrunk)?;
+        for (name, outcome) in self.outcomes.iter() {
+            writeln!(f, "  {}: {}", name, outcome)?;
+        }
+        write!(f, "Original input: {:?}", self.original)
+    }
+}
+
+// Seed from the AOC_SEED environment variable, to explore other inputs or reproduce a failure
+pub fn seed() -> u64 {
+    env::var("AOC_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED)
+}
+
+// Generates `cases` inputs and panics with a minimal counterexample if the implementations
+// don't all give the same answer
+pub fn assert_agree<T: Case, A: PartialEq + fmt::Debug>(
+    cases: usize,
+    implementations: &[Implementation<T, A>],
+) {
+    if let Some(disagreement) = find_disagreement(seed(), cases, implementations) {
+        panic!("{}", disagreement);
+    }
+}
+
+pub fn find_disagreement<T: Case, A: PartialEq + fmt::Debug>(
+    seed: u64,
+    cases: usize,
+    implementations: &[Implementation<T, A>],
+) -> Option<Disagreement<T, A>> {
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let original = T::generate(&mut rng);
+        if let Some(outcomes) = disagree(&original, implementations) {
+            let (shrunk, outcomes) = shrink(original.clone(), outcomes, implementations);
+            return Some(Disagreement {
+                seed,
+                case,
+                original,
+                shrunk,
+                outcomes,
+            });
+        }
+    }
+    None
+}
+
+// Outcomes of every implementation, if any of them differs from the first one
+fn disagree<T, A: PartialEq>(
+    input: &T,
+    implementations: &[Implementation<T, A>],
+) -> Option<Vec<(&'static str, Outcome<A>)>> {
+    let outcomes: Vec<(&'static str, Outcome<A>)> = implementations
+        .iter()
+        .map(|(name, implementation)| {
+            let outcome = match panic::catch_unwind(AssertUnwindSafe(|| implementation(input))) {
+                Ok(answer) => Outcome::Answer(answer),
+                Err(payload) => Outcome::Panic(panic_message(payload)),
+            };
+            (*name, outcome)
+        })
+        .collect();
+    let (_, first) = outcomes.first()?;
+    if outcomes.iter().all(|(_, outcome)| outcome == first) {
+        None
+    } else {
+        Some(outcomes)
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+// Keeps taking the first smaller input that still makes the implementations disagree
+fn shrink<T: Case, A: PartialEq>(
+    mut input: T,
+    mut outcomes: Vec<(&'static str, Outcome<A>)>,
+    implementations: &[Implementation<T, A>],
+) -> (T, Vec<(&'static str, Outcome<A>)>) {
+    'steps: for _ in 0..MAX_SHRINK_STEPS {
+        for candidate in input.shrink() {
+            if let Some(candidate_outcomes) = disagree(&candidate, implementations) {
+                input = candidate;
+                outcomes = candidate_outcomes;
+                continue 'steps;
+            }
+        }
+        break;
+    }
+    (input, outcomes)
+}
+
+// Copies of `items` with one item removed, keeping at least `min_len` of them
+pub fn shrink_vec<T: Clone>(items: &[T], min_len: usize) -> Vec<Vec<T>> {
+    if items.len() <= min_len {
+        return Vec::new();
+    }
+    (0..items.len())
+        .map(|idx| {
+            let mut smaller = items.to_vec();
+            smaller.remove(idx);
+            smaller
+        })
+        .collect()
+}
+
+// Smaller numbers down to `min`, biggest jumps first
+pub fn shrink_number(number: u64, min: u64) -> Vec<u64> {
+    let mut smaller = Vec::new();
+    if number <= min {
+        return smaller;
+    }
+    smaller.push(min);
+    let half = min + (number - min) / 2;
+    if half > min {
+        smaller.push(half);
+    }
+    if number - 1 > half {
+        smaller.push(number - 1);
+    }
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Numbers(Vec<u64>);
+
+    impl Case for Numbers {
+        fn generate(rng: &mut Rng) -> Numbers {
+            let len = rng.range(0, 20);
+            Numbers((0..len).map(|_| rng.range(0, 100)).collect())
+        }
+
+        fn shrink(&self) -> Vec<Numbers> {
+            let mut smaller: Vec<Numbers> =
+                shrink_vec(&self.0, 0).into_iter().map(Numbers).collect();
+            for (idx, number) in self.0.iter().enumerate() {
+                for number in shrink_number(*number, 0) {
+                    let mut numbers = self.0.clone();
+                    numbers[idx] = number;
+                    smaller.push(Numbers(numbers));
+                }
+            }
+            smaller
+        }
+    }
+
+    fn sum(numbers: &Numbers) -> u64 {
+        numbers.0.iter().sum()
+    }
+
+    fn sum_pairs(numbers: &Numbers) -> u64 {
+        numbers
+            .0
+            .chunks(2)
+            .map(|pair| pair.iter().sum::<u64>())
+            .sum()
+    }
+
+    // Forgets about numbers above 50
+    fn sum_small(numbers: &Numbers) -> u64 {
+        numbers.0.iter().filter(|number| **number <= 50).sum()
+    }
+
+    #[test]
+    fn agreeing_implementations() {
+        let implementations: [Implementation<Numbers, u64>; 2] =
+            [("sum", sum), ("pairs", sum_pairs)];
+        assert_eq!(find_disagreement(DEFAULT_SEED, 200, &implementations), None);
+        assert_agree(200, &implementations);
+    }
+
+    #[test]
+    fn shrinks_to_minimal_input() {
+        let implementations: [Implementation<Numbers, u64>; 2] =
+            [("sum", sum), ("small", sum_small)];
+        let disagreement = find_disagreement(DEFAULT_SEED, 200, &implementations).unwrap();
+        assert_eq!(disagreement.shrunk, Numbers(vec![51]));
+        assert_eq!(
+            disagreement.outcomes,
+            vec![("sum", Outcome::Answer(51)), ("small", Outcome::Answer(0))]
+        );
+        assert!(disagreement.to_string().contains("AOC_SEED=2020"));
+    }
+
+    #[test]
+    fn panics_disagree() {
+        let implementations: [Implementation<Numbers, u64>; 2] = [
+            ("sum", sum),
+            ("non-empty", |numbers| {
+                assert!(!numbers.0.is_empty(), "No numbers!");
+                sum(numbers)
+            }),
+        ];
+        let disagreement = find_disagreement(DEFAULT_SEED, 200, &implementations).unwrap();
+        assert_eq!(disagreement.shrunk, Numbers(vec![]));
+        assert_eq!(
+            disagreement.outcomes[1].1,
+            Outcome::Panic("No numbers!".to_owned())
+        );
+    }
+
+    #[test]
+    fn shrink_numbers() {
+        assert_eq!(shrink_number(10, 0), vec![0, 5, 9]);
+        assert_eq!(shrink_number(2, 1), vec![1]);
+        assert!(shrink_number(3, 3).is_empty());
+        assert_eq!(shrink_vec(&[1, 2], 1), vec![vec![2], vec![1]]);
+        assert!(shrink_vec(&[1, 2], 2).is_empty());
+    }
+}
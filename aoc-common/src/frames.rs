@@ -0,0 +1,157 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::simulation::Simulation;
+
+// Clears the terminal and moves the cursor to the top left corner
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+// Rendered states of a simulation, the initial one first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frames {
+    frames: Vec<String>,
+}
+
+impl Frames {
+    pub fn new() -> Frames {
+        Frames::default()
+    }
+
+    // Renders the simulation before the first step and after every step. Stops early when the
+    // simulation finishes or its state doesn't change anymore, so settled grids aren't repeated.
+    pub fn record<S: Simulation>(
+        simulation: &mut S,
+        steps: usize,
+        render: impl Fn(&S) -> String,
+    ) -> Frames {
+        let mut frames = Frames::new();
+        frames.push(render(simulation));
+        let mut state = simulation.state();
+        for _ in 0..steps {
+            if simulation.finished() {
+                break;
+            }
+            simulation.step();
+            let next = simulation.state();
+            if next == state {
+                break;
+            }
+            state = next;
+            frames.push(render(simulation));
+        }
+        frames
+    }
+
+    // For things that aren't a simulation, like a picture that is searched for monsters
+    pub fn push(&mut self, frame: String) {
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.frames.iter().map(String::as_str)
+    }
+
+    // Shows the frames one after another, each replacing the previous one on the screen
+    pub fn play(&self, out: &mut impl Write, delay: Duration) -> io::Result<()> {
+        for (idx, frame) in self.frames.iter().enumerate() {
+            write!(out, "{}", CLEAR_SCREEN)?;
+            writeln!(out, "Frame {}/{}", idx + 1, self.len())?;
+            write!(out, "{}", frame)?;
+            if !frame.ends_with('\n') {
+                writeln!(out)?;
+            }
+            out.flush()?;
+            if idx + 1 < self.len() {
+                thread::sleep(delay);
+            }
+        }
+        Ok(())
+    }
+
+    // Writes every frame into its own numbered file in `dir`, which is created if needed.
+    // Returns the paths in order.
+    pub fn dump(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+        let digits = self.len().to_string().len().max(4);
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(idx, frame)| {
+                let path = dir.join(format!("frame-{:0width$}.txt", idx, width = digits));
+                fs::write(&path, frame)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts down to zero and stays there
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        type State = u32;
+
+        fn step(&mut self) {
+            self.0 = self.0.saturating_sub(1);
+        }
+
+        fn state(&self) -> u32 {
+            self.0
+        }
+    }
+
+    fn render(countdown: &Countdown) -> String {
+        format!("{}\n", countdown.0)
+    }
+
+    #[test]
+    fn record_steps() {
+        let frames = Frames::record(&mut Countdown(5), 2, render);
+        assert_eq!(frames.iter().collect::<Vec<_>>(), vec!["5\n", "4\n", "3\n"]);
+        // Nothing changes after reaching zero
+        let frames = Frames::record(&mut Countdown(2), 10, render);
+        assert_eq!(frames.len(), 3);
+        let frames = Frames::record(&mut Countdown(2), 0, render);
+        assert_eq!(frames.len(), 1);
+    }
+
+    #[test]
+    fn play_frames() {
+        let frames = Frames::record(&mut Countdown(1), 10, render);
+        let mut out = Vec::new();
+        frames.play(&mut out, Duration::from_millis(0)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            format!("{0}Frame 1/2\n1\n{0}Frame 2/2\n0\n", CLEAR_SCREEN)
+        );
+    }
+
+    #[test]
+    fn dump_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let frames = Frames::record(&mut Countdown(1), 10, render);
+        let paths = frames.dump(&dir).unwrap();
+        assert_eq!(
+            paths,
+            vec![dir.join("frame-0000.txt"), dir.join("frame-0001.txt")]
+        );
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "0\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Write;
+
+use crate::rng::Rng;
+use crate::solution::{self, Answer, Solution};
+
+// A generated puzzle input, with the answers where the generator knows them from how it was built
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Generated {
+    pub fn new(input: String) -> Generated {
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn with_part1(mut self, answer: impl Into<Answer>) -> Generated {
+        self.part1 = Some(answer.into());
+        self
+    }
+
+    pub fn with_part2(mut self, answer: impl Into<Answer>) -> Generated {
+        self.part2 = Some(answer.into());
+        self
+    }
+}
+
+// Random but valid puzzle inputs of any size, to see how the solvers scale beyond the real input
+pub trait Generator {
+    // What `size` counts, like "rules" or "tiles per side"
+    const SIZE: &'static str;
+
+    // About the size of the real puzzle input
+    const DEFAULT_SIZE: usize;
+
+    // Bounds outside of which the input can't be valid or the answers wouldn't fit anymore
+    const MIN_SIZE: usize = 1;
+    const MAX_SIZE: usize = usize::MAX;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated;
+}
+
+// Generates an input for every seed and size and panics if a known answer doesn't match, or if
+// the day doesn't have the number of parts it says
+pub fn assert_known_answers<S: Solution + Generator>(seeds: u64, sizes: &[usize]) {
+    for seed in 0..seeds {
+        for &size in sizes {
+            let generated = S::generate(&mut Rng::new(seed), size);
+            let (part1, part2) = solution::solve::<S>(&generated.input).unwrap_or_else(|err| {
+                panic!(
+                    "Generated input of size {} with seed {} doesn't parse: {}",
+                    size,
+                    seed,
+                    err.render(&generated.input)
+                )
+            });
+            assert_eq!(
+                part2.is_some(),
+                S::PARTS == 2,
+                "Day {} has {} part(s), but part 2 says otherwise",
+                S::DAY,
+                S::PARTS
+            );
+            let known = [
+                (1, generated.part1, Some(part1)),
+                (2, generated.part2, part2),
+            ];
+            for (part, expected, actual) in known.iter() {
+                if expected.is_some() {
+                    assert_eq!(
+                        expected, actual,
+                        "Part {} of size {} with seed {}",
+                        part, size, seed
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Joins items into lines, the way most inputs are laid out
+pub fn lines<T: std::fmt::Display>(items: impl IntoIterator<Item = T>) -> String {
+    let mut text = String::new();
+    for item in items {
+        writeln!(text, "{}", item).unwrap();
+    }
+    text
+}
+
+// Distinct lowercase words of the given length, like the names of ingredients or bag colors
+pub fn words(rng: &mut Rng, count: usize, len: usize) -> Vec<String> {
+    assert!(
+        (26f64).powi(len as i32) >= 2.0 * count as f64,
+        "Not enough words of length {} for {}",
+        len,
+        count
+    );
+    let mut words = std::collections::HashSet::new();
+    while words.len() < count {
+        let word: String = (0..len)
+            .map(|_| (b'a' + rng.range(0, 26) as u8) as char)
+            .collect();
+        words.insert(word);
+    }
+    let mut words: Vec<String> = words.into_iter().collect();
+    // Sets don't keep the order, so sort before shuffling to stay reproducible
+    words.sort_unstable();
+    rng.shuffle(&mut words);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distinct_words() {
+        let mut rng = Rng::new(2020);
+        let mut generated = words(&mut rng, 500, 3);
+        assert!(generated.iter().all(|word| word.len() == 3));
+        generated.sort_unstable();
+        generated.dedup();
+        assert_eq!(generated.len(), 500);
+        assert_eq!(
+            words(&mut Rng::new(1), 20, 4),
+            words(&mut Rng::new(1), 20, 4)
+        );
+    }
+
+    #[test]
+    fn build_generated() {
+        let generated = Generated::new(lines([1, 2, 3]))
+            .with_part1(6)
+            .with_part2("six");
+        assert_eq!(generated.input, "1\n2\n3\n");
+        assert_eq!(generated.part1, Some(Answer::Number(6)));
+        assert_eq!(generated.part2, Some(Answer::Text("six".to_owned())));
+    }
+
+    // Says it only has one part, but answers the second one anyway
+    struct OnePart;
+
+    impl Solution for OnePart {
+        const DAY: u32 = 25;
+        const PARTS: u32 = 1;
+
+        type Input<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<&str, crate::parse::ParseError> {
+            Ok(input)
+        }
+
+        fn part1(input: &&str) -> Answer {
+            input.len().into()
+        }
+
+        fn part2(_input: &&str) -> Option<Answer> {
+            Some(0.into())
+        }
+    }
+
+    impl Generator for OnePart {
+        const SIZE: &'static str = "letters";
+        const DEFAULT_SIZE: usize = 3;
+
+        fn generate(_rng: &mut Rng, size: usize) -> Generated {
+            Generated::new("a".repeat(size)).with_part1(size)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Day 25 has 1 part(s), but part 2 says otherwise")]
+    fn parts_have_to_agree() {
+        assert_known_answers::<OnePart>(1, &[3]);
+    }
+}
@@ -0,0 +1,479 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// Points with integer coordinates, and the directions, turns and mirror images of grid puzzles.
+// Like in `grid`, x grows to the right and y downwards, so north is (0, -1) and turning right
+// is clockwise on the screen.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+// Vector arithmetic and distances, field by field
+macro_rules! point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl $point {
+            pub const ORIGIN: $point = $point { $($field: 0),+ };
+
+            pub const fn new($($field: i64),+) -> $point {
+                $point { $($field),+ }
+            }
+
+            // Steps along the axes to get from one point to the other
+            pub fn manhattan(self, other: $point) -> u64 {
+                0 $(+ (self.$field - other.$field).unsigned_abs())+
+            }
+
+            // Steps when diagonal ones are allowed too, like a king in chess
+            pub fn chebyshev(self, other: $point) -> u64 {
+                0 $(.max((self.$field - other.$field).unsigned_abs()))+
+            }
+        }
+
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: i64) -> $point {
+                $point { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+impl Point2 {
+    // Turned by 90 degrees clockwise around the origin
+    pub fn rotate_right(self) -> Point2 {
+        Point2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(self) -> Point2 {
+        Point2::new(self.y, -self.x)
+    }
+
+    // Quarter turns to the right, negative ones turn left
+    pub fn rotate(self, quarters: i32) -> Point2 {
+        match quarters.rem_euclid(4) {
+            0 => self,
+            1 => self.rotate_right(),
+            2 => -self,
+            _ => self.rotate_left(),
+        }
+    }
+}
+
+// A point in any number of dimensions, for puzzles that only differ in that
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> PointN<N> {
+    pub const ORIGIN: PointN<N> = PointN([0; N]);
+
+    // The same value along every axis
+    pub const fn splat(value: i64) -> PointN<N> {
+        PointN([value; N])
+    }
+
+    fn zip_with(self, other: PointN<N>, f: impl Fn(i64, i64) -> i64) -> PointN<N> {
+        let mut coords = self.0;
+        for (coord, other) in coords.iter_mut().zip(other.0.iter()) {
+            *coord = f(*coord, *other);
+        }
+        PointN(coords)
+    }
+
+    pub fn manhattan(self, other: PointN<N>) -> u64 {
+        (0..N)
+            .map(|axis| (self.0[axis] - other.0[axis]).unsigned_abs())
+            .sum()
+    }
+
+    pub fn chebyshev(self, other: PointN<N>) -> u64 {
+        (0..N)
+            .map(|axis| (self.0[axis] - other.0[axis]).unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Every point p with min[i] <= p[i] < max[i] along all axes, the first axis changing fastest
+    pub fn points_between(min: PointN<N>, max: PointN<N>) -> impl Iterator<Item = PointN<N>> {
+        let empty = (0..N).any(|axis| min.0[axis] >= max.0[axis]);
+        let mut next = if empty { None } else { Some(min) };
+        std::iter::from_fn(move || {
+            let current = next?;
+            let mut point = current;
+            next = (0..N).find_map(|axis| {
+                point.0[axis] += 1;
+                if point.0[axis] < max.0[axis] {
+                    return Some(point);
+                }
+                point.0[axis] = min.0[axis];
+                None
+            });
+            Some(current)
+        })
+    }
+
+    // The 3^N - 1 points surrounding this one, diagonals included
+    pub fn neighbors(self) -> impl Iterator<Item = PointN<N>> {
+        PointN::points_between(self - PointN::splat(1), self + PointN::splat(2))
+            .filter(move |point| *point != self)
+    }
+}
+
+impl<const N: usize> Add for PointN<N> {
+    type Output = PointN<N>;
+
+    fn add(self, other: PointN<N>) -> PointN<N> {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for PointN<N> {
+    type Output = PointN<N>;
+
+    fn sub(self, other: PointN<N>) -> PointN<N> {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> Neg for PointN<N> {
+    type Output = PointN<N>;
+
+    fn neg(self) -> PointN<N> {
+        PointN::ORIGIN - self
+    }
+}
+
+impl<const N: usize> Mul<i64> for PointN<N> {
+    type Output = PointN<N>;
+
+    fn mul(self, factor: i64) -> PointN<N> {
+        self.zip_with(self, |a, _| a * factor)
+    }
+}
+
+impl<const N: usize> AddAssign for PointN<N> {
+    fn add_assign(&mut self, other: PointN<N>) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> SubAssign for PointN<N> {
+    fn sub_assign(&mut self, other: PointN<N>) {
+        *self = *self - other;
+    }
+}
+
+// The directions of the sides of a square, clockwise from the top. The discriminants count
+// quarter turns, so they can index arrays kept in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub const fn offset(self) -> Point2 {
+        match self {
+            Dir4::North => Point2::new(0, -1),
+            Dir4::East => Point2::new(1, 0),
+            Dir4::South => Point2::new(0, 1),
+            Dir4::West => Point2::new(-1, 0),
+        }
+    }
+
+    // Quarter turns to the right, negative ones turn left
+    pub fn turn(self, quarters: i32) -> Dir4 {
+        Dir4::ALL[(self as i32 + quarters).rem_euclid(4) as usize]
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        self.turn(1)
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        self.turn(-1)
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        self.turn(2)
+    }
+}
+
+// The directions to all eight surrounding cells, clockwise from the top
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub const fn offset(self) -> Point2 {
+        match self {
+            Dir8::North => Point2::new(0, -1),
+            Dir8::NorthEast => Point2::new(1, -1),
+            Dir8::East => Point2::new(1, 0),
+            Dir8::SouthEast => Point2::new(1, 1),
+            Dir8::South => Point2::new(0, 1),
+            Dir8::SouthWest => Point2::new(-1, 1),
+            Dir8::West => Point2::new(-1, 0),
+            Dir8::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    // Quarter turns to the right, negative ones turn left
+    pub fn turn(self, quarters: i32) -> Dir8 {
+        Dir8::ALL[(self as i32 + 2 * quarters).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Dir8 {
+        self.turn(1)
+    }
+
+    pub fn turn_left(self) -> Dir8 {
+        self.turn(-1)
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        self.turn(2)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Dir8 {
+        Dir8::ALL[2 * dir as usize]
+    }
+}
+
+// One of the eight ways to turn and mirror a square onto itself, the dihedral group D4. It
+// mirrors left to right first if `flipped`, then turns right `quarters` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symmetry {
+    quarters: u8,
+    flipped: bool,
+}
+
+impl Symmetry {
+    pub const IDENTITY: Symmetry = Symmetry::new(0, false);
+    pub const FLIP_HORIZONTAL: Symmetry = Symmetry::new(0, true);
+    pub const FLIP_VERTICAL: Symmetry = Symmetry::new(2, true);
+
+    // The turns first, then the same turns after mirroring
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::new(0, false),
+        Symmetry::new(1, false),
+        Symmetry::new(2, false),
+        Symmetry::new(3, false),
+        Symmetry::new(0, true),
+        Symmetry::new(1, true),
+        Symmetry::new(2, true),
+        Symmetry::new(3, true),
+    ];
+
+    pub const fn new(quarters: u8, flipped: bool) -> Symmetry {
+        Symmetry {
+            quarters: quarters % 4,
+            flipped,
+        }
+    }
+
+    // Quarter turns to the right, negative ones turn left
+    pub const fn rotation(quarters: i32) -> Symmetry {
+        Symmetry::new(quarters.rem_euclid(4) as u8, false)
+    }
+
+    pub fn quarters(self) -> u8 {
+        self.quarters
+    }
+
+    pub fn is_flipped(self) -> bool {
+        self.flipped
+    }
+
+    // This one followed by `next`. Mirroring reverses the turns made before it.
+    pub fn then(self, next: Symmetry) -> Symmetry {
+        if next.flipped {
+            Symmetry::new((4 + next.quarters - self.quarters) % 4, !self.flipped)
+        } else {
+            Symmetry::new(self.quarters + next.quarters, self.flipped)
+        }
+    }
+
+    // Undoes this one, mirror images are their own inverse
+    pub fn inverse(self) -> Symmetry {
+        if self.flipped {
+            self
+        } else {
+            Symmetry::new(4 - self.quarters, false)
+        }
+    }
+
+    // Applied around the origin
+    pub fn apply(self, point: Point2) -> Point2 {
+        let point = if self.flipped {
+            Point2::new(-point.x, point.y)
+        } else {
+            point
+        };
+        point.rotate(self.quarters as i32)
+    }
+
+    pub fn apply_dir(self, dir: Dir4) -> Dir4 {
+        let dir = match (self.flipped, dir) {
+            (true, Dir4::East) => Dir4::West,
+            (true, Dir4::West) => Dir4::East,
+            _ => dir,
+        };
+        dir.turn(self.quarters as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let a = Point2::new(3, -4);
+        assert_eq!(a + Point2::new(1, 1), Point2::new(4, -3));
+        assert_eq!(a * 2 - a, a);
+        assert_eq!(a.manhattan(Point2::ORIGIN), 7);
+        assert_eq!(a.chebyshev(Point2::new(-1, -1)), 4);
+        assert_eq!(a.rotate_right(), Point2::new(4, 3));
+        assert_eq!(a.rotate(-1), a.rotate_left());
+        assert_eq!(a.rotate(6), -a);
+
+        let b = Point3::new(1, -2, 3);
+        assert_eq!(b.manhattan(Point3::ORIGIN), 6);
+        assert_eq!((b + b).chebyshev(-b), 9);
+        let c = PointN([1, -2, 3, 4]);
+        assert_eq!(c.manhattan(PointN::ORIGIN), 10);
+        assert_eq!((c * 2 - c).chebyshev(PointN::splat(1)), 3);
+    }
+
+    #[test]
+    fn points_between_and_neighbors() {
+        let points: Vec<PointN<2>> =
+            PointN::points_between(PointN([0, 5]), PointN([2, 7])).collect();
+        assert_eq!(
+            points,
+            vec![
+                PointN([0, 5]),
+                PointN([1, 5]),
+                PointN([0, 6]),
+                PointN([1, 6])
+            ]
+        );
+        assert_eq!(
+            PointN::points_between(PointN([0, 0]), PointN([3, 0])).count(),
+            0
+        );
+        assert_eq!(PointN::<3>::ORIGIN.neighbors().count(), 26);
+        assert!(PointN([2, 2, 2, 2])
+            .neighbors()
+            .all(|point| point.chebyshev(PointN::splat(2)) == 1));
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Dir4::North.turn_right(), Dir4::East);
+        assert_eq!(Dir4::North.turn_left(), Dir4::West);
+        assert_eq!(Dir4::West.turn(5), Dir4::North);
+        assert_eq!(Dir4::South.reverse(), Dir4::North);
+        assert_eq!(Dir8::NorthWest.turn_right(), Dir8::NorthEast);
+        assert_eq!(Dir8::from(Dir4::South), Dir8::South);
+        for dir in Dir8::ALL.iter() {
+            assert_eq!(dir.offset().chebyshev(Point2::ORIGIN), 1);
+            assert_eq!(dir.turn_right().offset(), dir.offset().rotate_right());
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+    }
+
+    #[test]
+    fn symmetry_group() {
+        let corner = Point2::new(2, 1);
+        let images: Vec<Point2> = Symmetry::ALL.iter().map(|s| s.apply(corner)).collect();
+        for (idx, image) in images.iter().enumerate() {
+            assert!(!images[..idx].contains(image));
+        }
+        assert_eq!(Symmetry::FLIP_VERTICAL.apply(corner), Point2::new(2, -1));
+
+        for a in Symmetry::ALL.iter() {
+            assert_eq!(a.then(a.inverse()), Symmetry::IDENTITY);
+            for dir in Dir4::ALL.iter() {
+                assert_eq!(a.apply_dir(*dir).offset(), a.apply(dir.offset()));
+            }
+            for b in Symmetry::ALL.iter() {
+                assert_eq!(a.then(*b).apply(corner), b.apply(a.apply(corner)));
+            }
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::VecDeque;
+
+// Directed graph with its nodes and edges kept in vectors and referred to by index. Nodes
+// pointing at each other through references don't get past the borrow checker, indices do.
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    // Along the edges, from a node to its successors
+    Outgoing,
+    // Against the edges, from a node to its predecessors
+    Incoming,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edge<E> {
+    from: NodeId,
+    to: NodeId,
+    weight: E,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+    // Indices into `edges` for every node
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Graph<N, E> {
+        Graph::default()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        self.nodes.push(node);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "Edge {} -> {} between unknown nodes!",
+            from,
+            to
+        );
+        self.outgoing[from].push(self.edges.len());
+        self.incoming[to].push(self.edges.len());
+        self.edges.push(Edge { from, to, weight });
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    // The nodes next to `id` in the given direction, with the weights of the edges to them
+    pub fn neighbors(
+        &self,
+        id: NodeId,
+        direction: Direction,
+    ) -> impl Iterator<Item = (NodeId, &E)> + '_ {
+        let edges = match direction {
+            Direction::Outgoing => &self.outgoing[id],
+            Direction::Incoming => &self.incoming[id],
+        };
+        edges.iter().map(move |&edge| {
+            let edge = &self.edges[edge];
+            match direction {
+                Direction::Outgoing => (edge.to, &edge.weight),
+                Direction::Incoming => (edge.from, &edge.weight),
+            }
+        })
+    }
+
+    pub fn successors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> + '_ {
+        self.neighbors(id, Direction::Outgoing)
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> + '_ {
+        self.neighbors(id, Direction::Incoming)
+    }
+
+    // Every node reachable from `start`, closest ones first, with its distance in edges
+    pub fn bfs(&self, start: NodeId, direction: Direction) -> Vec<(NodeId, usize)> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::new();
+        let mut order = Vec::new();
+        seen[start] = true;
+        queue.push_back((start, 0));
+        while let Some((id, distance)) = queue.pop_front() {
+            order.push((id, distance));
+            for (next, _) in self.neighbors(id, direction) {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        order
+    }
+
+    // Every node reachable from `start`, in the order a depth first search enters them
+    pub fn dfs(&self, start: NodeId, direction: Direction) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+            // Reversed, so the first neighbor is entered first
+            let neighbors: Vec<NodeId> = self
+                .neighbors(id, direction)
+                .map(|(next, _)| next)
+                .collect();
+            stack.extend(neighbors.into_iter().rev().filter(|next| !seen[*next]));
+        }
+        order
+    }
+
+    // All nodes with every edge going from an earlier node to a later one, None with a cycle
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        let mut waiting: Vec<usize> = self.incoming.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<NodeId> = (0..self.len()).filter(|&id| waiting[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for (next, _) in self.successors(id) {
+                waiting[next] -= 1;
+                if waiting[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    // Only the nodes reachable from `start`, ordered like `topological_order`, None if those
+    // contain a cycle, cycles elsewhere don't matter
+    pub fn topological_order_from(&self, start: NodeId) -> Option<Vec<NodeId>> {
+        let reachable = self.dfs(start, Direction::Outgoing);
+        let mut inside = vec![false; self.len()];
+        for &id in reachable.iter() {
+            inside[id] = true;
+        }
+        let mut waiting = vec![0; self.len()];
+        for &id in reachable.iter() {
+            waiting[id] = self
+                .predecessors(id)
+                .filter(|(prev, _)| inside[*prev])
+                .count();
+        }
+        let mut ready: VecDeque<NodeId> = reachable
+            .iter()
+            .copied()
+            .filter(|&id| waiting[id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(reachable.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for (next, _) in self.successors(id) {
+                waiting[next] -= 1;
+                if waiting[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        if order.len() == reachable.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    // The nodes of some cycle in the order the edges connect them, if there is one
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        for root in 0..self.len() {
+            if state[root] != State::New {
+                continue;
+            }
+            // The current path with how many successors of each node were looked at so far
+            let mut path: Vec<(NodeId, usize)> = vec![(root, 0)];
+            state[root] = State::OnPath;
+            while let Some((id, checked)) = path.last_mut() {
+                let id = *id;
+                match self.outgoing[id].get(*checked) {
+                    Some(&edge) => {
+                        *checked += 1;
+                        let next = self.edges[edge].to;
+                        match state[next] {
+                            State::New => {
+                                state[next] = State::OnPath;
+                                path.push((next, 0));
+                            }
+                            State::OnPath => {
+                                let start = path.iter().position(|(id, _)| *id == next).unwrap();
+                                return Some(path[start..].iter().map(|(id, _)| *id).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[id] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+// A maximum matching in a bipartite graph, the left nodes have the candidates they can be
+// matched with on the right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matching {
+    pub left: Vec<Option<usize>>,
+    pub right: Vec<Option<usize>>,
+}
+
+impl Matching {
+    pub fn size(&self) -> usize {
+        self.left.iter().flatten().count()
+    }
+
+    // Every node on either side has a partner
+    pub fn is_perfect(&self) -> bool {
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .all(Option::is_some)
+    }
+
+    // Whether no other matching of the same size exists. That one would come from switching
+    // partners around a cycle, or from a node moving to a partner nobody has.
+    pub fn is_unique(&self, candidates: &[Vec<usize>]) -> bool {
+        let mut swaps: Graph<(), ()> = Graph::new();
+        for _ in 0..self.right.len() {
+            swaps.add_node(());
+        }
+        for (left, options) in candidates.iter().enumerate() {
+            for &right in options.iter() {
+                match (self.left[left], self.right[right]) {
+                    (Some(partner), _) if partner == right => {}
+                    // The partner of `left` could be given up for `right`
+                    (Some(partner), Some(_)) => swaps.add_edge(partner, right, ()),
+                    _ => return false,
+                }
+            }
+        }
+        swaps.find_cycle().is_none()
+    }
+}
+
+// Kuhn's algorithm, which looks for a path to a free partner from every left node in turn
+pub fn max_matching(candidates: &[Vec<usize>], right_len: usize) -> Matching {
+    fn augment(
+        left: usize,
+        candidates: &[Vec<usize>],
+        visited: &mut [bool],
+        matching: &mut Matching,
+    ) -> bool {
+        for &right in candidates[left].iter() {
+            if visited[right] {
+                continue;
+            }
+            visited[right] = true;
+            let free = match matching.right[right] {
+                Some(other) => augment(other, candidates, visited, matching),
+                None => true,
+            };
+            if free {
+                matching.left[left] = Some(right);
+                matching.right[right] = Some(left);
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut matching = Matching {
+        left: vec![None; candidates.len()],
+        right: vec![None; right_len],
+    };
+    for left in 0..candidates.len() {
+        let mut visited = vec![false; right_len];
+        augment(left, candidates, &mut visited, &mut matching);
+    }
+    matching
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -> b -> c -> d, a -> c and a lone e
+    fn small_graph() -> Graph<char, u32> {
+        let mut graph = Graph::new();
+        for name in "abcde".chars() {
+            graph.add_node(name);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 2);
+        graph.add_edge(2, 3, 3);
+        graph.add_edge(0, 2, 4);
+        graph
+    }
+
+    #[test]
+    fn traverse() {
+        let graph = small_graph();
+        assert_eq!(graph.len(), 5);
+        assert_eq!(
+            graph.successors(0).collect::<Vec<_>>(),
+            vec![(1, &1), (2, &4)]
+        );
+        assert_eq!(
+            graph.predecessors(2).collect::<Vec<_>>(),
+            vec![(1, &2), (0, &4)]
+        );
+        assert_eq!(
+            graph.bfs(0, Direction::Outgoing),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2)]
+        );
+        assert_eq!(graph.dfs(0, Direction::Outgoing), vec![0, 1, 2, 3]);
+        assert_eq!(graph.dfs(3, Direction::Incoming), vec![3, 2, 1, 0]);
+        assert_eq!(graph.bfs(4, Direction::Incoming), vec![(4, 0)]);
+    }
+
+    #[test]
+    fn order_and_cycles() {
+        let mut graph = small_graph();
+        assert_eq!(graph.topological_order(), Some(vec![0, 4, 1, 2, 3]));
+        assert_eq!(graph.find_cycle(), None);
+        assert_eq!(graph.topological_order_from(1), Some(vec![1, 2, 3]));
+
+        graph.add_edge(3, 1, 5);
+        assert_eq!(graph.topological_order(), None);
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 3]));
+        assert_eq!(graph.topological_order_from(1), None);
+        assert_eq!(graph.topological_order_from(4), Some(vec![4]));
+        graph.add_edge(4, 4, 6);
+        assert!(graph.find_cycle().is_some());
+    }
+
+    #[test]
+    fn matchings() {
+        // Only one way to pair them up, found by moving the first left node along
+        let candidates = vec![vec![0, 1], vec![0], vec![1, 2]];
+        let matching = max_matching(&candidates, 3);
+        assert_eq!(matching.left, vec![Some(1), Some(0), Some(2)]);
+        assert!(matching.is_perfect() && matching.is_unique(&candidates));
+
+        // Two ways around the cycle
+        let candidates = vec![vec![0, 1], vec![0, 1]];
+        let matching = max_matching(&candidates, 2);
+        assert!(matching.is_perfect() && !matching.is_unique(&candidates));
+
+        // The last left node has nobody left, the right one is free
+        let candidates = vec![vec![0], vec![0], vec![]];
+        let matching = max_matching(&candidates, 2);
+        assert_eq!(matching.size(), 1);
+        assert!(!matching.is_perfect() && !matching.is_unique(&candidates));
+        let candidates = vec![vec![0], vec![1]];
+        assert!(max_matching(&candidates, 3).is_unique(&candidates));
+    }
+}
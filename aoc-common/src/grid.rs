@@ -0,0 +1,397 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Point2, Symmetry};
+use crate::parse::ParseError;
+
+// Offsets to the 4 neighbours sharing a side, and to all 8 surrounding ones in reading order
+pub const DIRECTIONS4: [Point2; 4] = [
+    Point2::new(0, -1),
+    Point2::new(1, 0),
+    Point2::new(0, 1),
+    Point2::new(-1, 0),
+];
+pub const DIRECTIONS8: [Point2; 8] = [
+    Point2::new(-1, -1),
+    Point2::new(0, -1),
+    Point2::new(1, -1),
+    Point2::new(-1, 0),
+    Point2::new(1, 0),
+    Point2::new(-1, 1),
+    Point2::new(0, 1),
+    Point2::new(1, 1),
+];
+
+// Rectangular grid stored row by row. Positions are (x, y), with x the column and y the row,
+// both starting at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid2D<T> {
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid2D<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {}!",
+            cells.len(),
+            width
+        );
+        Grid2D {
+            height: cells.len() / width,
+            width,
+            cells,
+        }
+    }
+
+    // Parses one row per line of `text`, which has to be a slice of `input` for the errors.
+    // `cell` turns a character into a cell, or None if it doesn't belong in the grid.
+    pub fn parse(
+        input: &str,
+        text: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid2D<T>, ParseError> {
+        let text = text.trim();
+        let width = text.lines().next().unwrap_or("").trim().chars().count();
+        if width == 0 {
+            return Err(ParseError::at(input, text, "a row of the grid"));
+        }
+
+        let mut cells = Vec::with_capacity(width * text.lines().count());
+        for line in text.lines() {
+            let line = line.trim();
+            if line.chars().count() != width {
+                let expected = format!("a row of {} cells", width);
+                return Err(ParseError::at(input, line, expected));
+            }
+            for (idx, ch) in line.char_indices() {
+                let value = cell(ch).ok_or_else(|| {
+                    ParseError::at(input, &line[idx..idx + ch.len_utf8()], expected)
+                })?;
+                cells.push(value);
+            }
+        }
+        Ok(Grid2D::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // As if the grid repeated itself endlessly in every direction
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self[(x, y)] = value;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    // Every cell together with its position
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    // Neighbours with the given offsets, leaving out those outside of the grid
+    pub fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'a [Point2],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        directions.iter().filter_map(move |step| {
+            let (x, y) = (x as isize + step.x as isize, y as isize + step.y as isize);
+            if self.contains(x, y) {
+                Some((x as usize, y as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &DIRECTIONS4)
+    }
+
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &DIRECTIONS8)
+    }
+
+    // Positions seen when looking from (x, y) in one direction, up to the edge of the grid
+    pub fn line_of_sight(
+        &self,
+        x: usize,
+        y: usize,
+        step: Point2,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let (dx, dy) = (step.x as isize, step.y as isize);
+        let mut position = (x as isize, y as isize);
+        std::iter::from_fn(move || {
+            position = (position.0 + dx, position.1 + dy);
+            if (dx, dy) != (0, 0) && self.contains(position.0, position.1) {
+                Some((position.0 as usize, position.1 as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid2D<U> {
+        Grid2D {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid2D<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid2D<T> {
+        Grid2D::from_vec(width, vec![value; width * height])
+    }
+
+    // Builds a grid of the given size, taking every cell from the position `f` picks in this one
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid2D<T> {
+        let cells = (0..width * height)
+            .map(|idx| self[f(idx % width, idx / width)].clone())
+            .collect();
+        Grid2D::from_vec(width, cells)
+    }
+
+    // Rotated by 90 degrees clockwise
+    pub fn rotate_right(&self) -> Grid2D<T> {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    // Rotated by 90 degrees counterclockwise
+    pub fn rotate_left(&self) -> Grid2D<T> {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    // Mirrored left to right, every row reversed
+    pub fn flip_horizontal(&self) -> Grid2D<T> {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    // Upside down, the rows in reverse order
+    pub fn flip_vertical(&self) -> Grid2D<T> {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+
+    // Mirrored along the diagonal from the top left, rows become columns
+    pub fn transpose(&self) -> Grid2D<T> {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    // Mirrored and turned the way `symmetry` does it with points
+    pub fn transform(&self, symmetry: Symmetry) -> Grid2D<T> {
+        let mut grid = if symmetry.is_flipped() {
+            self.flip_horizontal()
+        } else {
+            self.clone()
+        };
+        for _ in 0..symmetry.quarters() {
+            grid = grid.rotate_right();
+        }
+        grid
+    }
+
+    // The part of the grid with its top left corner at (x, y)
+    pub fn sub_grid(&self, x: usize, y: usize, width: usize, height: usize) -> Grid2D<T> {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "Sub-grid doesn't fit into the grid!"
+        );
+        self.remap(width, height, |sub_x, sub_y| (x + sub_x, y + sub_y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the grid!",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the grid!",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 123
+    // 456
+    fn numbers() -> Grid2D<u32> {
+        Grid2D::from_vec(3, vec![1, 2, 3, 4, 5, 6])
+    }
+
+    fn chars(input: &str) -> Grid2D<char> {
+        Grid2D::parse(input, input, "a character", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_grid() {
+        let input = "#.#\n..#\n";
+        let grid = Grid2D::parse(input, input, "'.' or '#'", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[(2, 1)]);
+        assert_eq!(
+            grid.map(|cell| if *cell { '#' } else { '.' }).to_string(),
+            input
+        );
+
+        let input = "#.#\n.x#\n";
+        let err = Grid2D::parse(input, input, "'.' or '#'", |ch| {
+            Some(ch).filter(|ch| *ch != 'x')
+        })
+        .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 2, "'.' or '#'")
+        );
+        let err = Grid2D::parse("#.#\n.#", "#.#\n.#", "", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert!(Grid2D::parse("\n", "\n", "", Some).is_err());
+    }
+
+    #[test]
+    fn access() {
+        let mut grid = numbers();
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(*grid.get_wrapping(4, -1), 5);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec![2, 5]);
+        grid.set(0, 1, 9);
+        *grid.get_mut(1, 1).unwrap() += 1;
+        assert_eq!(grid.to_string(), "123\n966\n");
+        assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &6)));
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = numbers();
+        let sides: Vec<(usize, usize)> = grid.neighbors4(0, 0).collect();
+        assert_eq!(sides, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8(1, 1).count(), 5);
+        let sight: Vec<(usize, usize)> = grid.line_of_sight(0, 0, Point2::new(1, 0)).collect();
+        assert_eq!(sight, vec![(1, 0), (2, 0)]);
+        assert_eq!(grid.line_of_sight(0, 0, Point2::new(1, 1)).count(), 1);
+        assert_eq!(grid.line_of_sight(0, 0, Point2::ORIGIN).count(), 0);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = numbers();
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(grid.flip_vertical().to_string(), "456\n123\n");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.transform(Symmetry::rotation(1)), grid.rotate_right());
+        assert_eq!(
+            grid.transform(Symmetry::FLIP_VERTICAL),
+            grid.flip_vertical()
+        );
+        assert_eq!(
+            grid.transform(Symmetry::new(1, true)),
+            grid.flip_horizontal().rotate_right()
+        );
+        assert_eq!(grid.sub_grid(1, 0, 2, 2).to_string(), "23\n56\n");
+        assert_eq!(
+            chars("ab\ncd")
+                .map(|ch| ch.to_ascii_uppercase())
+                .to_string(),
+            "AB\nCD\n"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub const INPUT_USAGE: &str = "Input options:
+  --input <path>  Read the puzzle input from <path>, or from stdin if <path> is '-'
+  --simple        Use the sample input from inputs/simple/ instead of the full one";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // Reads the whole input, normalized so every day sees the same text however it was saved
+    pub fn read(&self) -> Result<String, String> {
+        let result = match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+        result
+            .map(|raw| normalize(&raw))
+            .map_err(|err| format!("Could not read input {}: {}", self, err))
+    }
+}
+
+// Inputs as the days expect them: '\n' line endings, no whitespace at the end of a line, no blank
+// lines at the start or the end, a single blank line between paragraphs and a final newline.
+// Files saved on Windows or with a byte order mark read the same as any other.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut normalized = String::with_capacity(raw.len());
+    let mut after_blank = false;
+    for line in raw.replace("\r\n", "\n").split(['\n', '\r']) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            after_blank = !normalized.is_empty();
+            continue;
+        }
+        if after_blank {
+            normalized.push('\n');
+            after_blank = false;
+        }
+        normalized.push_str(line);
+        normalized.push('\n');
+    }
+    normalized
+}
+
+// Splits an input into paragraphs separated by one or more blank lines, lines with nothing but
+// whitespace count as blank too. Paragraphs are slices of the input without their final line
+// break, so parse errors still point into the input.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content.trim_start().is_empty() {
+            if let Some(start) = start.take() {
+                paragraphs.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        paragraphs.push(&input[start..end]);
+    }
+    paragraphs.into_iter()
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+// Input related command line flags, shared by the day binaries and the runner
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputOptions {
+    pub input: Option<String>,
+    pub simple: bool,
+}
+
+impl InputOptions {
+    // Takes the input flags out of the arguments and returns the remaining ones untouched
+    pub fn extract(args: &[String]) -> Result<(InputOptions, Vec<String>), String> {
+        let mut options = InputOptions::default();
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().ok_or("Missing path after --input!")?;
+                    options.input = Some(path.to_owned());
+                }
+                "--simple" => options.simple = true,
+                _ => rest.push(arg.to_owned()),
+            }
+        }
+
+        if options.input.is_some() && options.simple {
+            return Err("Only one of --input and --simple can be used!".to_owned());
+        }
+        Ok((options, rest))
+    }
+
+    pub fn source(&self, day: u32) -> InputSource {
+        match self.input.as_deref() {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::File(default_path(day, self.simple)),
+        }
+    }
+}
+
+pub fn default_path(day: u32, simple: bool) -> PathBuf {
+    let file_name = format!("day-{:02}.txt", day);
+    if simple {
+        inputs_dir().join("simple").join(file_name)
+    } else {
+        inputs_dir().join(file_name)
+    }
+}
+
+// Path of a named input: "full", "simple", or "full-<name>" and "simple-<name>" for extra inputs
+// some days have next to the usual ones, e.g. "simple-small" is inputs/simple/day-10-small.txt
+pub fn named_path(day: u32, name: &str) -> Option<PathBuf> {
+    let (simple, extra) = match name.split_once('-') {
+        Some((kind, extra)) if !extra.is_empty() => (kind, Some(extra)),
+        Some(_) => return None,
+        None => (name, None),
+    };
+    let simple = match simple {
+        "full" => false,
+        "simple" => true,
+        _ => return None,
+    };
+    let path = default_path(day, simple);
+    Some(match extra {
+        Some(extra) => path.with_file_name(format!("day-{:02}-{}.txt", day, extra)),
+        None => path,
+    })
+}
+
+// Inputs are looked up in the working directory first and fall back to the workspace's
+// inputs folder, so the binaries also work when run from a day's directory.
+pub fn inputs_dir() -> PathBuf {
+    let local = PathBuf::from("inputs");
+    if local.is_dir() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs")
+    }
+}
+
+// Loads the input for a day binary according to its command line, or exits with a message
+pub fn load(day: u32) -> String {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = InputOptions::extract(&args).and_then(|(options, rest)| {
+        if let Some(arg) = rest.first() {
+            return Err(format!("Unknown argument '{}'\n\n{}", arg, INPUT_USAGE));
+        }
+        options.source(day).read()
+    });
+
+    match result {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn default_paths() {
+        assert!(default_path(7, false).ends_with("inputs/day-07.txt"));
+        assert!(default_path(25, true).ends_with("inputs/simple/day-25.txt"));
+    }
+
+    #[test]
+    fn named_paths() {
+        assert_eq!(named_path(7, "full"), Some(default_path(7, false)));
+        assert_eq!(named_path(7, "simple"), Some(default_path(7, true)));
+        let small = named_path(10, "simple-small").unwrap();
+        assert!(small.ends_with("inputs/simple/day-10-small.txt"));
+        assert_eq!(named_path(10, "simple-"), None);
+        assert_eq!(named_path(10, "sample"), None);
+    }
+
+    #[test]
+    fn extract_options() {
+        let (options, rest) = InputOptions::extract(&args(&["run", "--simple", "7"])).unwrap();
+        assert!(options.simple);
+        assert_eq!(rest, args(&["run", "7"]));
+        assert_eq!(options.source(7), InputSource::File(default_path(7, true)));
+
+        let (options, _) = InputOptions::extract(&args(&["--input", "my.txt"])).unwrap();
+        assert_eq!(
+            options.source(7),
+            InputSource::File(PathBuf::from("my.txt"))
+        );
+
+        let (options, _) = InputOptions::extract(&args(&["--input", "-"])).unwrap();
+        assert_eq!(options.source(7), InputSource::Stdin);
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!(InputOptions::extract(&args(&["--input"])).is_err());
+        assert!(InputOptions::extract(&args(&["--input", "a.txt", "--simple"])).is_err());
+    }
+
+    #[test]
+    fn normalize_inputs() {
+        assert_eq!(normalize("a\r\nb\r\n\r\nc\r\n"), "a\nb\n\nc\n");
+        assert_eq!(normalize("\u{feff}\n\na  \nb\t\n\n \n\nc"), "a\nb\n\nc\n");
+        assert_eq!(normalize("a\rb\n\n\n"), "a\nb\n");
+        assert_eq!(normalize("  indented\n"), "  indented\n");
+        assert_eq!(normalize("\r\n \n"), "");
+    }
+
+    #[test]
+    fn split_paragraphs() {
+        let input = "\na\nb\n\n\n c \r\n  \r\nd";
+        let split: Vec<&str> = paragraphs(input).collect();
+        assert_eq!(split, vec!["a\nb", " c", "d"]);
+        // Slices of the input, so they can be located in it
+        assert_eq!(split[1].as_ptr(), input[7..].as_ptr());
+        assert_eq!(paragraphs("").count(), 0);
+        assert_eq!(paragraphs("a\n\nb\n").collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn crlf_inputs_read_the_same() {
+        let dirs = [inputs_dir(), inputs_dir().join("simple")];
+        for path in dirs.iter().flat_map(|dir| fs::read_dir(dir).unwrap()) {
+            let path = path.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+            let text = fs::read_to_string(&path).unwrap();
+            let crlf = text.replace('\n', "\r\n");
+            assert_eq!(normalize(&crlf), normalize(&text), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn read_default_input() {
+        let input = InputSource::File(default_path(1, false)).read().unwrap();
+        assert!(!input.trim().is_empty());
+        assert!(InputSource::File(PathBuf::from("missing.txt"))
+            .read()
+            .is_err());
+    }
+}
@@ -0,0 +1,16 @@
+// Code shared between the runner and the separate days
+pub mod answers;
+pub mod cancel;
+pub mod differential;
+pub mod frames;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod math;
+pub mod params;
+pub mod parse;
+pub mod rng;
+pub mod simulation;
+pub mod solution;
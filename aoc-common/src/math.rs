@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+// Number theory for puzzles working modulo something. Moduli are u64, products go through
+// u128 so they can't overflow.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Greatest common divisor with the Bézout coefficients: (g, x, y) where a * x + b * y = g
+pub fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    (old_r, old_x, old_y)
+}
+
+// The x with a * x ≡ 1 (mod modulus), if a and the modulus are coprime
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    let (g, x, _) = egcd(a as i128, modulus as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(modulus as i128) as u64)
+}
+
+pub fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+pub fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+// Solves x ≡ residue (mod modulus) for all the given pairs at once. Returns the smallest such x
+// with the modulus all solutions repeat at, which is the lcm of the moduli. The moduli don't
+// have to be coprime. None if the congruences contradict each other or the lcm overflows.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences.iter().try_fold(
+        (0, 1),
+        |(residue, modulus), &(next_residue, next_modulus)| {
+            let next_residue = next_residue % next_modulus;
+            let g = gcd(modulus, next_modulus);
+            let diff = next_residue as i128 - residue as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            // residue + modulus * k ≡ next_residue (mod next_modulus), solved for k
+            let step = next_modulus / g;
+            let diff = (diff / g as i128).rem_euclid(step as i128) as u64;
+            let k = mul_mod(diff, mod_inverse(modulus / g % step, step)?, step);
+            let lcm = modulus.checked_mul(step)?;
+            let solution = (residue as u128 + modulus as u128 * k as u128) % lcm as u128;
+            Some((solution as u64, lcm))
+        },
+    )
+}
+
+// The smallest x with base^x ≡ target (mod modulus), found with baby-step giant-step in about
+// sqrt(modulus) steps and memory. Works for moduli that share factors with the base too.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 1 {
+        return Some(0);
+    }
+    let (base, mut target, mut modulus) = (base % modulus, target % modulus, modulus);
+
+    // Divide out the common factors first, the few exponents this skips are checked on the way
+    let mut offset = 0;
+    let mut factor = 1 % modulus;
+    loop {
+        let g = gcd(base, modulus);
+        if g == 1 {
+            break;
+        }
+        if target == factor {
+            return Some(offset);
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        modulus /= g;
+        offset += 1;
+        factor = mul_mod(factor, base / g, modulus);
+    }
+
+    // factor * base^(n * p - q) ≡ target, so factor * (base^n)^p ≡ target * base^q
+    let n = (modulus as f64).sqrt() as u64 + 1;
+    let mut baby_steps = HashMap::new();
+    let mut value = target;
+    for q in 0..=n {
+        // Later steps overwrite earlier ones, the largest q gives the smallest exponent
+        baby_steps.insert(value, q);
+        value = mul_mod(value, base, modulus);
+    }
+    let giant_step = pow_mod(base, n, modulus);
+    let mut value = factor;
+    for p in 1..=n {
+        value = mul_mod(value, giant_step, modulus);
+        if let Some(q) = baby_steps.get(&value) {
+            return Some(n * p - q + offset);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcds_and_inverses() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(17, 5), 1);
+        let (g, x, y) = egcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+    }
+
+    #[test]
+    fn large_products() {
+        let big = u64::MAX - 58; // The largest prime below 2^64
+        assert_eq!(mul_mod(big - 1, big - 1, big), 1);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+        // Fermat's little theorem
+        assert_eq!(pow_mod(123_456_789, big - 1, big), 1);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // 10 and 4 share a factor, so the solutions repeat every 20 instead of 40
+        assert_eq!(crt(&[(3, 10), (1, 4)]), Some((13, 20)));
+        assert_eq!(crt(&[(3, 10), (2, 4)]), None);
+        assert_eq!(crt(&[(1, 1 << 40), (0, 3 << 30)]), None);
+        assert_eq!(crt(&[(1, 1 << 40), (1, 3 << 30)]), Some((1, 3 << 40)));
+        assert_eq!(crt(&[(0, 1 << 40), (0, 3 << 40)]), Some((0, 3 << 40)));
+        // The lcm doesn't fit
+        assert_eq!(crt(&[(0, 1 << 40), (0, (1 << 40) - 1)]), None);
+    }
+
+    #[test]
+    fn discrete_logs_match_brute_force() {
+        for modulus in 1..50 {
+            for base in 0..modulus {
+                for target in 0..modulus {
+                    let expected =
+                        (0..2 * modulus).find(|&x| pow_mod(base, x, modulus) == target % modulus);
+                    assert_eq!(
+                        discrete_log(base, target, modulus),
+                        expected,
+                        "{}^x = {} mod {}",
+                        base,
+                        target,
+                        modulus
+                    );
+                }
+            }
+        }
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+// A puzzle constant a day lets the runner override with `--param <name>=<value>`
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+    // Rejects values the solver can't work with, with a message saying why
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+// Name and value of a parameter given on the command line
+pub type Override = (String, String);
+
+// Values of the parameters a day declares, the defaults unless they were overridden
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Params {
+    pub fn defaults(declared: &'static [Param]) -> Params {
+        Params {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_owned()))
+                .collect(),
+        }
+    }
+
+    // Every override has to name a declared parameter and pass its check
+    pub fn with_overrides(
+        declared: &'static [Param],
+        overrides: &[Override],
+    ) -> Result<Params, String> {
+        let mut params = Params::defaults(declared);
+        for (name, value) in overrides {
+            let param = declared
+                .iter()
+                .find(|param| param.name == name)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = declared.iter().map(|param| param.name).collect();
+                    if names.is_empty() {
+                        format!("Unknown parameter '{}', there are none!", name)
+                    } else {
+                        format!(
+                            "Unknown parameter '{}', expected {}!",
+                            name,
+                            names.join(", ")
+                        )
+                    }
+                })?;
+            (param.check)(value)
+                .map_err(|err| format!("Invalid value '{}' for {}: {}", value, name, err))?;
+            let entry = params
+                .values
+                .iter_mut()
+                .find(|(declared, _)| declared == name)
+                .expect("Parameter is declared");
+            entry.1 = value.to_owned();
+        }
+        Ok(params)
+    }
+
+    pub fn value(&self, name: &str) -> &str {
+        self.values
+            .iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_else(|| panic!("Parameter {} is not declared!", name))
+    }
+
+    // Values were checked when the parameters were built, so failing here is a bug in the day
+    pub fn get<T: FromStr>(&self, name: &str) -> T {
+        let value = self.value(name);
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid value '{}' for {}!", value, name))
+    }
+}
+
+// Parses `<name>=<value>` as given to `--param`
+pub fn parse_override(arg: &str) -> Result<Override, String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(format!(
+            "Invalid parameter '{}', expected <name>=<value>!",
+            arg
+        )),
+    }
+}
+
+// Check for parameters that are a number in an inclusive range
+pub fn between<T: FromStr + PartialOrd + Display>(
+    value: &str,
+    min: T,
+    max: T,
+) -> Result<(), String> {
+    let number: T = value.parse().map_err(|_| "not a number!".to_owned())?;
+    if number < min || number > max {
+        return Err(format!("has to be from {} to {}!", min, max));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param {
+            name: "turns",
+            default: "2020",
+            description: "Turns to play",
+            check: |value| between(value, 1usize, 1_000_000),
+        },
+        Param {
+            name: "name",
+            default: "elf",
+            description: "Anything goes",
+            check: |_| Ok(()),
+        },
+    ];
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<Override> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults_and_overrides() {
+        let params = Params::defaults(DECLARED);
+        assert_eq!(params.get::<usize>("turns"), 2020);
+        assert_eq!(params.value("name"), "elf");
+
+        let params = Params::with_overrides(DECLARED, &overrides(&[("turns", "7")])).unwrap();
+        assert_eq!(params.get::<usize>("turns"), 7);
+        assert_eq!(params.value("name"), "elf");
+    }
+
+    #[test]
+    fn invalid_overrides() {
+        let err = Params::with_overrides(DECLARED, &overrides(&[("rounds", "7")])).unwrap_err();
+        assert_eq!(err, "Unknown parameter 'rounds', expected turns, name!");
+        let err = Params::with_overrides(DECLARED, &overrides(&[("turns", "0")])).unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value '0' for turns: has to be from 1 to 1000000!"
+        );
+        assert!(Params::with_overrides(DECLARED, &overrides(&[("turns", "x")])).is_err());
+        assert!(Params::with_overrides(&[], &overrides(&[("turns", "7")])).is_err());
+    }
+
+    #[test]
+    fn parse_overrides() {
+        assert_eq!(
+            parse_override("turns=30000000"),
+            Ok(("turns".to_owned(), "30000000".to_owned()))
+        );
+        assert!(parse_override("turns").is_err());
+        assert!(parse_override("=7").is_err());
+    }
+}
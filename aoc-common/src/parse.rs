@@ -0,0 +1,129 @@
+use std::fmt;
+use std::process;
+use std::str::FromStr;
+
+// Error for malformed puzzle input, pointing at the offending text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,   // Starting at 1
+    pub column: usize, // Starting at 1, counted in characters
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // `part` has to be a slice of `input`, its position is found from where it points to.
+    // Pass an empty slice at the end of a line or input for missing text.
+    pub fn at(input: &str, part: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = locate(input, part);
+        ParseError {
+            line,
+            column,
+            text: part.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    // Shows the error together with the input line it points to
+    pub fn render(&self, input: &str) -> String {
+        let mut rendered = self.to_string();
+        if let Some(line) = input.lines().nth(self.line - 1) {
+            let number = self.line.to_string();
+            rendered.push_str(&format!("\n{} | {}", number, line.trim_end()));
+            rendered.push_str(&format!(
+                "\n{} | {}{}",
+                " ".repeat(number.len()),
+                " ".repeat(self.column - 1),
+                "^".repeat(self.text.chars().count().max(1))
+            ));
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Parses `part` (a slice of `input`) into any `FromStr` type
+pub fn parse_at<T: FromStr>(input: &str, part: &str, expected: &str) -> Result<T, ParseError> {
+    part.parse()
+        .map_err(|_| ParseError::at(input, part, expected))
+}
+
+// For the day binaries, which have no better way to handle a broken input than to stop
+pub fn or_exit<T>(result: Result<T, ParseError>, input: &str) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("Could not parse the input at {}", err.render(input));
+        process::exit(1);
+    })
+}
+
+// Finds the line and column of a slice of the input. Slices from elsewhere point to its end.
+fn locate(input: &str, part: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= input.len())
+        .unwrap_or(input.len());
+
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "nop +0\nacc +1\njmp x4\n";
+
+    #[test]
+    fn locates_parts() {
+        let part = &INPUT[18..20];
+        assert_eq!(part, "x4");
+        let err = ParseError::at(INPUT, part, "a number");
+        assert_eq!((err.line, err.column), (3, 5));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: expected a number, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_at::<i32>(INPUT, &INPUT[11..13], "a number"), Ok(1));
+        assert!(parse_at::<i32>(INPUT, &INPUT[18..20], "a number").is_err());
+    }
+
+    #[test]
+    fn missing_text() {
+        let err = ParseError::at(INPUT, &INPUT[INPUT.len()..], "an instruction");
+        assert_eq!((err.line, err.column), (4, 1));
+        assert!(err.to_string().ends_with("found nothing"));
+    }
+
+    #[test]
+    fn renders_line() {
+        let err = ParseError::at(INPUT, &INPUT[18..20], "a number");
+        assert_eq!(
+            err.render(INPUT),
+            "line 3, column 5: expected a number, found \"x4\"\n3 | jmp x4\n  |     ^^"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::cancel;
+
+// Something that evolves one step at a time, like the seats of day 11 or the cups of day 23
+pub trait Simulation {
+    // Everything that decides how the simulation goes on, equal states evolve the same way
+    type State: Eq + Hash;
+
+    fn step(&mut self);
+
+    fn state(&self) -> Self::State;
+
+    // Whether no more steps can be made, like in a card game where a deck ran empty
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+// Why a driver stopped stepping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    // The state after `steps` steps doesn't change anymore
+    Fixpoint { steps: usize },
+    // The state after `start + period` steps is the same as after `start` steps
+    Cycle { start: usize, period: usize },
+    // The simulation finished after `steps` steps
+    Finished { steps: usize },
+    // Nothing else happened within the limit of steps
+    Limit { steps: usize },
+}
+
+// Makes the given amount of steps, unless the simulation finishes before. Returns the steps made.
+pub fn run<S: Simulation>(simulation: &mut S, steps: usize) -> usize {
+    for step in 0..steps {
+        if simulation.finished() {
+            return step;
+        }
+        cancel::checkpoint();
+        simulation.step();
+    }
+    steps
+}
+
+// Steps until a state is seen for the second time. Every state is kept, so this is meant for
+// simulations with small states or short histories.
+pub fn until_repeat<S: Simulation>(simulation: &mut S, limit: usize) -> Stop {
+    let mut seen: HashMap<S::State, usize> = HashMap::new();
+    let mut steps = 0;
+    loop {
+        if simulation.finished() {
+            return Stop::Finished { steps };
+        }
+        if let Some(start) = seen.insert(simulation.state(), steps) {
+            return Stop::Cycle {
+                start,
+                period: steps - start,
+            };
+        }
+        if steps == limit {
+            return Stop::Limit { steps };
+        }
+        cancel::checkpoint();
+        simulation.step();
+        steps += 1;
+    }
+}
+
+// Steps until the state stops changing. Simulations that repeat more than one state never get
+// there, so they stop at the first repeated state instead.
+pub fn until_fixpoint<S: Simulation>(simulation: &mut S, limit: usize) -> Stop {
+    match until_repeat(simulation, limit.saturating_add(1)) {
+        Stop::Cycle { start, period: 1 } => Stop::Fixpoint { steps: start },
+        Stop::Limit { .. } => Stop::Limit { steps: limit },
+        stop => stop,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Follows x -> x * x + 1 modulo some number, stopping at zero
+    struct Square {
+        value: u64,
+        modulus: u64,
+    }
+
+    impl Simulation for Square {
+        type State = u64;
+
+        fn step(&mut self) {
+            self.value = (self.value * self.value + 1) % self.modulus;
+        }
+
+        fn state(&self) -> u64 {
+            self.value
+        }
+
+        fn finished(&self) -> bool {
+            self.value == 0
+        }
+    }
+
+    fn square(value: u64, modulus: u64) -> Square {
+        Square { value, modulus }
+    }
+
+    #[test]
+    fn run_steps() {
+        let mut simulation = square(1, 1000);
+        assert_eq!(run(&mut simulation, 3), 3);
+        // 1, 2, 5, 26
+        assert_eq!(simulation.value, 26);
+        // 1, 2, 0
+        assert_eq!(run(&mut square(1, 5), 10), 2);
+    }
+
+    #[test]
+    fn find_cycles() {
+        // 2, 5, 26, 677, 330, 901, 802, 205, 26
+        assert_eq!(
+            until_repeat(&mut square(2, 1000), 100),
+            Stop::Cycle {
+                start: 2,
+                period: 6
+            }
+        );
+        assert_eq!(
+            until_fixpoint(&mut square(2, 1000), 100),
+            Stop::Cycle {
+                start: 2,
+                period: 6
+            }
+        );
+        assert_eq!(
+            until_repeat(&mut square(2, 1000), 5),
+            Stop::Limit { steps: 5 }
+        );
+        assert_eq!(
+            until_repeat(&mut square(1, 5), 100),
+            Stop::Finished { steps: 2 }
+        );
+    }
+
+    #[test]
+    fn find_fixpoints() {
+        // 3 * 3 + 1 = 10 = 3 modulo 7
+        assert_eq!(
+            until_fixpoint(&mut square(3, 7), 10),
+            Stop::Fixpoint { steps: 0 }
+        );
+        // 1, 2, 5 = 5 * 5 + 1 modulo 21
+        assert_eq!(
+            until_fixpoint(&mut square(1, 21), 10),
+            Stop::Fixpoint { steps: 2 }
+        );
+        assert_eq!(
+            until_fixpoint(&mut square(1, 21), 2),
+            Stop::Fixpoint { steps: 2 }
+        );
+        assert_eq!(
+            until_fixpoint(&mut square(1, 21), 1),
+            Stop::Limit { steps: 1 }
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::frames::Frames;
+use crate::params::{Param, Params};
+use crate::parse::ParseError;
+
+// Common shape of every day, so runners, benchmarks and tests can treat them all the same.
+// Some days keep references into the raw input, so the parsed input borrows from it.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    // Puzzle constants the runner can override, `parse` uses their defaults
+    const PARAMS: &'static [Param] = &[];
+
+    // Like `parse`, but with the given values for PARAMS. Days with parameters keep them in
+    // their input, so both parts and all strategies see the same values.
+    fn parse_with<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer;
+
+    // Day 25 only has one puzzle, it keeps the default `part2` and sets this to 1. The generated
+    // inputs every day is tested with check that the two agree.
+    const PARTS: u32 = 2;
+
+    fn part2(_input: &Self::Input<'_>) -> Option<Answer> {
+        None
+    }
+
+    // Name of the implementation in `part1` and `part2`
+    const DEFAULT_STRATEGY: &'static str = "default";
+
+    // Alternative implementations some days keep around besides the default one
+    fn strategies() -> Vec<Strategy<Self>> {
+        Vec::new()
+    }
+
+    // Rendered steps of a part for watching it evolve, None if the part doesn't simulate
+    // anything. Without a number of steps, as many as the puzzle needs are recorded.
+    fn frames(_input: &Self::Input<'_>, _part: u32, _steps: Option<usize>) -> Option<Frames> {
+        None
+    }
+}
+
+pub type PartFn<S> = for<'a, 'b> fn(&'b <S as Solution>::Input<'a>) -> Answer;
+
+// A named alternative implementation. Parts it doesn't implement are solved by the default one.
+pub struct Strategy<S: Solution + ?Sized> {
+    pub name: &'static str,
+    pub slow: bool, // Too slow to run on the full input in reasonable time
+    pub part1: Option<PartFn<S>>,
+    pub part2: Option<PartFn<S>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyInfo {
+    pub name: &'static str,
+    pub slow: bool,
+}
+
+// Answers to both parts, day 25 only has one
+pub type Answers = (Answer, Option<Answer>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(number: $t) -> Answer {
+                    Answer::Number(i64::try_from(number).expect("Answer doesn't fit into i64!"))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_owned())
+    }
+}
+
+// Parses the input and solves both parts of a day
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, ParseError> {
+    let parsed = S::parse(input)?;
+    Ok((S::part1(&parsed), S::part2(&parsed)))
+}
+
+// Parses the input and solves only one part, None if the day doesn't have that part
+pub fn solve_part<S: Solution>(input: &str, part: u32) -> Result<Option<Answer>, ParseError> {
+    if part < 1 || part > S::PARTS {
+        return Ok(None);
+    }
+    let parsed = S::parse(input)?;
+    Ok(match part {
+        1 => Some(S::part1(&parsed)),
+        _ => S::part2(&parsed),
+    })
+}
+
+// All implementations of a day, the default one first
+pub fn strategies<S: Solution>() -> Vec<StrategyInfo> {
+    let default = StrategyInfo {
+        name: S::DEFAULT_STRATEGY,
+        slow: false,
+    };
+    let alternatives = S::strategies().into_iter().map(|strategy| StrategyInfo {
+        name: strategy.name,
+        slow: strategy.slow,
+    });
+    std::iter::once(default).chain(alternatives).collect()
+}
+
+// Like `solve`, but with the named implementation and parameters. Returns None if there is no
+// such name.
+pub fn solve_with<S: Solution>(
+    input: &str,
+    params: &Params,
+    name: &str,
+) -> Option<Result<Answers, ParseError>> {
+    solve_timed::<S>(input, params, name, &mut |_| {})
+}
+
+// Like `solve_with`, but calls `lap` after parsing and after each of the parts, so the caller
+// can time them. Laps after a part get its answer, parts a day doesn't have still get their lap.
+pub fn solve_timed<S: Solution>(
+    input: &str,
+    params: &Params,
+    name: &str,
+    lap: &mut dyn FnMut(Option<&Answer>),
+) -> Option<Result<Answers, ParseError>> {
+    let strategy = if name == S::DEFAULT_STRATEGY {
+        None
+    } else {
+        Some(
+            S::strategies()
+                .into_iter()
+                .find(|strategy| strategy.name == name)?,
+        )
+    };
+
+    Some(S::parse_with(input, params).map(|parsed| {
+        lap(None);
+        let part1 = match strategy.as_ref().and_then(|strategy| strategy.part1) {
+            Some(part1) => part1(&parsed),
+            None => S::part1(&parsed),
+        };
+        lap(Some(&part1));
+        let part2 = match strategy.as_ref().and_then(|strategy| strategy.part2) {
+            Some(part2) => Some(part2(&parsed)),
+            None => S::part2(&parsed),
+        };
+        lap(part2.as_ref());
+        (part1, part2)
+    }))
+}
+
+// Parses the input and records the frames of one part, see `Solution::frames`
+pub fn frames<S: Solution>(
+    input: &str,
+    params: &Params,
+    part: u32,
+    steps: Option<usize>,
+) -> Result<Option<Frames>, ParseError> {
+    if part < 1 || part > S::PARTS {
+        return Ok(None);
+    }
+    let parsed = S::parse_with(input, params)?;
+    Ok(S::frames(&parsed, part, steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u32 = 0;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            Ok(input.split(',').collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Answer {
+            input
+                .iter()
+                .map(|number| number.parse::<u32>().unwrap())
+                .sum::<u32>()
+                .into()
+        }
+
+        const DEFAULT_STRATEGY: &'static str = "sum";
+
+        fn strategies() -> Vec<Strategy<Self>> {
+            vec![Strategy {
+                name: "count",
+                slow: false,
+                part1: Some(|input| input.len().into()),
+                part2: None,
+            }]
+        }
+    }
+
+    // Repeats the input a configurable amount of times
+    struct Repeat;
+
+    impl Solution for Repeat {
+        const DAY: u32 = 0;
+
+        type Input<'a> = String;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            Repeat::parse_with(input, &Params::defaults(Repeat::PARAMS))
+        }
+
+        const PARAMS: &'static [Param] = &[Param {
+            name: "times",
+            default: "2",
+            description: "How often to repeat the input",
+            check: |value| crate::params::between(value, 0usize, 10),
+        }];
+
+        fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+            Ok(input.repeat(params.get("times")))
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Answer {
+            input.as_str().into()
+        }
+    }
+
+    #[test]
+    fn answers_from_values() {
+        assert_eq!(Answer::from(42u64), Answer::Number(42));
+        assert_eq!(Answer::from(-7), Answer::Number(-7));
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::from(9709761600usize).to_string(), "9709761600");
+    }
+
+    #[test]
+    fn solve_generically() {
+        assert_eq!(solve::<Sum>("1,2,3"), Ok((Answer::Number(6), None)));
+        assert_eq!(solve_part::<Sum>("1,2", 1), Ok(Some(Answer::Number(3))));
+        assert_eq!(solve_part::<Sum>("1,2", 2), Ok(None));
+        assert_eq!(solve_part::<Sum>("1,2", 3), Ok(None));
+    }
+
+    #[test]
+    fn solve_with_strategies() {
+        let names: Vec<&str> = strategies::<Sum>().iter().map(|info| info.name).collect();
+        assert_eq!(names, vec!["sum", "count"]);
+        let params = Params::defaults(Sum::PARAMS);
+        assert_eq!(
+            solve_with::<Sum>("1,2,3", &params, "sum"),
+            Some(Ok((Answer::Number(6), None)))
+        );
+        assert_eq!(
+            solve_with::<Sum>("1,2,3", &params, "count"),
+            Some(Ok((Answer::Number(3), None)))
+        );
+        assert_eq!(solve_with::<Sum>("1,2,3", &params, "product"), None);
+    }
+
+    #[test]
+    fn laps_for_every_phase() {
+        let mut laps = Vec::new();
+        let params = Params::defaults(Sum::PARAMS);
+        let answers = solve_timed::<Sum>("1,2,3", &params, "count", &mut |answer| {
+            laps.push(answer.cloned())
+        });
+        assert_eq!(answers, Some(Ok((Answer::Number(3), None))));
+        assert_eq!(laps, vec![None, Some(Answer::Number(3)), None]);
+    }
+
+    #[test]
+    fn solve_with_params() {
+        assert_eq!(solve::<Repeat>("ab"), Ok((Answer::from("abab"), None)));
+        let overrides = vec![("times".to_owned(), "3".to_owned())];
+        let params = Params::with_overrides(Repeat::PARAMS, &overrides).unwrap();
+        assert_eq!(
+            solve_with::<Repeat>("ab", &params, "default"),
+            Some(Ok((Answer::from("ababab"), None)))
+        );
+    }
+}
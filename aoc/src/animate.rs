@@ -0,0 +1,145 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Selection;
+
+const DEFAULT_DELAY_MS: u64 = 100;
+
+struct AnimateOptions {
+    part: u32,
+    steps: Option<usize>,
+    delay: Duration,
+    dump: Option<PathBuf>,
+}
+
+impl AnimateOptions {
+    // Takes the animate flags out of the arguments and returns the remaining ones untouched
+    fn extract(args: &[String]) -> Result<(AnimateOptions, Vec<String>), String> {
+        let mut options = AnimateOptions {
+            part: 1,
+            steps: None,
+            delay: Duration::from_millis(DEFAULT_DELAY_MS),
+            dump: None,
+        };
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => {
+                    let part = args.next().ok_or("Missing part after --part!")?;
+                    options.part = match part.as_str() {
+                        "1" => 1,
+                        "2" => 2,
+                        _ => return Err(format!("Invalid part '{}', expected 1 or 2!", part)),
+                    };
+                }
+                "--steps" => {
+                    let steps = args.next().ok_or("Missing count after --steps!")?;
+                    let steps = steps
+                        .parse()
+                        .map_err(|_| format!("Invalid number of steps '{}'!", steps))?;
+                    options.steps = Some(steps);
+                }
+                "--delay" => {
+                    let delay = args.next().ok_or("Missing milliseconds after --delay!")?;
+                    let delay = delay
+                        .parse()
+                        .map_err(|_| format!("Invalid delay '{}'!", delay))?;
+                    options.delay = Duration::from_millis(delay);
+                }
+                "--dump" => {
+                    let path = args.next().ok_or("Missing directory after --dump!")?;
+                    options.dump = Some(PathBuf::from(path));
+                }
+                _ => rest.push(arg.to_owned()),
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+// Records every step of a day's part and plays it in the terminal, or writes the frames to
+// numbered files
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (options, args) = AnimateOptions::extract(args)?;
+    let selection = Selection::from_args(&args)?;
+    let day = match selection.days.as_slice() {
+        [day] => *day,
+        _ => return Err("Only a single day can be animated!".to_owned()),
+    };
+
+    let input = selection.read_input(day)?;
+    let params = selection.params(day);
+    let frames = (day.frames)(&input, &params, options.part, options.steps)
+        .map_err(|err| selection.parse_error(day, &input, err))?
+        .ok_or(format!(
+            "Day {} has nothing to animate in part {}!",
+            day.number, options.part
+        ))?;
+
+    match &options.dump {
+        Some(dir) => {
+            let paths = frames
+                .dump(dir)
+                .map_err(|err| format!("Could not write frames to {}: {}", dir.display(), err))?;
+            println!("Wrote {} frames to {}", paths.len(), dir.display());
+        }
+        None => frames
+            .play(&mut io::stdout(), options.delay)
+            .map_err(|err| format!("Could not play frames: {}", err))?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_common::input::{default_path, InputSource};
+    use aoc_common::params::Params;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_options() {
+        let (options, rest) = AnimateOptions::extract(&args(&[
+            "11", "--part", "2", "--steps", "3", "--delay", "20", "--simple",
+        ]))
+        .unwrap();
+        assert_eq!((options.part, options.steps), (2, Some(3)));
+        assert_eq!(options.delay, Duration::from_millis(20));
+        assert!(options.dump.is_none());
+        assert_eq!(rest, args(&["11", "--simple"]));
+
+        let (options, _) = AnimateOptions::extract(&args(&["11"])).unwrap();
+        assert_eq!((options.part, options.steps), (1, None));
+        assert!(AnimateOptions::extract(&args(&["11", "--part", "3"])).is_err());
+        assert!(AnimateOptions::extract(&args(&["11", "--delay", "fast"])).is_err());
+    }
+
+    #[test]
+    fn days_with_frames() {
+        let input = InputSource::File(default_path(20, true)).read().unwrap();
+        let day = registry::find(20).unwrap();
+        let params = Params::defaults(day.params);
+        assert!((day.frames)(&input, &params, 1, None).unwrap().is_none());
+        assert!((day.frames)(&input, &params, 2, None).unwrap().is_some());
+        assert!((day.frames)(&input, &params, 3, None).unwrap().is_none());
+
+        let animated: Vec<u32> = registry::DAYS
+            .iter()
+            .filter(|day| {
+                let input = InputSource::File(default_path(day.number, false))
+                    .read()
+                    .unwrap();
+                let params = Params::defaults(day.params);
+                (day.frames)(&input, &params, 2, Some(1)).unwrap().is_some()
+            })
+            .map(|day| day.number)
+            .collect();
+        assert_eq!(animated, vec![11, 17, 20, 24]);
+    }
+}
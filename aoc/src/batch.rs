@@ -0,0 +1,271 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::input::InputSource;
+use aoc_common::params::Params;
+use aoc_common::solution::Answer;
+
+use crate::params;
+use crate::registry::{self, Day};
+use crate::selection;
+use crate::strategies;
+use crate::table::Table;
+use crate::timeout;
+
+// What solving one of the inputs came to. Answers and laps are kept up to the point where
+// something went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRun {
+    pub path: PathBuf,
+    pub star1: Option<Answer>,
+    pub star2: Option<Answer>,
+    pub laps: Vec<Duration>,
+    pub failure: Option<String>,
+}
+
+impl FileRun {
+    fn new(path: &Path) -> FileRun {
+        FileRun {
+            path: path.to_owned(),
+            star1: None,
+            star2: None,
+            laps: Vec::new(),
+            failure: None,
+        }
+    }
+
+    // Time spent in each phase instead of since the start
+    pub fn phase_times(&self) -> Vec<Duration> {
+        let mut previous = Duration::default();
+        self.laps
+            .iter()
+            .map(|lap| {
+                let time = *lap - previous;
+                previous = *lap;
+                time
+            })
+            .collect()
+    }
+}
+
+// Solves a single input, turning unreadable files, parse errors and panics into a failure
+pub fn solve_file(day: &Day, params: &Params, name: &str, path: &Path) -> FileRun {
+    let mut run = FileRun::new(path);
+    let input = match InputSource::File(path.to_owned()).read() {
+        Ok(input) => input,
+        Err(err) => {
+            run.failure = Some(err);
+            return run;
+        }
+    };
+
+    let start = Instant::now();
+    let mut answers = Vec::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (day.solve_timed)(&input, params, name, &mut |answer| {
+            run.laps.push(start.elapsed());
+            answers.push(answer.cloned());
+        })
+    }));
+    let mut answers = answers.into_iter().skip(1).flatten();
+    run.star1 = answers.next();
+    run.star2 = answers.next();
+    run.failure = match result {
+        Ok(Some(Ok(_))) => None,
+        Ok(Some(Err(err))) => Some(format!("parse error at {}", err)),
+        Ok(None) => Some(format!("no implementation '{}'", name)),
+        Err(payload) => Some(format!("panicked: {}", timeout::panic_message(&*payload))),
+    };
+    run
+}
+
+// Solves all inputs on `jobs` threads, the results come back in the order of the paths
+pub fn solve_files(
+    day: &Day,
+    params: &Params,
+    name: &str,
+    paths: &[PathBuf],
+    jobs: usize,
+) -> Vec<FileRun> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(vec![None; paths.len()]);
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, paths.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let path = match paths.get(idx) {
+                    Some(path) => path,
+                    None => break,
+                };
+                let run = solve_file(day, params, name, path);
+                runs.lock().unwrap()[idx] = Some(run);
+            });
+        }
+    });
+    runs.into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|run| run.expect("Every input was solved"))
+        .collect()
+}
+
+// The files in a directory, sorted by name
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("Could not read {}: {}", dir.display(), err))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("Could not read {}: {}", dir.display(), err))?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    if paths.is_empty() {
+        return Err(format!("No inputs in {}!", dir.display()));
+    }
+    Ok(paths)
+}
+
+struct BatchOptions {
+    jobs: usize,
+}
+
+impl BatchOptions {
+    // Takes the batch flags out of the arguments and returns the remaining ones untouched
+    fn extract(args: &[String]) -> Result<(BatchOptions, Vec<String>), String> {
+        let mut options = BatchOptions {
+            jobs: thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+        };
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--jobs" {
+                let jobs = args.next().ok_or("Missing count after --jobs!")?;
+                options.jobs = jobs
+                    .parse()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or(format!("Invalid number of jobs '{}'!", jobs))?;
+            } else {
+                rest.push(arg.to_owned());
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+// Solves a day for every input in a directory, e.g. to compare the inputs of a team
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (options, args) = BatchOptions::extract(args)?;
+    let (strategy, args) = strategies::extract_impl(&args)?;
+    let (overrides, args) = params::extract_params(&args)?;
+    let (day, dir) = match args.as_slice() {
+        [day, dir] => (day, Path::new(dir)),
+        _ => return Err(crate::usage()),
+    };
+    let day = match selection::parse_days(day)?.as_slice() {
+        [number] => registry::find(*number).ok_or(format!("Day {} is not registered!", number))?,
+        _ => return Err("Only a single day can be run in batch mode!".to_owned()),
+    };
+    let params = params::for_day(day, &overrides)?;
+    let name = match strategy.as_deref() {
+        Some(name) => strategies::find(day, name)?.name,
+        None => (day.strategies)()[0].name,
+    };
+    let paths = input_files(dir)?;
+
+    // Failures end up in the table, the panic messages would only get mixed up on stderr
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let runs = solve_files(day, &params, name, &paths, options.jobs);
+    panic::set_hook(hook);
+
+    let mut table = Table::new(&[
+        "Input", "Star 1", "Star 2", "Parse", "Part 1", "Part 2", "Result",
+    ]);
+    for run in runs.iter() {
+        let file_name = run.path.file_name().unwrap_or_default();
+        let mut row = vec![
+            file_name.to_string_lossy().into_owned(),
+            run.star1.as_ref().map_or("-".to_owned(), Answer::to_string),
+            run.star2.as_ref().map_or("-".to_owned(), Answer::to_string),
+        ];
+        let times = run.phase_times();
+        row.extend((0..3).map(|idx| {
+            times
+                .get(idx)
+                .map_or("-".to_owned(), |time| format!("{:.2?}", time))
+        }));
+        row.push(run.failure.clone().unwrap_or_else(|| "ok".to_owned()));
+        table.add_row(row);
+    }
+    print!("{}", table);
+
+    let failed = runs.iter().filter(|run| run.failure.is_some()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} input(s) failed!", failed, runs.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input::default_path;
+    use std::env;
+    use std::process;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_options() {
+        let (options, rest) = BatchOptions::extract(&args(&["7", "team", "--jobs", "3"])).unwrap();
+        assert_eq!(options.jobs, 3);
+        assert_eq!(rest, args(&["7", "team"]));
+        assert!(BatchOptions::extract(&args(&["7", "team", "--jobs", "0"])).is_err());
+        assert!(BatchOptions::extract(&args(&["7", "team", "--jobs"])).is_err());
+    }
+
+    #[test]
+    fn flag_broken_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-batch-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let full = fs::read_to_string(default_path(20, false)).unwrap();
+        let simple = fs::read_to_string(default_path(20, true)).unwrap();
+        // Without its last tile the picture isn't a square anymore
+        let (missing_tile, _) = simple.trim_end().rsplit_once("\n\n").unwrap();
+        fs::write(dir.join("a-full.txt"), full).unwrap();
+        fs::write(dir.join("b-simple.txt"), &simple).unwrap();
+        fs::write(dir.join("c-missing-tile.txt"), missing_tile).unwrap();
+        fs::write(dir.join("d-garbage.txt"), "Tile x:\n").unwrap();
+
+        let day = registry::find(20).unwrap();
+        let params = Params::defaults(day.params);
+        let paths = input_files(&dir).unwrap();
+        let runs = solve_files(day, &params, "default", &paths, 3);
+        let names: Vec<_> = runs.iter().map(|run| run.path.clone()).collect();
+        assert_eq!(names, paths);
+
+        assert_eq!(runs[0].failure, None);
+        assert_eq!(runs[0].laps.len(), 3);
+        assert_eq!(runs[1].star1, Some(Answer::Number(20899048083289)));
+        assert_eq!(runs[1].star2, Some(Answer::Number(273)));
+        assert!(runs[2]
+            .failure
+            .as_ref()
+            .is_some_and(|failure| failure.starts_with("panicked: ")));
+        assert!(runs[3]
+            .failure
+            .as_ref()
+            .is_some_and(|failure| failure.starts_with("parse error at line 1")));
+        assert_eq!(runs[3].laps, Vec::<Duration>::new());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::input::InputOptions;
+use aoc_common::params::Params;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::Solution;
+
+use crate::memory::{self, Usage};
+use crate::table::Table;
+use crate::Selection;
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_BASELINE: &str = "target/bench-baseline.txt";
+// Changes of the median smaller than this are considered noise
+const NOISE_THRESHOLD: f64 = 0.1;
+
+// In the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL
+            .iter()
+            .copied()
+            .find(|phase| phase.name() == name)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+// Statistics for every phase a day has, day 25 has no second part
+pub type Timings = Vec<(Phase, Stats)>;
+
+// Runs every phase of a day the given amount of times
+pub fn measure<S: Solution>(
+    input: &str,
+    params: &Params,
+    runs: usize,
+) -> Result<Timings, ParseError> {
+    let mut samples: HashMap<Phase, Vec<Duration>> = HashMap::new();
+    let mut record =
+        |phase: Phase, elapsed: Duration| samples.entry(phase).or_default().push(elapsed);
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = black_box(S::parse_with(input, params)?);
+        record(Phase::Parse, start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed));
+        record(Phase::Part1, start.elapsed());
+
+        let start = Instant::now();
+        if black_box(S::part2(&parsed)).is_some() {
+            record(Phase::Part2, start.elapsed());
+        }
+    }
+
+    Ok(Phase::ALL
+        .iter()
+        .filter_map(|phase| {
+            samples
+                .remove(phase)
+                .map(|samples| (*phase, Stats::new(samples)))
+        })
+        .collect())
+}
+
+// Saved results of an earlier run, keyed by day and phase
+#[derive(Debug, Default, PartialEq)]
+struct Baseline {
+    stats: HashMap<(u32, Phase), Stats>,
+}
+
+impl Baseline {
+    fn load(path: &Path) -> Result<Option<Baseline>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read baseline {}: {}", path.display(), err))?;
+        Baseline::parse(&text)
+            .map(Some)
+            .map_err(|line| format!("Invalid line in baseline {}: {}", path.display(), line))
+    }
+
+    // One line per day and phase: "<day> <phase> <min> <median> <max>", all times in nanoseconds
+    fn parse(text: &str) -> Result<Baseline, &str> {
+        let mut baseline = Baseline::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let (day, phase, times) = match parts.as_slice() {
+                [day, phase, times @ ..] if times.len() == 3 => (day, phase, times),
+                _ => return Err(line),
+            };
+            let day: u32 = day.parse().map_err(|_| line)?;
+            let phase = Phase::from_name(phase).ok_or(line)?;
+            let times = times
+                .iter()
+                .map(|time| time.parse().map(Duration::from_nanos))
+                .collect::<Result<Vec<Duration>, _>>()
+                .map_err(|_| line)?;
+            let stats = Stats {
+                min: times[0],
+                median: times[1],
+                max: times[2],
+            };
+            baseline.stats.insert((day, phase), stats);
+        }
+        Ok(baseline)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+        }
+        fs::write(path, self.to_string())
+            .map_err(|err| format!("Could not write baseline {}: {}", path.display(), err))
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<&(u32, Phase)> = self.stats.keys().collect();
+        keys.sort_by_key(|(day, phase)| (*day, Phase::ALL.iter().position(|p| p == phase)));
+        for key in keys {
+            let stats = self.stats[key];
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                key.0,
+                key.1,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Compares medians, as they are the least affected by outliers
+pub fn compare(current: Duration, baseline: Duration) -> String {
+    if baseline.as_nanos() == 0 {
+        return "-".to_owned();
+    }
+    let change = current.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+    let verdict = if change > NOISE_THRESHOLD {
+        " regression"
+    } else if change < -NOISE_THRESHOLD {
+        " improvement"
+    } else {
+        ""
+    };
+    format!("{:+.1}%{}", change * 100.0, verdict)
+}
+
+struct BenchOptions {
+    runs: usize,
+    save: bool,
+    baseline: PathBuf,
+    memory: bool,
+}
+
+impl BenchOptions {
+    // Takes the bench flags out of the arguments and returns the remaining ones untouched
+    fn extract(args: &[String]) -> Result<(BenchOptions, Vec<String>), String> {
+        let mut options = BenchOptions {
+            runs: DEFAULT_RUNS,
+            save: false,
+            baseline: PathBuf::from(DEFAULT_BASELINE),
+            memory: false,
+        };
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" => {
+                    let runs = args.next().ok_or("Missing count after --runs!")?;
+                    options.runs = match runs.parse() {
+                        Ok(runs) if runs > 0 => runs,
+                        _ => return Err(format!("Invalid run count '{}'!", runs)),
+                    };
+                }
+                "--save" => options.save = true,
+                "--baseline" => {
+                    let path = args.next().ok_or("Missing path after --baseline!")?;
+                    options.baseline = PathBuf::from(path);
+                }
+                "--memory" => options.memory = true,
+                _ => rest.push(arg.to_owned()),
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+fn usage_columns(usage: Usage) -> [String; 3] {
+    [
+        usage.allocations.to_string(),
+        memory::format_bytes(usage.bytes),
+        memory::format_bytes(usage.peak),
+    ]
+}
+
+// Whether the days run on their full inputs with the default parameters, the only runs
+// baselines are kept for
+fn uses_defaults(selection: &Selection) -> bool {
+    selection.options == InputOptions::default() && selection.overrides.is_empty()
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (options, args) = BenchOptions::extract(args)?;
+    let selection = Selection::from_args(&args)?;
+    let defaults = uses_defaults(&selection);
+    if options.save && !defaults {
+        return Err(
+            "--save can't be used with --input, --simple or --param, baselines are for the full \
+             inputs with the defaults!"
+                .to_owned(),
+        );
+    }
+    // Other inputs or parameters would only be compared with timings they have nothing to do with
+    let baseline = if defaults {
+        Baseline::load(&options.baseline)?
+    } else {
+        None
+    };
+
+    let mut headers = vec!["Day", "Phase", "Min", "Median", "Max", "Baseline", "Change"];
+    if options.memory {
+        memory::enable();
+        headers.extend(["Allocs", "Bytes", "Peak"]);
+    }
+    let mut results = Baseline::default();
+    let mut table = Table::new(&headers);
+    for day in selection.days.iter() {
+        let input = selection.read_input(day)?;
+        let params = selection.params(day);
+        let timings = (day.bench)(&input, &params, options.runs)
+            .map_err(|err| selection.parse_error(day, &input, err))?;
+        // Counted in a run of its own, so the counting doesn't show up in the timings
+        let usages = if options.memory {
+            (day.memory)(&input, &params).map_err(|err| selection.parse_error(day, &input, err))?
+        } else {
+            Vec::new()
+        };
+
+        for (phase, stats) in timings {
+            let saved = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.stats.get(&(day.number, phase)));
+            let mut row = vec![
+                day.number.to_string(),
+                phase.to_string(),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+                saved.map_or("-".to_owned(), |saved| format!("{:.2?}", saved.median)),
+                saved.map_or("-".to_owned(), |saved| compare(stats.median, saved.median)),
+            ];
+            if options.memory {
+                let usage = usages
+                    .iter()
+                    .find(|(measured, _)| *measured == phase)
+                    .map_or(Usage::default(), |(_, usage)| *usage);
+                row.extend(usage_columns(usage));
+            }
+            table.add_row(row);
+            results.stats.insert((day.number, phase), stats);
+        }
+    }
+    print!("{}", table);
+
+    if options.save {
+        // Days that weren't benchmarked this time keep their old results
+        let mut saved = baseline.unwrap_or_default();
+        saved.stats.extend(results.stats);
+        saved.save(&options.baseline)?;
+        println!("Saved baseline to {}", options.baseline.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::new(vec![ms(5), ms(1), ms(3), ms(9), ms(4)]);
+        assert_eq!(
+            stats,
+            Stats {
+                min: ms(1),
+                median: ms(4),
+                max: ms(9)
+            }
+        );
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        let stats = Stats::new(vec![ms(1), ms(2), ms(3)]);
+        baseline.stats.insert((7, Phase::Part2), stats);
+        baseline.stats.insert((7, Phase::Parse), stats);
+        baseline.stats.insert((1, Phase::Part1), stats);
+
+        let text = baseline.to_string();
+        assert_eq!(text.lines().next(), Some("1 part1 1000000 2000000 3000000"));
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+        assert!(Baseline::parse("7 part3 1 2 3").is_err());
+        assert!(Baseline::parse("7 parse 1 2").is_err());
+    }
+
+    #[test]
+    fn compare_medians() {
+        assert_eq!(compare(ms(150), ms(100)), "+50.0% regression");
+        assert_eq!(compare(ms(50), ms(100)), "-50.0% improvement");
+        assert_eq!(compare(ms(102), ms(100)), "+2.0%");
+    }
+
+    #[test]
+    fn baselines_are_for_the_defaults() {
+        let selection = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            Selection::from_args(&args).unwrap()
+        };
+        assert!(uses_defaults(&selection(&["1..=3"])));
+        assert!(!uses_defaults(&selection(&["7", "--simple"])));
+        assert!(!uses_defaults(&selection(&["7", "--input", "other.txt"])));
+        assert!(!uses_defaults(&selection(&["15", "--param", "turns1=10"])));
+    }
+
+    #[test]
+    fn memory_flag() {
+        let args: Vec<String> = ["7", "--memory", "--runs", "3"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let (options, rest) = BenchOptions::extract(&args).unwrap();
+        assert!(options.memory);
+        assert_eq!(options.runs, 3);
+        assert_eq!(rest, vec!["7"]);
+        let usage = Usage {
+            allocations: 3,
+            bytes: 2048,
+            peak: 100,
+        };
+        assert_eq!(usage_columns(usage), ["3", "2.0 KiB", "100 B"]);
+    }
+
+    #[test]
+    fn measure_phases() {
+        let params = Params::defaults(day_25_rust::Day25::PARAMS);
+        let timings = measure::<day_25_rust::Day25>("5764801\n17807724", &params, 3).unwrap();
+        let phases: Vec<Phase> = timings.iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Part1]);
+        assert!(measure::<day_25_rust::Day25>("5764801", &params, 3).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::input;
+
+use crate::remote::Remote;
+use crate::selection;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Downloads the inputs of the selected days that aren't in the inputs folder yet
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (remote, args) = Remote::extract(args)?;
+    let days = match args.as_slice() {
+        [days] => selection::parse_days(days)?,
+        _ => return Err(crate::usage()),
+    };
+
+    let dir = input::inputs_dir();
+    for day in days {
+        match fetch(&remote, day, &dir)? {
+            Fetched::Cached(path) => println!("Day {} is already in {}", day, path.display()),
+            Fetched::Downloaded(path) => println!("Downloaded day {} to {}", day, path.display()),
+        }
+    }
+    Ok(())
+}
+
+// Inputs never change, so one that is already there is never downloaded again
+pub fn fetch(remote: &Remote, day: u32, dir: &Path) -> Result<Fetched, String> {
+    let path = dir.join(format!("day-{:02}.txt", day));
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let response = remote.send(&format!("day/{}/input", day), None)?;
+    if !response.is_success() {
+        return Err(format!(
+            "Could not download day {}, the server answered {}: {}",
+            day,
+            response.status,
+            response.body.lines().next().unwrap_or("").trim()
+        ));
+    }
+
+    // Written next to the input first, so an interrupted download doesn't leave half of one
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, &response.body)
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stand_in::StandIn;
+    use crate::http::Url;
+    use std::env;
+    use std::process;
+
+    fn remote(server: &StandIn, session: Option<&str>) -> Remote {
+        Remote {
+            base_url: Url::parse(&server.url).unwrap(),
+            session: session.map(str::to_owned),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn download_once() {
+        let server = StandIn::start("200 OK", &[], "1721\n979\n366\n");
+        let remote = remote(&server, Some("abc"));
+        let dir = temp_dir("once");
+        let path = dir.join("day-01.txt");
+
+        assert_eq!(
+            fetch(&remote, 1, &dir).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n366\n");
+        assert_eq!(fetch(&remote, 1, &dir).unwrap(), Fetched::Cached(path));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn report_failed_downloads() {
+        let server = StandIn::start(
+            "400 Bad Request",
+            &[],
+            "Please log in to get your puzzle input.\n",
+        );
+        let dir = temp_dir("failed");
+        let err = fetch(&remote(&server, Some("expired")), 7, &dir).unwrap_err();
+        assert_eq!(
+            err,
+            "Could not download day 7, the server answered 400: Please log in to get your puzzle input."
+        );
+        assert!(fs::read_dir(&dir).unwrap().next().is_none());
+
+        // Without a session nothing is sent at all
+        assert!(fetch(&remote(&server, None), 7, &dir).is_err());
+        assert_eq!(server.requests().len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use aoc_common::differential::DEFAULT_SEED;
+use aoc_common::rng::Rng;
+use aoc_common::solution::Answer;
+
+use crate::registry::{self, Day, Sizes};
+use crate::selection;
+use crate::table::Table;
+
+struct GenerateOptions {
+    size: Option<usize>,
+    seed: u64,
+    output: Option<PathBuf>,
+    check: bool,
+}
+
+impl GenerateOptions {
+    // Takes the generate flags out of the arguments and returns the remaining ones untouched
+    fn extract(args: &[String]) -> Result<(GenerateOptions, Vec<String>), String> {
+        let mut options = GenerateOptions {
+            size: None,
+            seed: DEFAULT_SEED,
+            output: None,
+            check: false,
+        };
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => {
+                    let size = args.next().ok_or("Missing size after --size!")?;
+                    let size = size
+                        .parse()
+                        .map_err(|_| format!("Invalid size '{}'!", size))?;
+                    options.size = Some(size);
+                }
+                "--seed" => {
+                    let seed = args.next().ok_or("Missing seed after --seed!")?;
+                    options.seed = seed
+                        .parse()
+                        .map_err(|_| format!("Invalid seed '{}'!", seed))?;
+                }
+                "--output" => {
+                    let path = args.next().ok_or("Missing path after --output!")?;
+                    options.output = Some(PathBuf::from(path));
+                }
+                "--check" => options.check = true,
+                _ => rest.push(arg.to_owned()),
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+fn check_size(day: &Day, size: usize) -> Result<(), String> {
+    let Sizes { name, min, max, .. } = day.sizes;
+    if (min..=max).contains(&size) {
+        return Ok(());
+    }
+    let range = match max {
+        usize::MAX => format!("at least {}", min),
+        _ => format!("from {} to {}", min, max),
+    };
+    Err(format!(
+        "Day {} needs {} {}, not {}!",
+        day.number, name, range, size
+    ))
+}
+
+fn describe(answer: &Option<Answer>) -> String {
+    answer
+        .as_ref()
+        .map_or_else(|| "unknown".to_owned(), Answer::to_string)
+}
+
+// Writes a generated input to stdout or a file. Everything else goes to stderr, so the input
+// can be piped straight into a day.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (options, args) = GenerateOptions::extract(args)?;
+    let number = match args.as_slice() {
+        [day] => selection::parse_days(day)?,
+        _ => return Err(crate::usage()),
+    };
+    let day = match number.as_slice() {
+        [number] => registry::find(*number).ok_or(format!("Day {} is not registered!", number))?,
+        _ => return Err("Inputs can only be generated for a single day!".to_owned()),
+    };
+    let size = options.size.unwrap_or(day.sizes.default);
+    check_size(day, size)?;
+
+    let start = Instant::now();
+    let generated = (day.generate)(&mut Rng::new(options.seed), size);
+    let elapsed = start.elapsed();
+    match &options.output {
+        Some(path) => fs::write(path, &generated.input)
+            .map_err(|err| format!("Could not write {}: {}", path.display(), err))?,
+        None => print!("{}", generated.input),
+    }
+    eprintln!(
+        "Generated day {} from seed {} ({}: {}) in {:.2?}",
+        day.number, options.seed, day.sizes.name, size, elapsed
+    );
+
+    if !options.check {
+        eprintln!("Star 1: {}", describe(&generated.part1));
+        if day.parts > 1 {
+            eprintln!("Star 2: {}", describe(&generated.part2));
+        }
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    let (star1, star2) = (day.solve)(&generated.input).map_err(|err| {
+        format!(
+            "Generated input doesn't parse: {}",
+            err.render(&generated.input)
+        )
+    })?;
+    let elapsed = start.elapsed();
+
+    let mut mismatches = 0;
+    let mut table = Table::new(&["Part", "Known", "Solved", "Result"]);
+    let solved = [
+        (1, generated.part1, Some(star1)),
+        (2, generated.part2, star2),
+    ];
+    for (part, known, solved) in solved.iter().take(day.parts as usize) {
+        let result = match known {
+            None => "-",
+            Some(_) if known == solved => "pass",
+            Some(_) => {
+                mismatches += 1;
+                "FAIL"
+            }
+        };
+        table.add_row(vec![
+            part.to_string(),
+            describe(known),
+            describe(solved),
+            result.to_owned(),
+        ]);
+    }
+    eprint!("{}", table);
+    eprintln!("Solved in {:.2?}", elapsed);
+
+    if mismatches > 0 {
+        return Err(format!(
+            "{} answer(s) don't match the generated ones!",
+            mismatches
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_options() {
+        let (options, rest) =
+            GenerateOptions::extract(&args(&["7", "--size", "100", "--seed", "3", "--check"]))
+                .unwrap();
+        assert_eq!(options.size, Some(100));
+        assert_eq!(options.seed, 3);
+        assert!(options.check);
+        assert!(options.output.is_none());
+        assert_eq!(rest, args(&["7"]));
+
+        let (options, _) = GenerateOptions::extract(&args(&["7"])).unwrap();
+        assert_eq!((options.size, options.seed), (None, DEFAULT_SEED));
+        assert!(GenerateOptions::extract(&args(&["7", "--size", "many"])).is_err());
+        assert!(GenerateOptions::extract(&args(&["7", "--seed"])).is_err());
+    }
+
+    #[test]
+    fn sizes_of_a_day() {
+        let day = registry::find(5).unwrap();
+        assert!(check_size(day, 100).is_ok());
+        assert!(check_size(day, 1).is_err());
+        assert!(check_size(day, 2000).is_err());
+        assert!(check_size(registry::find(1).unwrap(), 1_000_000).is_ok());
+    }
+}
@@ -0,0 +1,474 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+// Just enough HTTP for talking to the puzzle server, written out by hand like the rest of the
+// runner. Plain http is spoken directly, which is what local stand-in servers use. For https
+// the request is handed to curl, since there is no TLS without pulling in a lot of crates.
+
+const USER_AGENT: &str = "aoc-2020-runner";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    secure: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Url {
+    pub fn parse(url: &str) -> Result<Url, String> {
+        let invalid = || format!("Invalid URL '{}', expected http(s)://<host>[:<port>]!", url);
+        let (secure, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(invalid());
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+            None if secure => (authority, 443),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Url {
+            secure,
+            host: host.to_owned(),
+            port,
+            path: path.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    // The same server with a path below this one
+    pub fn join(&self, path: &str) -> Url {
+        Url {
+            path: format!("{}/{}", self.path, path.trim_start_matches('/')),
+            ..self.clone()
+        }
+    }
+}
+
+impl std::fmt::Display for Url {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scheme = if self.secure { "https" } else { "http" };
+        write!(f, "{}://{}:{}{}", scheme, self.host, self.port, self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    // Sent as a form with POST, None makes it a GET
+    pub form: Option<String>,
+}
+
+impl Request {
+    pub fn get(url: Url) -> Request {
+        Request {
+            url,
+            headers: Vec::new(),
+            form: None,
+        }
+    }
+
+    pub fn post(url: Url, form: &[(&str, &str)]) -> Request {
+        let form = form
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        Request {
+            form: Some(form),
+            ..Request::get(url)
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Request {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    pub fn send(&self) -> Result<Response, String> {
+        if self.url.secure {
+            return Response::parse(&self.send_with_curl()?);
+        }
+        let raw = self
+            .send_plain()
+            .map_err(|err| format!("Request to {} failed: {}", self.url, err))?;
+        let (mut response, body) = Response::parse_head(&raw)?;
+        // curl takes care of this itself, but keeps the header in what it prints. The chunks are
+        // counted in bytes, so they are put together before the body becomes text.
+        response.body = if response.is_chunked() {
+            String::from_utf8_lossy(&decode_chunks(body)?).into_owned()
+        } else {
+            String::from_utf8_lossy(body).into_owned()
+        };
+        Ok(response)
+    }
+
+    fn send_plain(&self) -> std::io::Result<Vec<u8>> {
+        let mut stream = TcpStream::connect((self.url.host.as_str(), self.url.port))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let method = if self.form.is_some() { "POST" } else { "GET" };
+        let path = if self.url.path.is_empty() {
+            "/"
+        } else {
+            &self.url.path
+        };
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}:{}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+            method, path, self.url.host, self.url.port, USER_AGENT
+        );
+        for (name, value) in self.headers.iter() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if let Some(form) = &self.form {
+            head.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            head.push_str(&format!("Content-Length: {}\r\n", form.len()));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        if let Some(form) = &self.form {
+            stream.write_all(form.as_bytes())?;
+        }
+
+        // The server closes the connection after answering
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw)?;
+        Ok(raw)
+    }
+
+    // The headers go through curl's stdin, so the session doesn't show up in the process list
+    fn send_with_curl(&self) -> Result<Vec<u8>, String> {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut config = format!(
+            "url = {}\nuser-agent = {}\n",
+            quote(&self.url.to_string()),
+            quote(USER_AGENT)
+        );
+        for (name, value) in self.headers.iter() {
+            config.push_str(&format!(
+                "header = {}\n",
+                quote(&format!("{}: {}", name, value))
+            ));
+        }
+        if let Some(form) = &self.form {
+            config.push_str(&format!("data = {}\n", quote(form)));
+        }
+
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--show-error", "--include", "--config", "-"])
+            .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Could not run curl for {}: {}", self.url, err))?;
+        curl.stdin
+            .take()
+            .expect("Stdin is piped")
+            .write_all(config.as_bytes())
+            .map_err(|err| format!("Could not pass the request to curl: {}", err))?;
+        let output = curl
+            .wait_with_output()
+            .map_err(|err| format!("curl failed: {}", err))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Request to {} failed: {}",
+                self.url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    // Status line, headers and body, like servers send them and `curl --include` prints them
+    fn parse(raw: &[u8]) -> Result<Response, String> {
+        let (mut response, body) = Response::parse_head(raw)?;
+        response.body = String::from_utf8_lossy(body).into_owned();
+        Ok(response)
+    }
+
+    // The status line and headers with an empty body, and the bytes of the body after them
+    fn parse_head(raw: &[u8]) -> Result<(Response, &[u8]), String> {
+        let end = find_bytes(raw, b"\r\n\r\n").ok_or("Incomplete response from the server!")?;
+        let head = String::from_utf8_lossy(&raw[..end]);
+        let mut lines = head.split("\r\n");
+        let status = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|status| status.parse().ok())
+            .ok_or("Invalid status line from the server!")?;
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+            .collect();
+        let response = Response {
+            status,
+            headers,
+            body: String::new(),
+        };
+        Ok((response, &raw[end + 4..]))
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    // Value of a header, by its name in lowercase
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Whether the body is sent in chunks, which is then the last of the transfer codings
+    fn is_chunked(&self) -> bool {
+        self.header("transfer-encoding")
+            .and_then(|codings| codings.rsplit(',').next())
+            .is_some_and(|coding| coding.trim().eq_ignore_ascii_case("chunked"))
+    }
+}
+
+// A body sent in chunks: the size of every chunk in hex on its own line, the chunk and a line
+// break, until a chunk of size 0
+fn decode_chunks(body: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "Invalid chunked response from the server!".to_owned();
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    loop {
+        let line_end = find_bytes(rest, b"\r\n").ok_or_else(invalid)?;
+        let size = std::str::from_utf8(&rest[..line_end]).map_err(|_| invalid())?;
+        let size = size.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid())?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let after = &rest[line_end + 2..];
+        let chunk = after.get(..size).ok_or_else(invalid)?;
+        decoded.extend_from_slice(chunk);
+        rest = after[size..].strip_prefix(b"\r\n").ok_or_else(invalid)?;
+    }
+}
+
+// Where the bytes first show up in the haystack
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn url_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// A local server that answers every request with the same response, for testing without a
+// network. It keeps the requests it got, the body included.
+#[cfg(test)]
+pub mod stand_in {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    pub struct StandIn {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StandIn {
+        pub fn start(status: &str, headers: &[&str], body: &str) -> StandIn {
+            let mut response = format!("HTTP/1.1 {}\r\n", status);
+            for header in headers {
+                response.push_str(&format!("{}\r\n", header));
+            }
+            response.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+            StandIn::serve(response.into_bytes())
+        }
+
+        // Sends the body in the given chunks instead of all at once, with the given codings in
+        // the Transfer-Encoding header
+        pub fn chunked(status: &str, codings: &str, chunks: &[&[u8]]) -> StandIn {
+            let mut response = format!(
+                "HTTP/1.1 {}\r\nTransfer-Encoding: {}\r\n\r\n",
+                status, codings
+            )
+            .into_bytes();
+            for chunk in chunks {
+                response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+                response.extend_from_slice(chunk);
+                response.extend_from_slice(b"\r\n");
+            }
+            response.extend_from_slice(b"0\r\n\r\n");
+            StandIn::serve(response)
+        }
+
+        fn serve(response: Vec<u8>) -> StandIn {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let seen = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = BufReader::new(stream.unwrap());
+                    let mut request = String::new();
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        stream.read_line(&mut line).unwrap();
+                        if let Some(value) = line.strip_prefix("Content-Length: ") {
+                            length = value.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                    }
+                    let mut body = vec![0; length];
+                    stream.read_exact(&mut body).unwrap();
+                    request.push_str(&String::from_utf8(body).unwrap());
+                    seen.lock().unwrap().push(request);
+                    stream.get_mut().write_all(&response).unwrap();
+                }
+            });
+            StandIn { url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stand_in::StandIn;
+    use super::*;
+
+    #[test]
+    fn parse_urls() {
+        let url = Url::parse("https://adventofcode.com").unwrap();
+        assert_eq!(url.to_string(), "https://adventofcode.com:443");
+        let url = Url::parse("http://127.0.0.1:8080/aoc/").unwrap();
+        assert_eq!(
+            url.join("/2020/day/7/input").to_string(),
+            "http://127.0.0.1:8080/aoc/2020/day/7/input"
+        );
+        assert!(Url::parse("ftp://example.com").is_err());
+        assert!(Url::parse("http://:80").is_err());
+        assert!(Url::parse("http://example.com:http").is_err());
+    }
+
+    #[test]
+    fn parse_responses() {
+        let response =
+            Response::parse(b"HTTP/2 404\r\ncontent-type: text/plain\r\n\r\nNot found").unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(
+            response.headers,
+            vec![("content-type".to_owned(), "text/plain".to_owned())]
+        );
+        assert_eq!(response.body, "Not found");
+        assert!(!response.is_success());
+        assert!(Response::parse(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+
+    #[test]
+    fn chunked_bodies() {
+        assert_eq!(
+            decode_chunks(b"5\r\n1721\n\r\n4;ext=1\r\n979\n\r\n0\r\n\r\n"),
+            Ok(b"1721\n979\n".to_vec())
+        );
+        assert!(decode_chunks(b"5\r\n1721\n").is_err());
+        assert!(decode_chunks(b"x\r\n1721\n\r\n0\r\n\r\n").is_err());
+
+        let long = "a".repeat(300);
+        let server = StandIn::chunked(
+            "200 OK",
+            "chunked",
+            &[b"1721\n979\n", b"366\n", long.as_bytes()],
+        );
+        let response = Request::get(Url::parse(&server.url).unwrap())
+            .send()
+            .unwrap();
+        assert_eq!(response.body, format!("1721\n979\n366\n{}", long));
+
+        // A chunk boundary in the middle of a character, with another spelling of the coding
+        let text = "1721 \u{2192} 979\n".as_bytes();
+        let server = StandIn::chunked("200 OK", "gzip, Chunked", &[&text[..6], &text[6..]]);
+        let response = Request::get(Url::parse(&server.url).unwrap())
+            .send()
+            .unwrap();
+        assert_eq!(response.body, "1721 \u{2192} 979\n");
+    }
+
+    #[test]
+    fn transfer_codings() {
+        let chunked = |codings: &str| {
+            let raw = format!("HTTP/1.1 200 OK\r\nTransfer-Encoding: {}\r\n\r\n", codings);
+            Response::parse(raw.as_bytes()).unwrap().is_chunked()
+        };
+        assert!(chunked("chunked"));
+        assert!(chunked("CHUNKED"));
+        assert!(chunked("gzip, chunked"));
+        assert!(!chunked("chunked, gzip"));
+        assert!(!chunked("gzip"));
+        assert!(!Response::parse(b"HTTP/1.1 200 OK\r\n\r\n")
+            .unwrap()
+            .is_chunked());
+    }
+
+    #[test]
+    fn talk_to_a_server() {
+        let server = StandIn::start("200 OK", &["X-Test: yes"], "1721\n979\n");
+        let url = Url::parse(&server.url).unwrap().join("input");
+        let response = Request::get(url.clone())
+            .header("Cookie", "session=abc")
+            .send()
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(response
+            .headers
+            .contains(&("x-test".to_owned(), "yes".to_owned())));
+        assert_eq!(response.body, "1721\n979\n");
+
+        Request::post(url, &[("level", "1"), ("answer", "a b&c")])
+            .send()
+            .unwrap();
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        assert!(requests[1].starts_with("POST /input HTTP/1.1\r\n"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"));
+    }
+}
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::time::Duration;
+
+use aoc_common::parse::ParseError;
+use aoc_common::solution::Answer;
+
+use crate::bench::Phase;
+use crate::timeout::Outcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+// Takes `--format <text|json>` out of the arguments and returns the remaining ones untouched
+pub fn extract_format(args: &[String]) -> Result<(Format, Vec<String>), String> {
+    let mut format = Format::Text;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = match args.next().map(String::as_str) {
+                Some("text") => Format::Text,
+                Some("json") => Format::Json,
+                Some(other) => return Err(format!("Unknown format '{}'!", other)),
+                None => return Err("Missing format after --format!".to_owned()),
+            };
+        } else {
+            rest.push(arg.to_owned());
+        }
+    }
+    Ok((format, rest))
+}
+
+// Just enough JSON for the runner's output, written out by hand like the rest of the runner
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Number(i64),
+    String(String),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Number(number) => Json::Number(*number),
+            Answer::Text(text) => Json::String(text.to_owned()),
+        }
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl From<String> for Json {
+    fn from(text: String) -> Json {
+        Json::String(text)
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for ch in text.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            ch if (ch as u32) < 0x20 => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => write!(f, "{}", ch)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+// Compact, on a single line, so every day can be printed as one line of JSON
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(text) => write_string(f, text),
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// FNV-1a, to tell inputs apart without printing them
+fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("fnv1a64:{:016x}", hash)
+}
+
+// What identifies a run: the day, the implementation and the input, whose hash is null if it
+// couldn't be read
+fn run_fields(
+    day: u32,
+    implementation: &str,
+    path: Option<String>,
+    input: Option<&str>,
+) -> Vec<(&'static str, Json)> {
+    vec![
+        ("day", Json::Number(day as i64)),
+        ("implementation", Json::String(implementation.to_owned())),
+        (
+            "input",
+            Json::Object(vec![
+                ("path", path.into()),
+                ("hash", input.map(input_hash).into()),
+            ]),
+        ),
+    ]
+}
+
+// A day whose input couldn't be read, with why instead of answers and timings
+pub fn read_error_report(
+    day: u32,
+    implementation: &str,
+    path: Option<String>,
+    message: &str,
+) -> Json {
+    let mut fields = run_fields(day, implementation, path, None);
+    fields.push(("star1", Json::Null));
+    fields.push(("star2", Json::Null));
+    fields.push(("read_error", Json::String(message.to_owned())));
+    Json::Object(fields)
+}
+
+// A day whose input couldn't be parsed, with where and why instead of answers and timings
+pub fn parse_error_report(
+    day: u32,
+    implementation: &str,
+    path: Option<String>,
+    input: &str,
+    err: &ParseError,
+) -> Json {
+    let mut fields = run_fields(day, implementation, path, Some(input));
+    fields.push(("star1", Json::Null));
+    fields.push(("star2", Json::Null));
+    fields.push((
+        "parse_error",
+        Json::Object(vec![
+            ("line", Json::Number(err.line as i64)),
+            ("column", Json::Number(err.column as i64)),
+            ("message", Json::String(err.to_string())),
+        ]),
+    ));
+    Json::Object(fields)
+}
+
+// Everything about running a day, the input path is None for stdin. Days that ran out of time
+// or panicked say in which phase, and only have the answers and timings of the phases before it.
+pub fn day_report(
+    day: u32,
+    implementation: &str,
+    path: Option<String>,
+    input: &str,
+    outcome: &Outcome,
+) -> Json {
+    let Outcome {
+        star1,
+        star2,
+        laps,
+        timed_out,
+        panicked,
+    } = outcome;
+    let mut phases = Vec::new();
+    let mut phase_start = Duration::ZERO;
+    for (phase, end) in Phase::ALL.iter().zip(laps.iter()) {
+        if *phase != Phase::Part2 || star2.is_some() {
+            let nanos = (*end - phase_start).as_nanos() as i64;
+            phases.push((phase.name(), Json::Number(nanos)));
+        }
+        phase_start = *end;
+    }
+
+    let mut fields = run_fields(day, implementation, path, Some(input));
+    fields.push(("star1", star1.as_ref().into()));
+    fields.push(("star2", star2.as_ref().into()));
+    fields.push(("nanoseconds", Json::Object(phases)));
+    if let Some(phase) = timed_out {
+        fields.push(("timed_out", Json::String(phase.name().to_owned())));
+    }
+    if let Some((phase, message)) = panicked {
+        fields.push((
+            "panicked",
+            Json::Object(vec![
+                ("phase", Json::String(phase.name().to_owned())),
+                ("message", Json::String(message.to_owned())),
+            ]),
+        ));
+    }
+    Json::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_values() {
+        let json = Json::Object(vec![
+            ("day", Json::Number(21)),
+            ("star1", (&Answer::from(2786)).into()),
+            ("star2", (&Answer::from("mxmxvkd,sqjhc")).into()),
+            ("missing", Json::from(None::<String>)),
+            ("nested", Json::Object(vec![])),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r#"{"day":21,"star1":2786,"star2":"mxmxvkd,sqjhc","missing":null,"nested":{}}"#
+        );
+    }
+
+    #[test]
+    fn report_of_a_day() {
+        let mut outcome = Outcome {
+            star1: Some(Answer::from(6198540)),
+            star2: None,
+            laps: vec![
+                Duration::from_nanos(10),
+                Duration::from_nanos(25),
+                Duration::from_nanos(26),
+            ],
+            timed_out: None,
+            panicked: None,
+        };
+        let report = day_report(25, "default", None, "5764801\n17807724\n", &outcome);
+        assert_eq!(
+            report.to_string(),
+            concat!(
+                r#"{"day":25,"implementation":"default","#,
+                r#""input":{"path":null,"hash":"fnv1a64:"#,
+                r#"f6ecbcc9cade9bc2"},"star1":6198540,"star2":null,"#,
+                r#""nanoseconds":{"parse":10,"part1":15}}"#
+            )
+        );
+
+        outcome.star1 = None;
+        outcome.laps.truncate(1);
+        outcome.timed_out = Some(Phase::Part1);
+        let report = day_report(25, "default", None, "", &outcome);
+        assert!(report.to_string().ends_with(
+            r#""star1":null,"star2":null,"nanoseconds":{"parse":10},"timed_out":"part1"}"#
+        ));
+
+        outcome.timed_out = None;
+        outcome.panicked = Some((Phase::Part1, "Key 1 can't be reached!".to_owned()));
+        let report = day_report(25, "default", None, "", &outcome);
+        assert!(report.to_string().ends_with(
+            r#""nanoseconds":{"parse":10},"panicked":{"phase":"part1","message":"Key 1 can't be reached!"}}"#
+        ));
+    }
+
+    #[test]
+    fn report_of_a_parse_error() {
+        let input = "1721\n9x9\n";
+        let err = ParseError::at(input, &input[5..8], "a number");
+        let report = parse_error_report(1, "default", None, input, &err);
+        assert!(report.to_string().ends_with(concat!(
+            r#""star1":null,"star2":null,"parse_error":{"line":2,"column":1,"#,
+            r#""message":"line 2, column 1: expected a number, found \"9x9\""}}"#
+        )));
+    }
+
+    #[test]
+    fn report_of_a_read_error() {
+        let report = read_error_report(4, "default", Some("day-04.txt".to_owned()), "Missing!");
+        assert_eq!(
+            report.to_string(),
+            concat!(
+                r#"{"day":4,"implementation":"default","input":{"path":"day-04.txt","hash":null},"#,
+                r#""star1":null,"star2":null,"read_error":"Missing!"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn hash_inputs() {
+        assert_eq!(input_hash(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(input_hash("a"), "fnv1a64:af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn extract_formats() {
+        let args: Vec<String> = vec!["7".to_owned(), "--format".to_owned(), "json".to_owned()];
+        let (format, rest) = extract_format(&args).unwrap();
+        assert_eq!(format, Format::Json);
+        assert_eq!(rest, vec!["7".to_owned()]);
+        assert!(extract_format(&args[..2]).is_err());
+        assert_eq!(extract_format(&[]).unwrap().0, Format::Text);
+    }
+
+    #[test]
+    fn escape_strings() {
+        let json = Json::String("say \"hi\"\\\n\u{1}ü".to_owned());
+        assert_eq!(json.to_string(), r#""say \"hi\"\\\n\u0001ü""#);
+    }
+}
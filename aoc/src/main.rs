@@ -0,0 +1,442 @@
+mod animate;
+mod batch;
+mod bench;
+mod fetch;
+mod generate;
+mod http;
+mod json;
+mod memory;
+mod params;
+mod registry;
+mod remote;
+mod selection;
+mod strategies;
+mod submit;
+mod table;
+mod timeout;
+mod verify;
+mod watch;
+
+use std::env;
+use std::process;
+use std::time::{Duration, Instant};
+
+use aoc_common::input::{InputOptions, InputSource, INPUT_USAGE};
+use aoc_common::params::{Override, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::solution::Answer;
+use bench::Phase;
+use json::Format;
+use registry::Day;
+use table::Table;
+
+// Counts allocations for 'bench --memory', does nothing else until that turns it on
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+const USAGE: &str =
+    "Usage: aoc run <days> [--impl <name>] [--format <text|json>] [--timeout <seconds>]
+               [--input <path> | --simple]
+       aoc bench <days> [--runs <count>] [--save] [--baseline <path>] [--memory]
+                [--input <path> | --simple]
+       aoc strategies [<days>]
+       aoc check <days> [--slow] [--input <path> | --simple]
+       aoc params [<days>]
+       aoc verify [<days>]
+       aoc generate <day> [--size <count>] [--seed <number>] [--output <path>] [--check]
+       aoc animate <day> [--part <1|2>] [--steps <count>] [--delay <ms>] [--dump <dir>]
+       aoc batch <day> <dir> [--impl <name>] [--jobs <count>]
+       aoc fetch <days> [--base-url <url>]
+       aoc submit <day> <1|2> [--impl <name>] [--input <path>] [--base-url <url>]
+                  [--history <path>]
+       aoc watch <day> [--impl <name>] [--interval <ms>] [--input <path> | --simple]
+
+Days can be a single day (7), a range (1..=25 or 1..26), a comma separated list
+of those (1,3,10..=12) or 'all'.
+
+Some days have more than one implementation. 'strategies' lists them, '--impl' picks
+one to run and 'check' runs all of them to see if they agree. Implementations that
+are too slow for the full input are only checked with '--slow'.
+
+Some days have parameters for puzzle constants, like the number of turns to play.
+'params' lists them with their defaults. 'run', 'bench' and 'check' take any number
+of '--param <name>=<value>' to override them for a single day.
+
+'--timeout' gives every day that many seconds (fractions are fine) to solve both parts.
+Parts that run out of time are reported as timed out and the remaining days still run,
+and so are parts that panic.
+
+'verify' solves the full and sample inputs of every day and compares the answers
+with the expected ones in inputs/answers.txt.
+
+'generate' writes a random input for a day to stdout, or to the file given with
+'--output'. The size counts something different for every day, like rules or tiles,
+and the seed defaults to 2020. The answers the generator knows are printed to stderr,
+'--check' also solves the input and compares them.
+
+'animate' records every step of a part that simulates something, like the seats of
+day 11, and replays them in the terminal. '--steps' limits how many steps are recorded,
+'--delay' sets the time between frames (default 100ms) and '--dump' writes the frames
+to numbered text files in a directory instead. Input options and '--param' work like
+they do for 'run'.
+
+'batch' solves a day for every file in a directory, e.g. the inputs of a whole team,
+on '--jobs' threads (default one per core), and prints the answers and timings of each.
+Files that fail to parse or make the solver panic are flagged instead of stopping the
+run. '--param' works like it does for 'run'.
+
+'fetch' downloads the inputs of the given days to inputs/day-NN.txt. Days that already
+have an input there are skipped, so it is never downloaded twice. The session token is
+taken from AOC_SESSION or the 'session' key in the config file, which is AOC_CONFIG or
+~/.config/aoc/config with 'key = value' lines. The server is https://adventofcode.com
+unless '--base-url', AOC_BASE_URL or the 'base-url' key say otherwise.
+
+'submit' solves a part of a day and sends the answer to the same server. Every answer
+sent is recorded in a history file (default target/submissions.txt). Answers that were
+already rejected are never sent again, and nothing is sent until the wait the server
+asked for after the last answer is over. '--impl' and '--param' work like they do for 'run'.
+
+'watch' solves a day every time its full or sample input changes, or only the input
+picked with '--input' or '--simple', and shows the answers and timings next to those of
+the run before. Inputs are checked every '--interval' milliseconds (default 500) and read
+from disk each time, so editing them needs no rebuild. Changes to the solution itself
+still do. '--impl' and '--param' work like they do for 'run'.
+
+Bench options:
+  --runs <count>     How many times to run each day (default 10)
+  --save             Save the results as the new baseline
+  --baseline <path>  Where the baseline is kept (default target/bench-baseline.txt)
+  --memory           Also count the allocations, bytes allocated and peak memory of
+                     every phase, from one extra run";
+
+fn usage() -> String {
+    format!("{}\n\n{}", USAGE, INPUT_USAGE)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("bench") => bench::run(&args[1..]),
+        Some("strategies") => strategies::list(&args[1..]),
+        Some("check") => strategies::check(&args[1..]),
+        Some("params") => params::list(&args[1..]),
+        Some("verify") => verify::run(&args[1..]),
+        Some("generate") => generate::run(&args[1..]),
+        Some("animate") => animate::run(&args[1..]),
+        Some("batch") => batch::run(&args[1..]),
+        Some("fetch") => fetch::run(&args[1..]),
+        Some("submit") => submit::run(&args[1..]),
+        Some("watch") => watch::run(&args[1..]),
+        _ => Err(usage()),
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+// Days picked on the command line, with the options for reading their inputs and the
+// parameters they were given
+struct Selection {
+    options: InputOptions,
+    overrides: Vec<Override>,
+    days: Vec<&'static Day>,
+}
+
+impl Selection {
+    fn from_args(args: &[String]) -> Result<Selection, String> {
+        let (options, args) = InputOptions::extract(args)?;
+        let (overrides, args) = params::extract_params(&args)?;
+        let selection = match args.as_slice() {
+            [selection] => selection,
+            _ => return Err(usage()),
+        };
+        let numbers = selection::parse_days(selection)?;
+        if options.input.is_some() && numbers.len() > 1 {
+            return Err("--input can only be used when running a single day!".to_owned());
+        }
+        if !overrides.is_empty() && numbers.len() > 1 {
+            return Err("--param can only be used when running a single day!".to_owned());
+        }
+
+        let days = numbers
+            .into_iter()
+            .map(|number| {
+                registry::find(number).ok_or(format!("Day {} is not registered!", number))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for day in days.iter() {
+            params::for_day(day, &overrides)?;
+        }
+        Ok(Selection {
+            options,
+            overrides,
+            days,
+        })
+    }
+
+    fn params(&self, day: &Day) -> Params {
+        params::for_day(day, &self.overrides).expect("Parameters were checked before")
+    }
+
+    fn read_input(&self, day: &Day) -> Result<String, String> {
+        self.options.source(day.number).read()
+    }
+
+    fn parse_error(&self, day: &Day, input: &str, err: ParseError) -> String {
+        let source = self.options.source(day.number);
+        format!("Could not parse {} at {}", source, err.render(input))
+    }
+}
+
+// A selected day with its input, ready to be solved unless the input couldn't be read
+struct Job {
+    day: &'static Day,
+    name: &'static str,
+    input: Result<String, String>,
+    params: Params,
+}
+
+// Why a job didn't get to solve anything
+#[derive(Debug)]
+enum Failure {
+    Unreadable(String),
+    Unparsed(ParseError),
+}
+
+// How far solving a job got, or why it didn't, with the time it took in total
+struct Solved {
+    outcome: Result<timeout::Outcome, Failure>,
+    elapsed: Duration,
+}
+
+// Solves the jobs one after the other. Days whose input can't be read or parsed, that run out
+// of time or panic don't stop the ones after them.
+fn solve_all(jobs: &[Job], budget: Option<Duration>) -> Vec<Solved> {
+    jobs.iter()
+        .map(|job| {
+            let start = Instant::now();
+            let outcome = match &job.input {
+                Ok(input) => timeout::solve(job.day, input, &job.params, job.name, budget)
+                    .map_err(Failure::Unparsed),
+                Err(message) => Err(Failure::Unreadable(message.to_owned())),
+            };
+            Solved {
+                outcome,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+// Fails if any of the days couldn't be read or parsed, ran out of time or panicked, once all of
+// them were reported
+fn verdict(jobs: &[Job], solved: &[Solved]) -> Result<(), String> {
+    let days = |failed: fn(&Result<timeout::Outcome, Failure>) -> bool| -> Vec<String> {
+        jobs.iter()
+            .zip(solved.iter())
+            .filter(|(_, solved)| failed(&solved.outcome))
+            .map(|(job, _)| job.day.number.to_string())
+            .collect()
+    };
+    let mut problems = Vec::new();
+    let unreadable = days(|outcome| matches!(outcome, Err(Failure::Unreadable(_))));
+    if !unreadable.is_empty() {
+        problems.push(format!(
+            "Could not read the input of day(s) {}!",
+            unreadable.join(", ")
+        ));
+    }
+    let unparsed = days(|outcome| matches!(outcome, Err(Failure::Unparsed(_))));
+    if !unparsed.is_empty() {
+        problems.push(format!(
+            "Could not parse the input of day(s) {}!",
+            unparsed.join(", ")
+        ));
+    }
+    let timed_out = days(|outcome| {
+        outcome
+            .as_ref()
+            .is_ok_and(|outcome| outcome.timed_out.is_some())
+    });
+    if !timed_out.is_empty() {
+        problems.push(format!(
+            "Ran out of time on day(s) {}!",
+            timed_out.join(", ")
+        ));
+    }
+    let panicked = days(|outcome| {
+        outcome
+            .as_ref()
+            .is_ok_and(|outcome| outcome.panicked.is_some())
+    });
+    if !panicked.is_empty() {
+        problems.push(format!("Panicked on day(s) {}!", panicked.join(", ")));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join(" "))
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (format, args) = json::extract_format(args)?;
+    let (strategy, args) = strategies::extract_impl(&args)?;
+    let (budget, args) = timeout::extract_timeout(&args)?;
+    let selection = Selection::from_args(&args)?;
+    if let Some(name) = strategy.as_deref() {
+        for day in selection.days.iter() {
+            strategies::find(day, name)?;
+        }
+    }
+
+    let mut jobs = Vec::new();
+    for day in selection.days.iter() {
+        jobs.push(Job {
+            day,
+            name: match strategy.as_deref() {
+                Some(name) => strategies::find(day, name)?.name,
+                None => (day.strategies)()[0].name,
+            },
+            input: selection.read_input(day),
+            params: selection.params(day),
+        });
+    }
+    let solved = solve_all(&jobs, budget);
+
+    let mut table = Table::new(&["Day", "Star 1", "Star 2", "Time"]);
+    for (job, Solved { outcome, elapsed }) in jobs.iter().zip(solved.iter()) {
+        let day = job.day;
+        // Only empty when the input couldn't be read, which the outcome says as well
+        let input = job.input.as_deref().unwrap_or_default();
+        match outcome {
+            Err(Failure::Unreadable(message)) => eprintln!("{}", message),
+            Err(Failure::Unparsed(err)) => {
+                eprintln!("{}", selection.parse_error(day, input, err.clone()))
+            }
+            Ok(_) => {}
+        }
+        match format {
+            Format::Text => {
+                let (star1, star2) = match outcome {
+                    Ok(outcome) => (
+                        star_cell(outcome, Phase::Part1, &outcome.star1),
+                        star_cell(outcome, Phase::Part2, &outcome.star2),
+                    ),
+                    Err(Failure::Unreadable(_)) => ("no input".to_owned(), "-".to_owned()),
+                    Err(Failure::Unparsed(err)) => {
+                        (format!("parse error at line {}", err.line), "-".to_owned())
+                    }
+                };
+                table.add_row(vec![
+                    day.number.to_string(),
+                    star1,
+                    star2,
+                    format!("{:.2?}", elapsed),
+                ]);
+            }
+            Format::Json => {
+                let path = match selection.options.source(day.number) {
+                    InputSource::File(path) => Some(path.display().to_string()),
+                    InputSource::Stdin => None,
+                };
+                let report = match outcome {
+                    Ok(outcome) => json::day_report(day.number, job.name, path, input, outcome),
+                    Err(Failure::Unparsed(err)) => {
+                        json::parse_error_report(day.number, job.name, path, input, err)
+                    }
+                    Err(Failure::Unreadable(message)) => {
+                        json::read_error_report(day.number, job.name, path, message)
+                    }
+                };
+                println!("{}", report);
+            }
+        }
+    }
+    if format == Format::Text {
+        print!("{}", table);
+    }
+    verdict(&jobs, &solved)
+}
+
+fn star_cell(outcome: &timeout::Outcome, part: Phase, answer: &Option<Answer>) -> String {
+    if outcome.ran_out(part) {
+        return "timed out".to_owned();
+    }
+    // The message goes with the first star that didn't get an answer, the ones after it stay empty
+    if let Some((phase, message)) = &outcome.panicked {
+        if (*phase).max(Phase::Part1) == part {
+            return format!("panicked: {}", message);
+        }
+    }
+    answer
+        .as_ref()
+        .map_or_else(|| "-".to_owned(), Answer::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input::default_path;
+
+    fn job(number: u32, overrides: &[Override]) -> Job {
+        let day = registry::find(number).unwrap();
+        Job {
+            day,
+            name: "default",
+            input: InputSource::File(default_path(number, true)).read(),
+            params: params::for_day(day, overrides).unwrap(),
+        }
+    }
+
+    #[test]
+    fn keep_going_after_a_panic() {
+        // Day 25 can't reach the sample keys with this subject and panics
+        let jobs = [
+            job(25, &[("subject".to_owned(), "6".to_owned())]),
+            job(7, &[]),
+        ];
+        let solved = solve_all(&jobs, None);
+        let panicked = solved[0].outcome.as_ref().unwrap();
+        assert_eq!(
+            panicked.panicked.as_ref().map(|(phase, _)| *phase),
+            Some(Phase::Part1)
+        );
+        assert!(star_cell(panicked, Phase::Part1, &None).starts_with("panicked: Key "));
+        assert_eq!(star_cell(panicked, Phase::Part2, &None), "-");
+        let solved_7 = solved[1].outcome.as_ref().unwrap();
+        assert_eq!(solved_7.star1, Some(Answer::Number(4)));
+        assert_eq!(solved_7.star2, Some(Answer::Number(32)));
+        assert_eq!(
+            verdict(&jobs, &solved),
+            Err("Panicked on day(s) 25!".to_owned())
+        );
+        assert_eq!(verdict(&jobs[1..], &solved[1..]), Ok(()));
+    }
+
+    #[test]
+    fn keep_going_without_an_input() {
+        let mut broken = job(7, &[]);
+        broken.input = Ok("light red bags contain lots of bags.\n".to_owned());
+        let mut missing = job(8, &[]);
+        missing.input = Err("Could not read input day-08.txt".to_owned());
+        let jobs = [broken, missing, job(7, &[])];
+        let solved = solve_all(&jobs, None);
+        assert!(matches!(solved[0].outcome, Err(Failure::Unparsed(ref err)) if err.line == 1));
+        assert!(matches!(solved[1].outcome, Err(Failure::Unreadable(_))));
+        assert_eq!(
+            solved[2].outcome.as_ref().unwrap().star1,
+            Some(Answer::Number(4))
+        );
+        assert_eq!(
+            verdict(&jobs, &solved),
+            Err("Could not read the input of day(s) 8! \
+                 Could not parse the input of day(s) 7!"
+                .to_owned())
+        );
+    }
+}
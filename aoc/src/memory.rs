@@ -0,0 +1,206 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use aoc_common::params::Params;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::Solution;
+
+use crate::bench::Phase;
+
+// The system allocator, counting what every thread allocates once `enable` was called. Until
+// then it only checks a flag, so runs without memory reports aren't slowed down. Solvers run
+// on a single thread, the counts are kept per thread so nothing else gets mixed in.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Live bytes can go below zero when memory from before a measurement is freed during it
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // Threads that are shutting down have no counters anymore
+    let _ = COUNTERS.try_with(|counters| {
+        let mut current = counters.get();
+        f(&mut current);
+        counters.set(current);
+    });
+}
+
+fn allocated(size: usize) {
+    update(|counters| {
+        counters.allocations += 1;
+        counters.bytes += size;
+        counters.live += size as isize;
+        counters.peak = counters.peak.max(counters.live);
+    });
+}
+
+fn freed(size: usize) {
+    update(|counters| counters.live -= size as isize);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    // Counted as a new allocation, which it often is
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// What a piece of code allocated. The peak is the most memory it held at once on top of
+// what was there before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+// Runs `f` and counts its allocations, all zero unless counting was enabled
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let before = COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.peak = current.live;
+        counters.set(current);
+        current
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    let usage = Usage {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as usize,
+    };
+    (result, usage)
+}
+
+// Allocations of every phase a day has, day 25 has no second part
+pub type Usages = Vec<(Phase, Usage)>;
+
+// Runs every phase of a day once and counts what it allocates
+pub fn measure<S: Solution>(input: &str, params: &Params) -> Result<Usages, ParseError> {
+    let (parsed, usage) = track(|| S::parse_with(input, params));
+    let parsed = parsed?;
+    let mut usages = vec![(Phase::Parse, usage)];
+
+    let (_, usage) = track(|| black_box(S::part1(&parsed)));
+    usages.push((Phase::Part1, usage));
+    let (answer, usage) = track(|| black_box(S::part2(&parsed)));
+    if answer.is_some() {
+        usages.push((Phase::Part2, usage));
+    }
+    Ok(usages)
+}
+
+// Sizes in powers of 1024 with one decimal, bytes as they are
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_allocations() {
+        enable();
+        let (kept, usage) = track(|| {
+            let scratch = vec![1u8; 1 << 20];
+            drop(black_box(scratch));
+            vec![2u8; 1 << 10]
+        });
+        assert_eq!(kept.len(), 1 << 10);
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.bytes, (1 << 20) + (1 << 10));
+        assert_eq!(usage.peak, 1 << 20);
+
+        // Memory from before that is freed doesn't make the peak negative
+        let (_, usage) = track(|| drop(kept));
+        assert_eq!(usage, Usage::default());
+    }
+
+    #[test]
+    fn measure_phases() {
+        enable();
+        let overrides = [("turns2".to_owned(), "100000".to_owned())];
+        let params = Params::with_overrides(day_15_rust::Day15::PARAMS, &overrides).unwrap();
+        let usages = measure::<day_15_rust::Day15>("0,3,6", &params).unwrap();
+        let phases: Vec<Phase> = usages.iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Part1, Phase::Part2]);
+        // Part 2 keeps the last turn of every number that can be spoken
+        assert!(usages[2].1.peak >= 100_000 * 8);
+        assert!(usages[2].1.bytes >= usages[2].1.peak);
+        assert!(measure::<day_15_rust::Day15>("0,x", &params).is_err());
+    }
+
+    #[test]
+    fn human_sizes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(120_000_000), "114.4 MiB");
+        assert_eq!(format_bytes(5 << 40), "5.0 TiB");
+        assert_eq!(format_bytes(5 << 50), "5120.0 TiB");
+    }
+}
@@ -0,0 +1,102 @@
+use aoc_common::params::{self, Override, Params};
+
+use crate::registry::{self, Day};
+use crate::selection;
+use crate::table::Table;
+
+// Takes every `--param <name>=<value>` out of the arguments and returns the remaining ones
+// untouched. Later values for the same name win.
+pub fn extract_params(args: &[String]) -> Result<(Vec<Override>, Vec<String>), String> {
+    let mut overrides = Vec::new();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--param" {
+            let value = args.next().ok_or("Missing <name>=<value> after --param!")?;
+            overrides.push(params::parse_override(value)?);
+        } else {
+            rest.push(arg.to_owned());
+        }
+    }
+    Ok((overrides, rest))
+}
+
+pub fn for_day(day: &Day, overrides: &[Override]) -> Result<Params, String> {
+    Params::with_overrides(day.params, overrides)
+        .map_err(|err| format!("Day {}: {}", day.number, err))
+}
+
+pub fn list(args: &[String]) -> Result<(), String> {
+    let numbers = match args {
+        [] => selection::parse_days("all")?,
+        [selection] => selection::parse_days(selection)?,
+        _ => return Err(crate::usage()),
+    };
+
+    let mut table = Table::new(&["Day", "Parameter", "Default", "Description"]);
+    for day in numbers.into_iter().filter_map(registry::find) {
+        for param in day.params.iter() {
+            table.add_row(vec![
+                day.number.to_string(),
+                param.name.to_owned(),
+                param.default.to_owned(),
+                param.description.to_owned(),
+            ]);
+        }
+    }
+
+    if table.is_empty() {
+        println!("None of the selected days have parameters.");
+        return Ok(());
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_overrides() {
+        let (overrides, rest) = extract_params(&args(&[
+            "15",
+            "--param",
+            "turns2=1000",
+            "--param",
+            "turns1=10",
+        ]))
+        .unwrap();
+        assert_eq!(
+            overrides,
+            vec![
+                ("turns2".to_owned(), "1000".to_owned()),
+                ("turns1".to_owned(), "10".to_owned())
+            ]
+        );
+        assert_eq!(rest, args(&["15"]));
+        assert!(extract_params(&args(&["15", "--param"])).is_err());
+        assert!(extract_params(&args(&["15", "--param", "turns2"])).is_err());
+    }
+
+    #[test]
+    fn params_of_a_day() {
+        let day15 = registry::find(15).unwrap();
+        let overrides = vec![("turns1".to_owned(), "10".to_owned())];
+        assert_eq!(
+            for_day(day15, &overrides).unwrap().get::<usize>("turns1"),
+            10
+        );
+        let overrides = vec![("rounds".to_owned(), "10".to_owned())];
+        let err = for_day(day15, &overrides).unwrap_err();
+        assert!(
+            err.starts_with("Day 15: Unknown parameter 'rounds'"),
+            "{}",
+            err
+        );
+    }
+}
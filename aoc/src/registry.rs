@@ -0,0 +1,199 @@
+use crate::bench::{self, Timings};
+use crate::memory::{self, Usages};
+use aoc_common::frames::Frames;
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::params::{Param, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{self, Answer, Answers, Solution, StrategyInfo};
+
+// Solves with the named implementation and calls back after every phase, see `solve_timed`
+pub type SolveTimed =
+    fn(&str, &Params, &str, &mut dyn FnMut(Option<&Answer>)) -> Option<Result<Answers, ParseError>>;
+
+// Solves with the named implementation and parameters, see `solve_with`
+pub type SolveWith = fn(&str, &Params, &str) -> Option<Result<Answers, ParseError>>;
+
+// Records the frames of a part with the given parameters and number of steps, see
+// `Solution::frames`
+pub type RecordFrames = fn(&str, &Params, u32, Option<usize>) -> Result<Option<Frames>, ParseError>;
+
+// Every day is registered here through its `Solution`, which parses the input and solves both stars
+pub struct Day {
+    pub number: u32,
+    pub parts: u32,
+    pub solve: fn(&str) -> Result<Answers, ParseError>,
+    pub solve_part: fn(&str, u32) -> Result<Option<Answer>, ParseError>,
+    pub bench: fn(&str, &Params, usize) -> Result<Timings, ParseError>,
+    pub memory: fn(&str, &Params) -> Result<Usages, ParseError>,
+    pub strategies: fn() -> Vec<StrategyInfo>,
+    pub params: &'static [Param],
+    pub solve_with: SolveWith,
+    pub solve_timed: SolveTimed,
+    pub frames: RecordFrames,
+    pub generate: fn(&mut Rng, usize) -> Generated,
+    pub sizes: Sizes,
+}
+
+// What the size of a generated input counts and which sizes the generator supports
+#[derive(Debug, Clone, Copy)]
+pub struct Sizes {
+    pub name: &'static str,
+    pub default: usize,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Day {
+    const fn of<S: Solution + Generator>() -> Day {
+        Day {
+            number: S::DAY,
+            parts: S::PARTS,
+            solve: solution::solve::<S>,
+            solve_part: solution::solve_part::<S>,
+            bench: bench::measure::<S>,
+            memory: memory::measure::<S>,
+            strategies: solution::strategies::<S>,
+            params: S::PARAMS,
+            solve_with: solution::solve_with::<S>,
+            solve_timed: solution::solve_timed::<S>,
+            frames: solution::frames::<S>,
+            generate: S::generate,
+            sizes: Sizes {
+                name: S::SIZE,
+                default: S::DEFAULT_SIZE,
+                min: S::MIN_SIZE,
+                max: S::MAX_SIZE,
+            },
+        }
+    }
+}
+
+pub const DAYS: [Day; 25] = [
+    Day::of::<day_01_rust::Day01>(),
+    Day::of::<day_02_rust::Day02>(),
+    Day::of::<day_03_rust::Day03>(),
+    Day::of::<day_04_rust::Day04>(),
+    Day::of::<day_05_rust::Day05>(),
+    Day::of::<day_06_rust::Day06>(),
+    Day::of::<day_07_rust::Day07>(),
+    Day::of::<day_08_rust::Day08>(),
+    Day::of::<day_09_rust::Day09>(),
+    Day::of::<day_10_rust::Day10>(),
+    Day::of::<day_11_rust::Day11>(),
+    Day::of::<day_12_rust::Day12>(),
+    Day::of::<day_13_rust::Day13>(),
+    Day::of::<day_14_rust::Day14>(),
+    Day::of::<day_15_rust::Day15>(),
+    Day::of::<day_16_rust::Day16>(),
+    Day::of::<day_17_rust::Day17>(),
+    Day::of::<day_18_rust::Day18>(),
+    Day::of::<day_19_rust::Day19>(),
+    Day::of::<day_20_rust::Day20>(),
+    Day::of::<day_21_rust::Day21>(),
+    Day::of::<day_22_rust::Day22>(),
+    Day::of::<day_23_rust::Day23>(),
+    Day::of::<day_24_rust::Day24>(),
+    Day::of::<day_25_rust::Day25>(),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers::Manifest;
+    use aoc_common::input::{default_path, named_path, InputSource};
+
+    #[test]
+    fn days_are_registered_in_order() {
+        for (idx, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, idx + 1);
+        }
+    }
+
+    #[test]
+    fn find_day() {
+        assert_eq!(find(7).unwrap().number, 7);
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn strategy_names_are_unique() {
+        for day in DAYS.iter() {
+            let mut names: Vec<&str> = (day.strategies)().iter().map(|info| info.name).collect();
+            let count = names.len();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(names.len(), count, "Day {}", day.number);
+        }
+    }
+
+    #[test]
+    fn default_sizes_are_supported() {
+        for day in DAYS.iter() {
+            let sizes = day.sizes;
+            assert!(sizes.min <= sizes.default && sizes.default <= sizes.max);
+        }
+    }
+
+    #[test]
+    fn default_params_pass_their_checks() {
+        for day in DAYS.iter() {
+            for param in day.params.iter() {
+                assert_eq!((param.check)(param.default), Ok(()), "Day {}", day.number);
+            }
+        }
+    }
+
+    #[test]
+    fn solve_day() {
+        let input = InputSource::File(default_path(7, true)).read().unwrap();
+        let answers = (find(7).unwrap().solve)(&input).unwrap();
+        assert_eq!(answers, (Answer::Number(4), Some(Answer::Number(32))));
+    }
+
+    #[test]
+    fn crlf_inputs() {
+        // Saved on Windows, with blank lines that aren't quite empty between the paragraphs. Only
+        // the parts with an expected answer are solved, the others may need other parameters.
+        let manifest = Manifest::load().unwrap();
+        for day in DAYS.iter() {
+            for name in manifest.inputs(day.number) {
+                if !name.starts_with("simple") {
+                    continue;
+                }
+                let path = named_path(day.number, name).unwrap();
+                let clean = InputSource::File(path).read().unwrap();
+                let crlf = clean
+                    .replace('\n', "\r\n")
+                    .replace("\r\n\r\n", "\r\n \r\n\t\r\n");
+                for part in
+                    (1..=day.parts).filter(|&part| manifest.get(day.number, name, part).is_some())
+                {
+                    assert_eq!(
+                        (day.solve_part)(&crlf, part),
+                        (day.solve_part)(&clean, part),
+                        "Day {} {} part {}",
+                        day.number,
+                        name,
+                        part
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_inputs_dont_panic() {
+        // Days that need something to work with say so at the very start, the others find nothing
+        for day in DAYS.iter() {
+            if let Err(err) = (day.solve)("") {
+                assert_eq!((err.line, err.column), (1, 1), "Day {}", day.number);
+            }
+        }
+    }
+}
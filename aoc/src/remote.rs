@@ -0,0 +1,178 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::http::{Request, Response, Url};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2020;
+
+// Settings from the config file, `key = value` lines with `#` comments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("Expected 'key = value' on line {}!", idx + 1))?;
+            let value = Some(value.trim().to_owned());
+            match key.trim() {
+                "session" => config.session = value,
+                "base-url" => config.base_url = value,
+                key => return Err(format!("Unknown key '{}' on line {}!", key, idx + 1)),
+            }
+        }
+        Ok(config)
+    }
+
+    // AOC_CONFIG if it is set, otherwise ~/.config/aoc/config
+    pub fn path() -> Option<PathBuf> {
+        match env::var_os("AOC_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => env::var_os("HOME").map(|home| {
+                PathBuf::from(home)
+                    .join(".config")
+                    .join("aoc")
+                    .join("config")
+            }),
+        }
+    }
+
+    // A missing config file is the same as an empty one
+    fn load() -> Result<Config, String> {
+        let path = match Config::path() {
+            Some(path) if path.is_file() => path,
+            _ => return Ok(Config::default()),
+        };
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        Config::parse(&text).map_err(|err| format!("Invalid config {}: {}", path.display(), err))
+    }
+}
+
+// The puzzle server and the session to log in with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remote {
+    pub base_url: Url,
+    pub session: Option<String>,
+}
+
+impl Remote {
+    // Takes `--base-url <url>` out of the arguments and fills in the rest from the environment
+    // and the config file, in that order
+    pub fn extract(args: &[String]) -> Result<(Remote, Vec<String>), String> {
+        let mut base_url = None;
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--base-url" {
+                base_url = Some(
+                    args.next()
+                        .ok_or("Missing URL after --base-url!")?
+                        .to_owned(),
+                );
+            } else {
+                rest.push(arg.to_owned());
+            }
+        }
+        let remote = Remote::resolve(base_url, |name| env::var(name).ok(), Config::load()?)?;
+        Ok((remote, rest))
+    }
+
+    fn resolve(
+        base_url: Option<String>,
+        var: impl Fn(&str) -> Option<String>,
+        config: Config,
+    ) -> Result<Remote, String> {
+        let base_url = base_url
+            .or_else(|| var("AOC_BASE_URL"))
+            .or(config.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned());
+        let session = var("AOC_SESSION")
+            .or(config.session)
+            .filter(|session| !session.is_empty());
+        Ok(Remote {
+            base_url: Url::parse(&base_url)?,
+            session,
+        })
+    }
+
+    // Only asked for when something is sent, so cached inputs work without logging in
+    fn session(&self) -> Result<&str, String> {
+        self.session.as_deref().ok_or_else(|| {
+            let config = Config::path().map_or_else(
+                || "the config file".to_owned(),
+                |path| path.display().to_string(),
+            );
+            format!(
+                "No session token, set AOC_SESSION or 'session' in {}!",
+                config
+            )
+        })
+    }
+
+    // Sends a request for a page of this year's puzzles with the session cookie
+    pub fn send(&self, path: &str, form: Option<&[(&str, &str)]>) -> Result<Response, String> {
+        let url = self.base_url.join(&format!("{}/{}", YEAR, path));
+        let request = match form {
+            Some(form) => Request::post(url, form),
+            None => Request::get(url),
+        };
+        request
+            .header("Cookie", &format!("session={}", self.session()?))
+            .send()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_configs() {
+        let config = Config::parse(
+            "# Logged in with the browser\nsession = 53616c7465\n\nbase-url = http://localhost:8000 # local\n",
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("53616c7465"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost:8000"));
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(Config::parse("session").is_err());
+        assert!(Config::parse("token = abc").is_err());
+    }
+
+    #[test]
+    fn resolve_settings() {
+        let config = Config {
+            session: Some("from-config".to_owned()),
+            base_url: Some("http://config:1".to_owned()),
+        };
+        let env = |name: &str| match name {
+            "AOC_BASE_URL" => Some("http://env:2".to_owned()),
+            _ => None,
+        };
+
+        let remote = Remote::resolve(None, env, config.clone()).unwrap();
+        assert_eq!(remote.base_url, Url::parse("http://env:2").unwrap());
+        assert_eq!(remote.session.as_deref(), Some("from-config"));
+        let remote = Remote::resolve(Some("http://flag:3".to_owned()), env, config).unwrap();
+        assert_eq!(remote.base_url, Url::parse("http://flag:3").unwrap());
+
+        let remote = Remote::resolve(None, |_| None, Config::default()).unwrap();
+        assert_eq!(remote.base_url, Url::parse(DEFAULT_BASE_URL).unwrap());
+        assert!(remote.session().is_err());
+        assert!(
+            Remote::resolve(Some("localhost".to_owned()), |_| None, Config::default()).is_err()
+        );
+    }
+}
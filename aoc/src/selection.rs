@@ -0,0 +1,107 @@
+use std::num::ParseIntError;
+
+pub const FIRST_DAY: u32 = 1;
+pub const LAST_DAY: u32 = 25;
+
+// Parses day selections such as "7", "all", "1..=25", "3..7" or "1,3,10..=12"
+pub fn parse_days(selection: &str) -> Result<Vec<u32>, String> {
+    let mut days: Vec<u32> = Vec::new();
+    for part in selection.trim().split(',') {
+        let part = part.trim();
+        if part == "all" {
+            days.extend(FIRST_DAY..=LAST_DAY);
+        } else if let Some((start, end)) = part.split_once("..=") {
+            let range = parse_day(start)?..=parse_day(end)?;
+            if range.is_empty() {
+                return Err(empty_range(part));
+            }
+            days.extend(range);
+        } else if let Some((start, end)) = part.split_once("..") {
+            let range = parse_day(start)?..parse_end(end)?;
+            if range.is_empty() {
+                return Err(empty_range(part));
+            }
+            days.extend(range);
+        } else {
+            days.push(parse_day(part)?);
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    if days.is_empty() {
+        return Err(format!(
+            "Selection '{}' doesn't contain any days!",
+            selection
+        ));
+    }
+    Ok(days)
+}
+
+// Ranges that end before they start would quietly drop out of a list of days otherwise
+fn empty_range(range: &str) -> String {
+    format!("Range '{}' doesn't contain any days!", range)
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    let number = parse_number(day)?;
+    if !(FIRST_DAY..=LAST_DAY).contains(&number) {
+        return Err(format!(
+            "Day {} is out of range {}..={}!",
+            number, FIRST_DAY, LAST_DAY
+        ));
+    }
+    Ok(number)
+}
+
+// The end of an exclusive range may be the day after the last one, as in "1..26"
+fn parse_end(day: &str) -> Result<u32, String> {
+    match parse_number(day)? {
+        number if number == LAST_DAY + 1 => Ok(number),
+        _ => parse_day(day),
+    }
+}
+
+fn parse_number(day: &str) -> Result<u32, String> {
+    day.trim()
+        .parse()
+        .map_err(|err: ParseIntError| format!("Invalid day '{}': {}", day, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_day() {
+        assert_eq!(parse_days("7"), Ok(vec![7]));
+    }
+
+    #[test]
+    fn all_days() {
+        assert_eq!(parse_days("all"), Ok((1..=25).collect()));
+    }
+
+    #[test]
+    fn ranges_and_lists() {
+        assert_eq!(parse_days("1..=3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_days("1..3"), Ok(vec![1, 2]));
+        assert_eq!(parse_days("1..26"), Ok((1..=25).collect()));
+        assert_eq!(parse_days("24..26,1"), Ok(vec![1, 24, 25]));
+        assert_eq!(parse_days("10..=12,3,1,3"), Ok(vec![1, 3, 10, 11, 12]));
+    }
+
+    #[test]
+    fn invalid_selections() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("seven").is_err());
+        assert!(parse_days("5..5").is_err());
+        assert!(parse_days("1..=26").is_err());
+        assert!(parse_days("1..27").is_err());
+        assert!(parse_days("26..27").is_err());
+        assert!(parse_days("1,6..3").is_err());
+        assert!(parse_days("6..=3,1").is_err());
+        assert!(parse_days("1,5..5").is_err());
+    }
+}
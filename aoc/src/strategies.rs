@@ -0,0 +1,217 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_common::params::Params;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answers, StrategyInfo};
+
+use crate::registry::{self, Day};
+use crate::selection;
+use crate::table::Table;
+use crate::timeout;
+use crate::Selection;
+
+// Takes `--impl <name>` out of the arguments and returns the remaining ones untouched
+pub fn extract_impl(args: &[String]) -> Result<(Option<String>, Vec<String>), String> {
+    let mut name = None;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--impl" {
+            let value = args.next().ok_or("Missing name after --impl!")?;
+            name = Some(value.to_owned());
+        } else {
+            rest.push(arg.to_owned());
+        }
+    }
+    Ok((name, rest))
+}
+
+pub fn find(day: &Day, name: &str) -> Result<StrategyInfo, String> {
+    let strategies = (day.strategies)();
+    strategies
+        .iter()
+        .find(|info| info.name == name)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Day {} has no implementation '{}', it has: {}",
+                day.number,
+                name,
+                describe(&strategies)
+            )
+        })
+}
+
+// Names separated by commas, with the first one marked as the default
+fn describe(strategies: &[StrategyInfo]) -> String {
+    let names: Vec<String> = strategies
+        .iter()
+        .enumerate()
+        .map(|(idx, info)| match (idx, info.slow) {
+            (0, _) if strategies.len() == 1 => info.name.to_owned(),
+            (0, _) => format!("{} (default)", info.name),
+            (_, true) => format!("{} (slow)", info.name),
+            (_, false) => info.name.to_owned(),
+        })
+        .collect();
+    names.join(", ")
+}
+
+pub fn list(args: &[String]) -> Result<(), String> {
+    let numbers = match args {
+        [] => selection::parse_days("all")?,
+        [selection] => selection::parse_days(selection)?,
+        _ => return Err(crate::usage()),
+    };
+
+    let mut table = Table::new(&["Day", "Implementations"]);
+    for day in numbers.into_iter().filter_map(registry::find) {
+        table.add_row(vec![day.number.to_string(), describe(&(day.strategies)())]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+// Solves with a registered implementation, a panic comes back as its message. It ends up in
+// the table, so it isn't printed on stderr as well.
+fn solve_caught(
+    day: &Day,
+    input: &str,
+    params: &Params,
+    name: &str,
+) -> Result<Result<Answers, ParseError>, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (day.solve_with)(input, params, name).expect("Strategy is registered")
+    }));
+    panic::set_hook(hook);
+    result.map_err(|payload| timeout::panic_message(&*payload))
+}
+
+// Runs every alternative implementation and compares its answers to the default one
+pub fn check(args: &[String]) -> Result<(), String> {
+    let slow = args.iter().any(|arg| arg == "--slow");
+    let args: Vec<String> = args
+        .iter()
+        .filter(|arg| *arg != "--slow")
+        .cloned()
+        .collect();
+    let selection = Selection::from_args(&args)?;
+
+    let (mut disagreements, mut panics) = (0, 0);
+    let mut table = Table::new(&["Day", "Implementation", "Star 1", "Star 2", "Result"]);
+    for day in selection.days.iter() {
+        let strategies = (day.strategies)();
+        if strategies.len() < 2 {
+            continue;
+        }
+
+        let input = selection.read_input(day)?;
+        let parse_error = |err| selection.parse_error(day, &input, err);
+        let params = selection.params(day);
+        let expected = solve_caught(day, &input, &params, strategies[0].name);
+        let mut add_row = |name: &str, answers: Option<&Answers>, result: &str| {
+            let (star1, star2) = match answers {
+                Some((star1, star2)) => (star1.to_string(), star2.as_ref()),
+                None => ("-".to_owned(), None),
+            };
+            table.add_row(vec![
+                day.number.to_string(),
+                name.to_owned(),
+                star1,
+                star2.map_or_else(|| "-".to_owned(), |answer| answer.to_string()),
+                result.to_owned(),
+            ]);
+        };
+        // Without answers from the default there's nothing to compare the others with
+        let expected = match expected {
+            Ok(answers) => answers.map_err(parse_error)?,
+            Err(message) => {
+                panics += 1;
+                add_row(strategies[0].name, None, &format!("panicked: {}", message));
+                continue;
+            }
+        };
+        add_row(strategies[0].name, Some(&expected), "default");
+
+        for info in strategies.iter().skip(1) {
+            if info.slow && !slow {
+                add_row(info.name, None, "skipped, slow");
+                continue;
+            }
+            let answers = match solve_caught(day, &input, &params, info.name) {
+                Ok(answers) => answers.map_err(parse_error)?,
+                Err(message) => {
+                    panics += 1;
+                    add_row(info.name, None, &format!("panicked: {}", message));
+                    continue;
+                }
+            };
+            if answers == expected {
+                add_row(info.name, Some(&answers), "agrees");
+            } else {
+                disagreements += 1;
+                add_row(info.name, Some(&answers), "DISAGREES");
+            }
+        }
+    }
+
+    if table.is_empty() {
+        println!("None of the selected days have alternative implementations.");
+        return Ok(());
+    }
+    print!("{}", table);
+
+    let mut problems = Vec::new();
+    if disagreements > 0 {
+        problems.push(format!(
+            "{} implementation(s) disagree with the default one!",
+            disagreements
+        ));
+    }
+    if panics > 0 {
+        problems.push(format!("{} implementation(s) panicked!", panics));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_impl_name() {
+        let (name, rest) = extract_impl(&args(&["15", "--impl", "hash"])).unwrap();
+        assert_eq!(name.as_deref(), Some("hash"));
+        assert_eq!(rest, args(&["15"]));
+        assert!(extract_impl(&args(&["15", "--impl"])).is_err());
+    }
+
+    #[test]
+    fn find_strategies() {
+        let day15 = registry::find(15).unwrap();
+        assert_eq!(find(day15, "hash").unwrap().name, "hash");
+        assert!(find(day15, "vector").is_ok());
+        let err = find(day15, "tree").unwrap_err();
+        assert!(err.ends_with("vector (default), hash"), "{}", err);
+    }
+
+    #[test]
+    fn catch_panics() {
+        let day13 = registry::find(13).unwrap();
+        let params = Params::defaults(day13.params);
+        let result = solve_caught(day13, "10\n4,6\n", &params, "crt");
+        assert_eq!(result.unwrap_err(), "The buses never line up!");
+        let answers = solve_caught(day13, "10\n4,x,6\n", &params, "sieving").unwrap();
+        assert_eq!(answers.unwrap().1, Some(4.into()));
+    }
+}
@@ -0,0 +1,486 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::remote::Remote;
+use crate::strategies;
+use crate::Selection;
+
+const DEFAULT_HISTORY: &str = "target/submissions.txt";
+// How long to wait after a wrong answer or one sent too soon when the server doesn't say
+const DEFAULT_WAIT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    // The answer wasn't looked at because the cooldown wasn't over
+    TooSoon,
+    // The part was already solved, e.g. in the browser
+    Solved,
+}
+
+impl Verdict {
+    const ALL: [Verdict; 4] = [
+        Verdict::Right,
+        Verdict::Wrong,
+        Verdict::TooSoon,
+        Verdict::Solved,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too-soon",
+            Verdict::Solved => "solved",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        Verdict::ALL
+            .iter()
+            .copied()
+            .find(|verdict| verdict.name() == name)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// What the server made of an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub verdict: Verdict,
+    // Seconds until the next answer is looked at
+    pub wait: u64,
+    // The text of the reply, which also says if a wrong answer is too high or too low
+    pub message: String,
+}
+
+impl Reply {
+    pub fn parse(html: &str) -> Result<Reply, String> {
+        let message = article_text(html);
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Right
+        } else if message.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::Solved
+        } else {
+            return Err(format!(
+                "Could not make sense of the server's reply: {}",
+                message
+            ));
+        };
+        let wait = parse_wait(&message).unwrap_or(match verdict {
+            Verdict::Wrong | Verdict::TooSoon => DEFAULT_WAIT_SECS,
+            Verdict::Right | Verdict::Solved => 0,
+        });
+        Ok(Reply {
+            verdict,
+            wait,
+            message,
+        })
+    }
+}
+
+// The reply is a whole page, the part that matters is in its article
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "You have 4m 31s left to wait" after answering too soon,
+// "Please wait one minute before trying again" after a wrong answer
+fn parse_wait(message: &str) -> Option<u64> {
+    let message = message.to_lowercase();
+    if let Some((before, _)) = message.split_once(" left to wait") {
+        let (_, left) = before.rsplit_once("you have ")?;
+        return left.split_whitespace().try_fold(0, |total, part| {
+            let unit = part.chars().last()?;
+            let amount: u64 = part[..part.len() - unit.len_utf8()].parse().ok()?;
+            let unit = match unit {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            Some(total + amount * unit)
+        });
+    }
+    let (_, rest) = message.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let amount = match words.next()? {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+    let unit = match words.next()?.trim_end_matches('s') {
+        "minute" => 60,
+        "second" => 1,
+        _ => return None,
+    };
+    Some(amount * unit)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Attempt {
+    time: u64,
+    day: u32,
+    part: u32,
+    verdict: Verdict,
+    wait: u64,
+    answer: String,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.time, self.day, self.part, self.verdict, self.wait, self.answer
+        )
+    }
+}
+
+// Every answer sent so far, so wrong ones aren't sent again and the cooldown is kept even
+// between runs
+#[derive(Debug, Default, PartialEq, Eq)]
+struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    fn load(path: &Path) -> Result<History, String> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read history {}: {}", path.display(), err))?;
+        History::parse(&text)
+            .map_err(|line| format!("Invalid line in history {}: {}", path.display(), line))
+    }
+
+    // One line per attempt: "<unix time> <day> <part> <verdict> <wait> <answer>"
+    fn parse(text: &str) -> Result<History, &str> {
+        let mut history = History::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let parts: Vec<&str> = line.splitn(6, ' ').collect();
+            let (time, day, part, verdict, wait, answer) = match parts.as_slice() {
+                [time, day, part, verdict, wait, answer] => {
+                    (time, day, part, verdict, wait, answer)
+                }
+                _ => return Err(line),
+            };
+            history.attempts.push(Attempt {
+                time: time.parse().map_err(|_| line)?,
+                day: day.parse().map_err(|_| line)?,
+                part: part.parse().map_err(|_| line)?,
+                verdict: Verdict::from_name(verdict).ok_or(line)?,
+                wait: wait.parse().map_err(|_| line)?,
+                answer: answer.to_string(),
+            });
+        }
+        Ok(history)
+    }
+
+    fn append(path: &Path, attempt: &Attempt) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", attempt))
+            .map_err(|err| format!("Could not write history {}: {}", path.display(), err))
+    }
+
+    // Whether the answer may be sent now, going by earlier attempts
+    fn check(&self, day: u32, part: u32, answer: &str, now: u64) -> Result<(), String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+        for attempt in attempts {
+            match attempt.verdict {
+                Verdict::Right | Verdict::Solved => {
+                    return Err(format!("Day {} part {} is already solved!", day, part))
+                }
+                Verdict::Wrong if attempt.answer == answer => {
+                    return Err(format!(
+                        "{} was already rejected for day {} part {}!",
+                        answer, day, part
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        // The cooldown holds for all days
+        let ready = self
+            .attempts
+            .iter()
+            .map(|attempt| attempt.time + attempt.wait)
+            .max()
+            .unwrap_or(0);
+        if ready > now {
+            return Err(format!(
+                "The server asked to wait, try again in {}s!",
+                ready - now
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Sends an answer unless the history says it's pointless or too soon, and records the reply.
+// Calls `sending` right before the answer goes out.
+pub fn submit(
+    remote: &Remote,
+    history: &Path,
+    day: u32,
+    part: u32,
+    answer: &str,
+    now: u64,
+    sending: &mut dyn FnMut(),
+) -> Result<Reply, String> {
+    History::load(history)?.check(day, part, answer, now)?;
+    sending();
+
+    let level = part.to_string();
+    let form = [("level", level.as_str()), ("answer", answer)];
+    let response = remote.send(&format!("day/{}/answer", day), Some(&form))?;
+    if !response.is_success() {
+        return Err(format!(
+            "Could not submit day {}, the server answered {}!",
+            day, response.status
+        ));
+    }
+    let reply = Reply::parse(&response.body)?;
+
+    let attempt = Attempt {
+        time: now,
+        day,
+        part,
+        verdict: reply.verdict,
+        wait: reply.wait,
+        answer: answer.to_owned(),
+    };
+    History::append(history, &attempt)?;
+    Ok(reply)
+}
+
+// Takes `--history <path>` out of the arguments and returns the remaining ones untouched
+fn extract_history(args: &[String]) -> Result<(PathBuf, Vec<String>), String> {
+    let mut history = PathBuf::from(DEFAULT_HISTORY);
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--history" {
+            history = PathBuf::from(args.next().ok_or("Missing path after --history!")?);
+        } else {
+            rest.push(arg.to_owned());
+        }
+    }
+    Ok((history, rest))
+}
+
+// Solves a part of a day and sends the answer to the server
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (history, args) = extract_history(args)?;
+    let (remote, args) = Remote::extract(&args)?;
+    let (strategy, args) = strategies::extract_impl(&args)?;
+    let (day, part, args) = match args.as_slice() {
+        [day, part, rest @ ..] => (day, part, rest),
+        _ => return Err(crate::usage()),
+    };
+    let part = match part.as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => return Err(format!("Invalid part '{}', expected 1 or 2!", part)),
+    };
+    let mut selection_args = vec![day.to_owned()];
+    selection_args.extend_from_slice(args);
+    let selection = Selection::from_args(&selection_args)?;
+    let day = match selection.days.as_slice() {
+        [day] => *day,
+        _ => return Err("Only a single day can be submitted!".to_owned()),
+    };
+    if selection.options.simple {
+        return Err("Answers for the simple input can't be submitted!".to_owned());
+    }
+
+    let input = selection.read_input(day)?;
+    let params = selection.params(day);
+    let name = match strategy.as_deref() {
+        Some(name) => strategies::find(day, name)?.name,
+        None => (day.strategies)()[0].name,
+    };
+    let (star1, star2) = (day.solve_with)(&input, &params, name)
+        .expect("Strategy was checked before")
+        .map_err(|err| selection.parse_error(day, &input, err))?;
+    let answer = match part {
+        1 => star1,
+        _ => star2.ok_or(format!("Day {} has no part 2 to submit!", day.number))?,
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock is after 1970")
+        .as_secs();
+    let answer = answer.to_string();
+    let reply = submit(
+        &remote,
+        &history,
+        day.number,
+        part,
+        &answer,
+        now,
+        &mut || println!("Submitting {} for day {} part {}", answer, day.number, part),
+    )?;
+    println!("{}", reply.message);
+    match reply.verdict {
+        Verdict::Right | Verdict::Solved => Ok(()),
+        Verdict::Wrong | Verdict::TooSoon => Err(format!(
+            "{} was not accepted, the next answer can be sent in {}s!",
+            answer, reply.wait
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stand_in::StandIn;
+    use crate::http::Url;
+    use std::env;
+    use std::process;
+
+    const WRONG: &str = "<html><main>\n<article><p>That's not the right answer; your answer is \
+        too high.  If you're stuck, make sure you're using the full input data. Please wait one \
+        minute before trying again. (You guessed <span style=\"white-space:nowrap;\"><code>\
+        1234</code>.)</span> <a href=\"/2020/day/7\">[Return to Day 7]</a></p></article>\n\
+        </main></html>";
+
+    #[test]
+    fn parse_replies() {
+        let reply = Reply::parse(WRONG).unwrap();
+        assert_eq!((reply.verdict, reply.wait), (Verdict::Wrong, 60));
+        assert!(reply
+            .message
+            .starts_with("That's not the right answer; your answer is too high. If"));
+        assert!(reply.message.contains("(You guessed 1234.)"));
+
+        let reply = Reply::parse(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an \
+             answer before trying again.  You have 4m 31s left to wait.</p></article>",
+        )
+        .unwrap();
+        assert_eq!((reply.verdict, reply.wait), (Verdict::TooSoon, 271));
+        let reply = Reply::parse(
+            "<article><p>That's not the right answer. Please wait 5 minutes before trying \
+             again.</p></article>",
+        )
+        .unwrap();
+        assert_eq!((reply.verdict, reply.wait), (Verdict::Wrong, 300));
+        let reply = Reply::parse("<article><p>That's the right answer!</p></article>").unwrap();
+        assert_eq!((reply.verdict, reply.wait), (Verdict::Right, 0));
+        let reply = Reply::parse(
+            "<article><p>You don't seem to be solving the right level.  Did you already \
+             complete it?</p></article>",
+        )
+        .unwrap();
+        assert_eq!(reply.verdict, Verdict::Solved);
+        assert!(Reply::parse("<html>Maintenance</html>").is_err());
+    }
+
+    #[test]
+    fn non_ascii_waits() {
+        assert_eq!(parse_wait("You have 4m 31ś left to wait."), None);
+        assert_eq!(parse_wait("You have 2ч left to wait."), None);
+        assert_eq!(parse_wait("You have 1h 2m 3s left to wait."), Some(3723));
+        let reply = Reply::parse(
+            "<article><p>You gave an answer too recently. You have 5 minutë left to \
+             wait.</p></article>",
+        )
+        .unwrap();
+        assert_eq!(
+            (reply.verdict, reply.wait),
+            (Verdict::TooSoon, DEFAULT_WAIT_SECS)
+        );
+    }
+
+    #[test]
+    fn check_history() {
+        let text = "1000 7 1 wrong 60 1234\n1100 7 1 right 0 4\n1200 21 2 wrong 300 a,b c\n";
+        let history = History::parse(text).unwrap();
+        assert_eq!(history.attempts[2].answer, "a,b c");
+        let round_trip: String = history
+            .attempts
+            .iter()
+            .map(|attempt| format!("{}\n", attempt))
+            .collect();
+        assert_eq!(round_trip, text);
+        assert!(History::parse("1000 7 1 maybe 60 1234").is_err());
+
+        assert!(history.check(7, 1, "5", 2000).is_err());
+        assert!(history.check(7, 2, "5", 1300).is_err());
+        assert!(history.check(7, 2, "5", 1500).is_ok());
+        assert!(history.check(21, 2, "a,b c", 2000).is_err());
+        assert!(history.check(21, 2, "a,b", 2000).is_ok());
+    }
+
+    #[test]
+    fn submit_to_server() {
+        let server = StandIn::start("200 OK", &[], WRONG);
+        let remote = Remote {
+            base_url: Url::parse(&server.url).unwrap(),
+            session: Some("abc".to_owned()),
+        };
+        let history = env::temp_dir().join(format!("aoc-submissions-{}.txt", process::id()));
+        let _ = fs::remove_file(&history);
+
+        let mut sent = 0;
+        let reply = submit(&remote, &history, 7, 1, "1234", 1000, &mut || sent += 1).unwrap();
+        assert_eq!(reply.verdict, Verdict::Wrong);
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2020/day/7/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=1234"));
+
+        // Nothing is sent during the cooldown or for an answer that was already wrong
+        assert!(submit(&remote, &history, 7, 1, "1000", 1030, &mut || sent += 1).is_err());
+        assert!(submit(&remote, &history, 7, 1, "1234", 2000, &mut || sent += 1).is_err());
+        assert_eq!((server.requests().len(), sent), (1, 1));
+        assert!(submit(&remote, &history, 7, 1, "1000", 2000, &mut || sent += 1).is_ok());
+        assert_eq!((server.requests().len(), sent), (2, 2));
+        assert_eq!(
+            fs::read_to_string(&history).unwrap(),
+            "1000 7 1 wrong 60 1234\n2000 7 1 wrong 60 1000\n"
+        );
+        fs::remove_file(history).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+// Plain text table with columns padded to the widest cell
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        assert_eq!(row.len(), self.headers.len());
+        self.rows.push(row);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .map(|col| {
+                self.rows
+                    .iter()
+                    .map(|row| row[col].chars().count())
+                    .chain(std::iter::once(self.headers[col].chars().count()))
+                    .max()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn write_row(f: &mut fmt::Formatter<'_>, row: &[String], widths: &[usize]) -> fmt::Result {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        writeln!(f, "{}", cells.join(" | ").trim_end())
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.column_widths();
+        Table::write_row(f, &self.headers, &widths)?;
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        writeln!(f, "{}", separator.join("-+-"))?;
+        for row in self.rows.iter() {
+            Table::write_row(f, row, &widths)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_columns() {
+        let mut table = Table::new(&["Day", "Star 1"]);
+        table.add_row(vec!["1".to_owned(), "988771".to_owned()]);
+        table.add_row(vec!["21".to_owned(), "x".to_owned()]);
+        assert_eq!(
+            table.to_string(),
+            "Day | Star 1\n----+-------\n1   | 988771\n21  | x\n"
+        );
+    }
+}
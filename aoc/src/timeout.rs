@@ -0,0 +1,205 @@
+use std::any::Any;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel;
+use aoc_common::params::Params;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::Answer;
+
+use crate::bench::Phase;
+use crate::registry::Day;
+
+// Takes `--timeout <seconds>` out of the arguments and returns the remaining ones untouched
+pub fn extract_timeout(args: &[String]) -> Result<(Option<Duration>, Vec<String>), String> {
+    let mut timeout = None;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--timeout" {
+            let seconds = args.next().ok_or("Missing seconds after --timeout!")?;
+            let budget = seconds
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+                .ok_or(format!("Invalid timeout '{}'!", seconds))?;
+            timeout = Some(Duration::from_secs_f64(budget));
+        } else {
+            rest.push(arg.to_owned());
+        }
+    }
+    Ok((timeout, rest))
+}
+
+// How far solving a day got. The laps are taken after parsing and after each part, like
+// `solve_timed` takes them, so a day that ran out of time has fewer of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub star1: Option<Answer>,
+    pub star2: Option<Answer>,
+    pub laps: Vec<Duration>,
+    // The phase that was still running when the time was up
+    pub timed_out: Option<Phase>,
+    // The phase that panicked, with the panic message
+    pub panicked: Option<(Phase, String)>,
+}
+
+impl Outcome {
+    fn new(answers: Vec<Option<Answer>>, laps: Vec<Duration>) -> Outcome {
+        let mut answers = answers.into_iter().skip(1);
+        Outcome {
+            star1: answers.next().flatten(),
+            star2: answers.next().flatten(),
+            timed_out: Phase::ALL.get(laps.len()).copied(),
+            panicked: None,
+            laps,
+        }
+    }
+
+    // Whether the given part was still running or never got to start
+    pub fn ran_out(&self, part: Phase) -> bool {
+        self.timed_out.is_some_and(|phase| phase <= part)
+    }
+}
+
+// The message a solver panicked with, only its first line as some span several
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    message.lines().next().unwrap_or("").to_owned()
+}
+
+enum Event {
+    Lap(Duration, Option<Answer>),
+    Failed(ParseError),
+}
+
+// Solves a day on a thread of its own with the named implementation. Without a budget this
+// waits for the day to finish. With one, the solver is cancelled at its next checkpoint once
+// the time is up. Solvers that never reach one are left running in the background, their
+// result is thrown away. Solvers that panic end up in the outcome like those that time out.
+pub fn solve(
+    day: &'static Day,
+    input: &str,
+    params: &Params,
+    name: &'static str,
+    budget: Option<Duration>,
+) -> Result<Outcome, ParseError> {
+    let start = Instant::now();
+    let deadline = budget.map(|budget| start + budget);
+    let (sender, receiver) = mpsc::channel();
+    let (input, params) = (input.to_owned(), params.clone());
+    let worker = thread::spawn(move || {
+        let solve = || {
+            let result = (day.solve_timed)(&input, &params, name, &mut |answer| {
+                let _ = sender.send(Event::Lap(start.elapsed(), answer.cloned()));
+            });
+            if let Err(err) = result.expect("Strategy was checked before") {
+                let _ = sender.send(Event::Failed(err));
+            }
+        };
+        match deadline {
+            // Running out of time is noticed by the receiver
+            Some(deadline) => cancel::with_deadline(deadline, solve).unwrap_or(()),
+            None => solve(),
+        }
+    });
+
+    let mut answers = Vec::new();
+    let mut laps = Vec::new();
+    loop {
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match event {
+            Ok(Event::Lap(lap, answer)) => {
+                laps.push(lap);
+                answers.push(answer);
+            }
+            Ok(Event::Failed(err)) => return Err(err),
+            Err(RecvTimeoutError::Disconnected) => {
+                if let Err(payload) = worker.join() {
+                    let mut outcome = Outcome::new(answers, laps);
+                    let phase = outcome
+                        .timed_out
+                        .take()
+                        .expect("Panicked before the last lap");
+                    outcome.panicked = Some((phase, panic_message(&*payload)));
+                    return Ok(outcome);
+                }
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+        }
+    }
+    Ok(Outcome::new(answers, laps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_common::input::{default_path, InputSource};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_timeouts() {
+        let (timeout, rest) = extract_timeout(&args(&["all", "--timeout", "1.5"])).unwrap();
+        assert_eq!(timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(rest, args(&["all"]));
+        assert_eq!(extract_timeout(&args(&["all"])).unwrap().0, None);
+        assert!(extract_timeout(&args(&["all", "--timeout"])).is_err());
+        assert!(extract_timeout(&args(&["all", "--timeout", "0"])).is_err());
+        assert!(extract_timeout(&args(&["all", "--timeout", "soon"])).is_err());
+    }
+
+    #[test]
+    fn solve_within_the_budget() {
+        let day = registry::find(7).unwrap();
+        let input = InputSource::File(default_path(7, true)).read().unwrap();
+        let params = Params::defaults(day.params);
+        let budget = Some(Duration::from_secs(60));
+        let outcome = solve(day, &input, &params, "default", budget).unwrap();
+        assert_eq!(outcome.star1, Some(Answer::Number(4)));
+        assert_eq!(outcome.star2, Some(Answer::Number(32)));
+        assert_eq!((outcome.laps.len(), outcome.timed_out), (3, None));
+        assert!(solve(day, "nonsense", &params, "default", budget).is_err());
+    }
+
+    #[test]
+    fn cancel_slow_parts() {
+        // The brute force takes far longer than the budget on the full input
+        let day = registry::find(13).unwrap();
+        let input = InputSource::File(default_path(13, false)).read().unwrap();
+        let params = Params::defaults(day.params);
+        let budget = Some(Duration::from_millis(50));
+        let outcome = solve(day, &input, &params, "brute-force", budget).unwrap();
+        assert!(outcome.star1.is_some());
+        assert_eq!(outcome.star2, None);
+        assert_eq!(outcome.timed_out, Some(Phase::Part2));
+        assert!(outcome.ran_out(Phase::Part2) && !outcome.ran_out(Phase::Part1));
+    }
+
+    #[test]
+    fn report_panics() {
+        // Day 25 panics on keys that can't be reached with the subject
+        let day = registry::find(25).unwrap();
+        let overrides = [("subject".to_owned(), "6".to_owned())];
+        let params = Params::with_overrides(day.params, &overrides).unwrap();
+        let outcome = solve(day, "5764801\n17807724\n", &params, "default", None).unwrap();
+        let (phase, message) = outcome.panicked.as_ref().unwrap();
+        assert_eq!(*phase, Phase::Part1);
+        assert!(message.starts_with("Key 5764801 can't be reached with subject 6"));
+        assert_eq!((outcome.star1.as_ref(), outcome.timed_out), (None, None));
+    }
+}
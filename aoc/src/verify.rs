@@ -0,0 +1,163 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_common::answers::Manifest;
+use aoc_common::input::{named_path, InputSource};
+
+use crate::registry::{self, Day};
+use crate::selection;
+use crate::table::Table;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Missing, // No expected answer, so the part isn't run at all
+}
+
+// The usual inputs first, followed by the extra ones the manifest has answers for
+fn input_names(manifest: &Manifest, day: u32) -> Vec<&str> {
+    let mut names = vec!["full", "simple"];
+    for name in manifest.inputs(day) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+// Solves a single part, turning parse errors and panics into the text shown instead of an answer
+fn solve(day: &Day, input: &str, part: u32) -> String {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (day.solve_part)(input, part)));
+    match result {
+        Ok(Ok(Some(answer))) => answer.to_string(),
+        Ok(Ok(None)) => "no answer".to_owned(),
+        Ok(Err(err)) => format!("parse error at line {}", err.line),
+        Err(_) => "panicked".to_owned(),
+    }
+}
+
+fn verify_input(
+    day: &Day,
+    name: &str,
+    expected: &[(u32, Option<&str>)],
+) -> Vec<(u32, String, Verdict)> {
+    let path = named_path(day.number, name).expect("Input names are checked by the manifest");
+    // Days without a sample input are fine, as long as no answers are expected for it
+    let input = if path.exists() {
+        InputSource::File(path).read()
+    } else {
+        Err(format!("No input {}", path.display()))
+    };
+
+    expected
+        .iter()
+        .map(|(part, expected)| match (expected, &input) {
+            (None, _) => (*part, "-".to_owned(), Verdict::Missing),
+            (Some(_), Err(_)) => (*part, "no input".to_owned(), Verdict::Fail),
+            (Some(expected), Ok(input)) => {
+                let actual = solve(day, input, *part);
+                let verdict = if actual == *expected {
+                    Verdict::Pass
+                } else {
+                    Verdict::Fail
+                };
+                (*part, actual, verdict)
+            }
+        })
+        .collect()
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let numbers = match args {
+        [] => selection::parse_days("all")?,
+        [selection] => selection::parse_days(selection)?,
+        _ => return Err(crate::usage()),
+    };
+    let manifest = Manifest::load()?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut table = Table::new(&["Day", "Input", "Part", "Expected", "Actual", "Result"]);
+    for day in numbers.into_iter().filter_map(registry::find) {
+        for name in input_names(&manifest, day.number) {
+            let expected: Vec<(u32, Option<&str>)> = (1..=day.parts)
+                .map(|part| (part, manifest.get(day.number, name, part)))
+                .collect();
+            let has_input = named_path(day.number, name).is_some_and(|path| path.exists());
+            if !has_input && expected.iter().all(|(_, expected)| expected.is_none()) {
+                continue;
+            }
+
+            for (part, actual, verdict) in verify_input(day, name, &expected) {
+                let result = match verdict {
+                    Verdict::Pass => {
+                        passed += 1;
+                        "pass"
+                    }
+                    Verdict::Fail => {
+                        failed += 1;
+                        "FAIL"
+                    }
+                    Verdict::Missing => {
+                        missing += 1;
+                        "missing"
+                    }
+                };
+                let expected = manifest.get(day.number, name, part).unwrap_or("-");
+                table.add_row(vec![
+                    day.number.to_string(),
+                    name.to_owned(),
+                    part.to_string(),
+                    expected.to_owned(),
+                    actual,
+                    result.to_owned(),
+                ]);
+            }
+        }
+    }
+    print!("{}", table);
+    println!(
+        "{} passed, {} failed, {} missing an expected answer",
+        passed, failed, missing
+    );
+
+    if failed > 0 {
+        return Err(format!(
+            "{} answer(s) don't match {}!",
+            failed,
+            Manifest::path().display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_of_a_day() {
+        let manifest = Manifest::parse("10 simple-small 1 35\n10 simple 1 220").unwrap();
+        assert_eq!(
+            input_names(&manifest, 10),
+            vec!["full", "simple", "simple-small"]
+        );
+        assert_eq!(input_names(&manifest, 1), vec!["full", "simple"]);
+    }
+
+    #[test]
+    fn verify_parts() {
+        let day = registry::find(7).unwrap();
+        let results = verify_input(day, "simple", &[(1, Some("4")), (2, None)]);
+        assert_eq!(
+            results,
+            vec![
+                (1, "4".to_owned(), Verdict::Pass),
+                (2, "-".to_owned(), Verdict::Missing)
+            ]
+        );
+        let results = verify_input(day, "simple", &[(2, Some("33"))]);
+        assert_eq!(results, vec![(2, "32".to_owned(), Verdict::Fail)]);
+        let results = verify_input(day, "simple-missing", &[(1, Some("4"))]);
+        assert_eq!(results, vec![(1, "no input".to_owned(), Verdict::Fail)]);
+    }
+}
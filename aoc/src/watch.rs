@@ -0,0 +1,272 @@
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_common::input::{default_path, InputOptions, InputSource};
+use aoc_common::solution::Answer;
+
+use crate::batch::{self, FileRun};
+use crate::bench;
+use crate::registry::Day;
+use crate::strategies;
+use crate::table::Table;
+use crate::Selection;
+
+const DEFAULT_INTERVAL_MS: u64 = 500;
+
+// When a file was last written and how large it was, None while it doesn't exist
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// A watched input, with how it looked and what it came to the last time it was solved
+struct Watched {
+    path: PathBuf,
+    seen: Option<Stamp>,
+    last: Option<FileRun>,
+}
+
+struct Watcher {
+    files: Vec<Watched>,
+}
+
+impl Watcher {
+    fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|path| Watched {
+                    path,
+                    seen: None,
+                    last: None,
+                })
+                .collect(),
+        }
+    }
+
+    // Indices of the files that changed since the last call, all of them the first time
+    fn changed(&mut self) -> Vec<usize> {
+        let mut changed = Vec::new();
+        for (idx, file) in self.files.iter_mut().enumerate() {
+            let current = stamp(&file.path);
+            if file.seen != Some(current) {
+                file.seen = Some(current);
+                changed.push(idx);
+            }
+        }
+        changed
+    }
+}
+
+// Answers and phase times of a run next to those of the run before it
+fn report(previous: Option<&FileRun>, current: &FileRun) -> Table {
+    let mut table = Table::new(&["", "Now", "Before", "Change"]);
+    let answer = |run: Option<&FileRun>, star: u32| {
+        run.and_then(|run| if star == 1 { &run.star1 } else { &run.star2 }.as_ref())
+            .map_or("-".to_owned(), Answer::to_string)
+    };
+    for star in 1..=2 {
+        let (now, before) = (answer(Some(current), star), answer(previous, star));
+        let change = match previous {
+            None => "-",
+            Some(_) if now == before => "same",
+            Some(_) => "changed",
+        };
+        table.add_row(vec![
+            format!("Star {}", star),
+            now,
+            before,
+            change.to_owned(),
+        ]);
+    }
+
+    let times = current.phase_times();
+    let previous_times = previous.map(FileRun::phase_times).unwrap_or_default();
+    for (idx, phase) in ["Parse", "Part 1", "Part 2"].iter().enumerate() {
+        let (now, before) = (times.get(idx), previous_times.get(idx));
+        let format =
+            |time: Option<&Duration>| time.map_or("-".to_owned(), |t| format!("{:.2?}", t));
+        let change = match (now, before) {
+            (Some(now), Some(before)) => bench::compare(*now, *before),
+            _ => "-".to_owned(),
+        };
+        table.add_row(vec![phase.to_string(), format(now), format(before), change]);
+    }
+    table
+}
+
+// The inputs given on the command line, or both the full and the sample input of the day
+fn input_paths(day: &Day, options: &InputOptions) -> Result<Vec<PathBuf>, String> {
+    match options.source(day.number) {
+        InputSource::Stdin => Err("Only files can be watched, not stdin!".to_owned()),
+        InputSource::File(path) if options.input.is_some() || options.simple => Ok(vec![path]),
+        InputSource::File(path) => Ok(vec![path, default_path(day.number, true)]),
+    }
+}
+
+struct WatchOptions {
+    interval: Duration,
+}
+
+impl WatchOptions {
+    // Takes the watch flags out of the arguments and returns the remaining ones untouched
+    fn extract(args: &[String]) -> Result<(WatchOptions, Vec<String>), String> {
+        let mut options = WatchOptions {
+            interval: Duration::from_millis(DEFAULT_INTERVAL_MS),
+        };
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--interval" {
+                let interval = args
+                    .next()
+                    .ok_or("Missing milliseconds after --interval!")?;
+                let interval = interval
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or(format!("Invalid interval '{}'!", interval))?;
+                options.interval = Duration::from_millis(interval);
+            } else {
+                rest.push(arg.to_owned());
+            }
+        }
+        Ok((options, rest))
+    }
+}
+
+// Solves a day again every time one of its inputs changes, until it's stopped with Ctrl-C.
+// Inputs are read from disk on every run, only changes to the sources need a rebuild.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (options, args) = WatchOptions::extract(args)?;
+    let (strategy, args) = strategies::extract_impl(&args)?;
+    let selection = Selection::from_args(&args)?;
+    let day = match selection.days.as_slice() {
+        [day] => *day,
+        _ => return Err("Only a single day can be watched!".to_owned()),
+    };
+    let params = selection.params(day);
+    let name = match strategy.as_deref() {
+        Some(name) => strategies::find(day, name)?.name,
+        None => (day.strategies)()[0].name,
+    };
+    let paths = input_paths(day, &selection.options)?;
+    let names: Vec<String> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    println!("Watching {}, stop with Ctrl-C", names.join(" and "));
+
+    // Failures are reported with the run, the panic messages would only get in the way
+    panic::set_hook(Box::new(|_| {}));
+    let mut watcher = Watcher::new(paths);
+    loop {
+        for idx in watcher.changed() {
+            let file = &mut watcher.files[idx];
+            let run = batch::solve_file(day, &params, name, &file.path);
+            println!();
+            println!("{}", file.path.display());
+            print!("{}", report(file.last.as_ref(), &run));
+            if let Some(failure) = &run.failure {
+                println!("Failed: {}", failure);
+            }
+            file.last = Some(run);
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn extract_options() {
+        let (options, rest) = WatchOptions::extract(&args(&["7", "--interval", "50"])).unwrap();
+        assert_eq!(options.interval, Duration::from_millis(50));
+        assert_eq!(rest, args(&["7"]));
+        assert!(WatchOptions::extract(&args(&["7", "--interval", "0"])).is_err());
+        assert!(WatchOptions::extract(&args(&["7", "--interval"])).is_err());
+    }
+
+    #[test]
+    fn watched_inputs() {
+        let day = crate::registry::find(7).unwrap();
+        let mut options = InputOptions::default();
+        assert_eq!(
+            input_paths(day, &options),
+            Ok(vec![default_path(7, false), default_path(7, true)])
+        );
+        options.simple = true;
+        assert_eq!(input_paths(day, &options), Ok(vec![default_path(7, true)]));
+        options = InputOptions {
+            input: Some("-".to_owned()),
+            simple: false,
+        };
+        assert!(input_paths(day, &options).is_err());
+    }
+
+    #[test]
+    fn notice_changes() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        // Missing files are solved once too, so their failure shows up
+        assert_eq!(watcher.changed(), vec![0]);
+        assert_eq!(watcher.changed(), Vec::<usize>::new());
+        fs::write(&path, "1\n").unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+        assert_eq!(watcher.changed(), Vec::<usize>::new());
+        fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+    }
+
+    // The cells of the row that starts with `label`
+    fn row(table: &Table, label: &str) -> Vec<String> {
+        let text = table.to_string();
+        let line = text.lines().find(|line| line.starts_with(label)).unwrap();
+        line.split('|').map(|cell| cell.trim().to_owned()).collect()
+    }
+
+    #[test]
+    fn compare_runs() {
+        let day = crate::registry::find(2).unwrap();
+        let params = aoc_common::params::Params::defaults(day.params);
+        let first = batch::solve_file(day, &params, "default", &default_path(2, true));
+        assert_eq!(
+            row(&report(None, &first), "Star 1"),
+            ["Star 1", "2", "-", "-"]
+        );
+
+        let mut second = first.clone();
+        second.star2 = Some(Answer::Number(7));
+        let table = report(Some(&first), &second);
+        assert_eq!(row(&table, "Star 1"), ["Star 1", "2", "2", "same"]);
+        assert_eq!(row(&table, "Star 2"), ["Star 2", "7", "1", "changed"]);
+        assert_eq!(row(&table, "Parse")[3], "+0.0%");
+
+        let broken = FileRun {
+            star1: None,
+            star2: None,
+            laps: Vec::new(),
+            failure: Some("parse error".to_owned()),
+            ..first.clone()
+        };
+        let table = report(Some(&first), &broken);
+        assert_eq!(row(&table, "Star 1")[1..], ["-", "2", "changed"]);
+        assert_eq!(row(&table, "Parse")[3], "-");
+    }
+}
@@ -0,0 +1,184 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// The sum the expense report entries have to add up to.
+pub const TARGET: i32 = 2020;
+
+/// Product of the two entries that sum to `target`, the numbers have to be sorted like
+/// [`parse_input`] returns them.
+pub fn star1(numbers: &[i32], target: i32) -> i64 {
+    let (low, high) = find_two_numbers(numbers, target).expect("No solution found!");
+    i64::from(*low) * i64::from(*high)
+}
+
+/// Product of the three entries that sum to `target`, on sorted numbers as well.
+pub fn star2(numbers: &[i32], target: i32) -> i64 {
+    let (first, second, third) = find_three_numbers(numbers, target).expect("No solution found!");
+    i64::from(*first) * i64::from(*second) * i64::from(*third)
+}
+
+/// Parses the expense report, one number per line, and sorts it.
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(input, &input[..0], "a number"));
+    }
+    let mut numbers = input
+        .trim()
+        .lines()
+        .map(|x| parse_at(input, x.trim(), "a number"))
+        .collect::<Result<Vec<i32>, _>>()?;
+    numbers.sort_unstable();
+    Ok(numbers)
+}
+
+// Assumes sorted numbers vector. Both numbers are different entries, even if they are equal.
+fn find_two_numbers(numbers: &[i32], wanted_sum: i32) -> Option<(&i32, &i32)> {
+    if numbers.is_empty() {
+        return None;
+    }
+    let mut low = 0;
+    let mut high = numbers.len() - 1;
+    while low < high {
+        let sum = numbers[low] + numbers[high];
+        if sum == wanted_sum {
+            return Some((&numbers[low], &numbers[high]));
+        } else if sum < wanted_sum {
+            low += 1;
+        } else {
+            high -= 1;
+        }
+    }
+    None
+}
+
+fn find_three_numbers(numbers: &[i32], wanted_sum: i32) -> Option<(&i32, &i32, &i32)> {
+    for (idx, third) in numbers.iter().enumerate() {
+        if let Some((first, second)) = find_two_numbers(&numbers[idx + 1..], wanted_sum - third) {
+            return Some((first, second, third));
+        }
+    }
+    None
+}
+
+/// Report Repair, finding the expense report entries that sum to [`TARGET`].
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    // Numbers with the sum to look for
+    type Input<'a> = (Vec<i32>, i32);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day01::parse_with(input, &Params::defaults(Day01::PARAMS))
+    }
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "target",
+        default: "2020",
+        description: "Sum the numbers have to add up to",
+        check: |value| params::between(value, 0, 1_000_000),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        Ok((parse_input(input)?, params.get("target")))
+    }
+
+    fn part1((numbers, target): &Self::Input<'_>) -> Answer {
+        star1(numbers, *target).into()
+    }
+
+    fn part2((numbers, target): &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(numbers, *target).into())
+    }
+}
+
+impl Generator for Day01 {
+    const SIZE: &'static str = "numbers";
+    const DEFAULT_SIZE: usize = 200;
+    const MIN_SIZE: usize = 5;
+
+    // Plants one pair and one triple adding up to TARGET. The other numbers are all so big that
+    // adding them to anything goes past TARGET.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let planted = loop {
+            let pair = rng.range(200, 1000) as i32;
+            let (first, second) = (rng.range(200, 800) as i32, rng.range(200, 800) as i32);
+            let planted = [pair, TARGET - pair, first, second, TARGET - first - second];
+            if is_unique(&planted) {
+                break planted;
+            }
+        };
+        let smallest = *planted.iter().min().unwrap();
+
+        let mut numbers = planted.to_vec();
+        while numbers.len() < size {
+            numbers.push(rng.range((TARGET + 1 - smallest) as u64, TARGET as u64) as i32);
+        }
+        rng.shuffle(&mut numbers);
+        Generated::new(generate::lines(numbers))
+            .with_part1(planted[0] * planted[1])
+            .with_part2(planted[2] * planted[3] * planted[4])
+    }
+}
+
+// Only the planted pair and triple add up to TARGET, and no number is used twice
+fn is_unique(planted: &[i32; 5]) -> bool {
+    let mut pairs = 0;
+    let mut triples = 0;
+    for first in 0..planted.len() {
+        for second in first + 1..planted.len() {
+            if planted[first] == planted[second] {
+                return false;
+            }
+            pairs += (planted[first] + planted[second] == TARGET) as u32;
+            for third in second + 1..planted.len() {
+                triples += (planted[first] + planted[second] + planted[third] == TARGET) as u32;
+            }
+        }
+    }
+    pairs == 1 && triples == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+
+    const INPUT: &str = include_str!("./../../inputs/day-01.txt");
+
+    #[test]
+    fn full_star1() {
+        let result = star1(&parse_input(INPUT).unwrap(), TARGET);
+        assert_eq!(result, answers::expected(1, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let result = star2(&parse_input(INPUT).unwrap(), TARGET);
+        assert_eq!(result, answers::expected(1, "full", 2));
+    }
+
+    #[test]
+    fn other_targets() {
+        let numbers = parse_input("5\n3\n3\n9\n").unwrap();
+        assert_eq!(star1(&numbers, 6), 9);
+        assert_eq!(star1(&numbers, 14), 45);
+        assert_eq!(star2(&numbers, 11), 45);
+        assert_eq!(find_two_numbers(&numbers, 10), None);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day01>(20, &[5, 50, 1000]);
+    }
+
+    #[test]
+    fn invalid_number() {
+        let err = parse_input("1721\n979\n36x6\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "36x6"));
+    }
+}
@@ -0,0 +1,129 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use parse_display::{Display, FromStr};
+
+/// How many passwords have their letter between the policy's minimum and maximum times.
+pub fn star1(input: &[PasswordReq]) -> usize {
+    input
+        .iter()
+        .filter(|&pass_unit| pass_unit.correct_part1())
+        .count()
+}
+
+/// How many passwords have their letter at exactly one of the two positions of the policy.
+pub fn star2(input: &[PasswordReq]) -> usize {
+    input
+        .iter()
+        .filter(|&pass_unit| pass_unit.correct_part2())
+        .count()
+}
+
+/// A password policy combined with the actual password, like "1-3 a: abcde".
+#[derive(Debug, Display, FromStr)]
+#[display("{num1}-{num2} {letter}: {password}")]
+pub struct PasswordReq {
+    num1: usize,
+    num2: usize,
+    letter: char,
+    password: String,
+}
+
+impl PasswordReq {
+    fn correct_part1(&self) -> bool {
+        let count = self.password.chars().filter(|x| *x == self.letter).count();
+
+        self.num1 <= count && count <= self.num2
+    }
+
+    fn correct_part2(&self) -> bool {
+        (self.password.chars().nth(self.num1 - 1).unwrap_or('\0') == self.letter)
+            ^ (self.password.chars().nth(self.num2 - 1).unwrap_or('\0') == self.letter)
+    }
+}
+
+/// Parses one password with its policy per line.
+pub fn parse_input(input: &str) -> Result<Vec<PasswordReq>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| parse_at(input, line, "a password policy like \"1-3 a: abcde\""))
+        .collect()
+}
+
+/// Password Philosophy, checking passwords against two readings of the same policies.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Input<'a> = Vec<PasswordReq>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day02 {
+    const SIZE: &'static str = "passwords";
+    const DEFAULT_SIZE: usize = 1000;
+
+    // Few different letters, so the policy's letter shows up often enough to matter
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let letters = ['a', 'b', 'c', 'd', 'e'];
+        let (mut correct1, mut correct2) = (0, 0);
+        let lines = (0..size).map(|_| {
+            let len = rng.range(3, 20) as usize;
+            let num1 = rng.range(1, len as u64 - 1) as usize;
+            let num2 = rng.range(num1 as u64 + 1, len as u64 + 1) as usize;
+            let letter = *rng.pick(&letters);
+            let password: Vec<char> = (0..len).map(|_| *rng.pick(&letters)).collect();
+
+            let count = password.iter().filter(|ch| **ch == letter).count();
+            correct1 += (num1 <= count && count <= num2) as usize;
+            correct2 += ((password[num1 - 1] == letter) != (password[num2 - 1] == letter)) as usize;
+            let password: String = password.into_iter().collect();
+            format!("{}-{} {}: {}", num1, num2, letter, password)
+        });
+        let input = generate::lines(lines.collect::<Vec<String>>());
+        Generated::new(input)
+            .with_part1(correct1)
+            .with_part2(correct2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+
+    const INPUT: &str = include_str!("./../../inputs/day-02.txt");
+
+    #[test]
+    fn full_star1() {
+        let input = parse_input(INPUT).unwrap();
+        let correct_count = star1(&input);
+        assert_eq!(correct_count, answers::expected(2, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let input = parse_input(INPUT).unwrap();
+        let correct_count = star2(&input);
+        assert_eq!(correct_count, answers::expected(2, "full", 2));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day02>(20, &[1, 100]);
+    }
+}
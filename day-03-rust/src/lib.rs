@@ -0,0 +1,226 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::grid::Grid2D;
+use aoc_common::params::{Param, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::fmt;
+
+/// Trees hit going down the map 3 right and 1 down at a time.
+pub fn star1(map: &Map) -> usize {
+    encountered_trees(map, 3, 1)
+}
+
+/// The slopes of the second star, as (right, down).
+pub const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// Product of the trees hit on each of the slopes.
+pub fn star2(map: &Map, slopes: &[(usize, usize)]) -> usize {
+    slopes
+        .iter()
+        .map(|(right, down)| encountered_trees(map, *right, *down))
+        .try_fold(1usize, |product, trees| product.checked_mul(trees))
+        .expect("Product of the trees doesn't fit!")
+}
+
+/// Parses slopes written as `<right>/<down>`, separated by commas.
+pub fn parse_slopes(slopes: &str) -> Result<Vec<(usize, usize)>, String> {
+    slopes
+        .split(',')
+        .map(|slope| {
+            let (right, down) = slope
+                .trim()
+                .split_once('/')
+                .ok_or_else(|| format!("'{}' is not <right>/<down>!", slope))?;
+            let right = right
+                .parse()
+                .map_err(|_| format!("'{}' is not a number!", right))?;
+            match down.parse() {
+                Ok(down) if down > 0 => Ok((right, down)),
+                _ => Err(format!("'{}' is not a positive number!", down)),
+            }
+        })
+        .collect()
+}
+
+/// Parses the map of open squares '.' and trees '#'.
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    Map::new(input)
+}
+
+fn encountered_trees(map: &Map, right: usize, down: usize) -> usize {
+    let mut tree_count = 0;
+    let mut y = down;
+    let mut x = right;
+    while y < map.grid.height() {
+        if let Field::Tree = map.at(x, y) {
+            tree_count += 1;
+        }
+        y += down;
+        x += right;
+    }
+    tree_count
+}
+
+#[allow(dead_code)] // Allow visited options for debugging
+#[derive(Copy, Clone, Debug)]
+enum Field {
+    Open,
+    Tree,
+    VisitedOpen,
+    VisitedTree,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Field::Open => write!(f, "."),
+            Field::Tree => write!(f, "#"),
+            Field::VisitedOpen => write!(f, "O"),
+            Field::VisitedTree => write!(f, "X"),
+        }
+    }
+}
+
+/// The area with the trees, repeating itself to the right.
+#[derive(Debug)]
+pub struct Map {
+    grid: Grid2D<Field>,
+}
+
+impl Map {
+    fn new(input: &str) -> Result<Map, ParseError> {
+        let grid = Grid2D::parse(input, input, "'.' or '#'", |ch| match ch {
+            '.' => Some(Field::Open),
+            '#' => Some(Field::Tree),
+            _ => None,
+        })?;
+        Ok(Map { grid })
+    }
+
+    // The map repeats itself to the right
+    fn at(&self, x: usize, y: usize) -> Field {
+        *self.grid.get_wrapping(x as isize, y as isize)
+    }
+
+    #[allow(dead_code)] // Allow option to visit the map for debugging
+    fn visit(&mut self, x: usize, y: usize) {
+        let finite_x = x % self.grid.width();
+        let field = &mut self.grid[(finite_x, y)];
+        *field = match field {
+            Field::Open => Field::VisitedOpen,
+            Field::Tree => Field::VisitedTree,
+            _ => panic!("Visited ({}, {}) twice!", finite_x, y),
+        }
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+/// Toboggan Trajectory, counting the trees on the way down the slope.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    // The map with the slopes for the second part
+    type Input<'a> = (Map, Vec<(usize, usize)>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day03::parse_with(input, &Params::defaults(Day03::PARAMS))
+    }
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "slopes",
+        default: "1/1,3/1,5/1,7/1,1/2",
+        description: "Slopes as <right>/<down> whose trees are multiplied in part 2",
+        check: |value| parse_slopes(value).map(|_| ()),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        let slopes = parse_slopes(params.value("slopes")).expect("Slopes were checked before");
+        Ok((parse_input(input)?, slopes))
+    }
+
+    fn part1((map, _): &Self::Input<'_>) -> Answer {
+        star1(map).into()
+    }
+
+    fn part2((map, slopes): &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(map, slopes).into())
+    }
+}
+
+impl Generator for Day03 {
+    const SIZE: &'static str = "rows";
+    const DEFAULT_SIZE: usize = 323;
+    const MIN_SIZE: usize = 2;
+    // The product of the trees on all slopes has to fit
+    const MAX_SIZE: usize = 5000;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let width = 31;
+        let trees: Vec<Vec<bool>> = (0..size)
+            .map(|_| (0..width).map(|_| rng.range(0, 5) == 0).collect())
+            .collect();
+
+        let count = |(right, down): (usize, usize)| {
+            (1..)
+                .map(|step| (step * right % width, step * down))
+                .take_while(|(_, y)| *y < size)
+                .filter(|(x, y)| trees[*y][*x])
+                .count()
+        };
+        let product: usize = SLOPES.iter().map(|slope| count(*slope)).product();
+
+        let mut input = String::new();
+        for row in trees.iter() {
+            input.extend(row.iter().map(|tree| if *tree { '#' } else { '.' }));
+            input.push('\n');
+        }
+        Generated::new(input)
+            .with_part1(count((3, 1)))
+            .with_part2(product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-03.txt");
+
+    #[test]
+    fn full_star1() {
+        let map = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&map), answers::expected(3, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let map = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&map, &SLOPES), answers::expected(3, "full", 2));
+    }
+
+    #[test]
+    fn other_slopes() {
+        let map = parse_input(INPUT).unwrap();
+        let slopes = parse_slopes("3/1, 1/2").unwrap();
+        assert_eq!(slopes, vec![(3, 1), (1, 2)]);
+        let product = star1(&map) * encountered_trees(&map, 1, 2);
+        assert_eq!(star2(&map, &slopes), product);
+        assert!(parse_slopes("3/0").is_err());
+        assert!(parse_slopes("3").is_err());
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day03>(20, &[2, 11, 500]);
+    }
+}
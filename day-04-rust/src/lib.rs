@@ -0,0 +1,237 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::input;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// The key and value pairs of a single passport.
+pub type Passport<'a> = Vec<(&'a str, &'a str)>;
+
+/// How many passports have all seven required fields, `cid` may be missing.
+pub fn star1(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .map(|passport| passport.iter().filter(|(key, _)| *key != "cid").count())
+        .filter(|x| *x == 7)
+        .count()
+}
+
+/// How many passports have all seven required fields with valid values.
+pub fn star2(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .filter(|passport| validate_passport(passport))
+        .count()
+}
+
+/// Parses the passports, separated by blank lines, into their "key:value" fields.
+pub fn parse_input(input: &str) -> Result<Vec<Passport<'_>>, ParseError> {
+    input::paragraphs(input)
+        .map(|passport| {
+            passport
+                .split_whitespace()
+                .map(|field| {
+                    field
+                        .split_once(':')
+                        .ok_or_else(|| ParseError::at(input, field, "a field like \"key:value\""))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn validate_passport(passport: &[(&str, &str)]) -> bool {
+    passport
+        .iter()
+        .filter(|(key, value)| validate_field(key, value).unwrap_or(false))
+        .count()
+        == 7 // All 7 required fields are valid
+}
+
+fn validate_field(key: &str, value: &str) -> Result<bool, std::num::ParseIntError> {
+    match key {
+        "byr" => {
+            let year: u32 = value.parse()?;
+            Ok((1920..=2002).contains(&year))
+        }
+        "iyr" => {
+            let year: u32 = value.parse()?;
+            Ok((2010..=2020).contains(&year))
+        }
+        "eyr" => {
+            let year: u32 = value.parse()?;
+            Ok((2020..=2030).contains(&year))
+        }
+        "hgt" => {
+            if let Some(height) = value.strip_suffix("cm") {
+                let height: u32 = height.parse()?;
+                return Ok((150..=193).contains(&height));
+            } else if let Some(height) = value.strip_suffix("in") {
+                let height: u32 = height.parse()?;
+                return Ok((59..=76).contains(&height));
+            }
+            Ok(false)
+        }
+        "hcl" => {
+            if !value.starts_with('#') {
+                return Ok(false);
+            }
+
+            let hex_chars = value
+                .chars()
+                .skip(1)
+                .filter(|x| x.is_ascii_hexdigit())
+                .count();
+            Ok(hex_chars == 6)
+        }
+        "ecl" => {
+            let accepted = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+            Ok(accepted.contains(&value))
+        }
+        "pid" => Ok(value.chars().filter(|x| x.is_ascii_digit()).count() == 9),
+        "cid" => Ok(false),
+        _ => Ok(false),
+    }
+}
+
+/// Passport Processing, letting the passports without a country ID through.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    type Input<'a> = Vec<Passport<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+impl Generator for Day04 {
+    const SIZE: &'static str = "passports";
+    const DEFAULT_SIZE: usize = 290;
+
+    // Every passport is either valid, has all fields but an invalid value, or misses a field
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let (mut present, mut valid) = (0, 0);
+        let mut passports = Vec::with_capacity(size);
+        for _ in 0..size {
+            let kind = rng.range(0, 3);
+            let invalid_field = rng.range(0, 7) as usize;
+            let mut fields: Vec<String> = REQUIRED_FIELDS
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| kind != 2 || *idx != invalid_field)
+                .map(|(idx, key)| {
+                    let value = field_value(rng, key, kind == 0 || idx != invalid_field);
+                    format!("{}:{}", key, value)
+                })
+                .collect();
+            if rng.bool() {
+                fields.push(format!("cid:{}", rng.range(100, 350)));
+            }
+            rng.shuffle(&mut fields);
+
+            present += (kind != 2) as usize;
+            valid += (kind == 0) as usize;
+            let mut passport = String::new();
+            for (idx, field) in fields.iter().enumerate() {
+                if idx > 0 {
+                    passport.push(if rng.range(0, 4) == 0 { '\n' } else { ' ' });
+                }
+                passport.push_str(field);
+            }
+            passports.push(passport);
+        }
+        Generated::new(passports.join("\n\n") + "\n")
+            .with_part1(present)
+            .with_part2(valid)
+    }
+}
+
+fn field_value(rng: &mut Rng, key: &str, valid: bool) -> String {
+    let year = |rng: &mut Rng, low: u64, high: u64| {
+        if valid {
+            rng.range(low, high + 1)
+        } else if rng.bool() {
+            rng.range(low - 20, low)
+        } else {
+            rng.range(high + 1, high + 20)
+        }
+    };
+    match key {
+        "byr" => year(rng, 1920, 2002).to_string(),
+        "iyr" => year(rng, 2010, 2020).to_string(),
+        "eyr" => year(rng, 2020, 2030).to_string(),
+        "hgt" => match (valid, rng.bool()) {
+            (true, true) => format!("{}cm", rng.range(150, 194)),
+            (true, false) => format!("{}in", rng.range(59, 77)),
+            (false, true) => format!("{}cm", rng.range(59, 77)),
+            (false, false) => rng.range(150, 194).to_string(),
+        },
+        "hcl" => {
+            let color = format!("{:06x}", rng.range(0, 1 << 24));
+            if valid {
+                format!("#{}", color)
+            } else {
+                color
+            }
+        }
+        "ecl" => {
+            let colors = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+            let invalid = ["zzz", "xry", "red", "gmt"];
+            rng.pick(if valid { &colors[..] } else { &invalid[..] })
+                .to_string()
+        }
+        _ => {
+            let digits = if valid { 9 } else { *rng.pick(&[8, 10]) };
+            (0..digits).map(|_| rng.range(0, 10).to_string()).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-04.txt");
+
+    #[test]
+    fn full_star1() {
+        assert_eq!(
+            star1(&parse_input(INPUT).unwrap()),
+            answers::expected(4, "full", 1)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        assert_eq!(
+            star2(&parse_input(INPUT).unwrap()),
+            answers::expected(4, "full", 2)
+        );
+    }
+
+    #[test]
+    fn field_without_value() {
+        let err = parse_input("ecl:gry pid:860033327\nbyr1937\n\nhcl:#cfa07d").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "byr1937"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day04>(20, &[1, 100]);
+    }
+}
@@ -0,0 +1,132 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// Parses the boarding passes into seat IDs, each code is a binary number with F and L as
+/// zeros and B and R as ones.
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "a seat code of 10 letters",
+        ));
+    }
+    input
+        .trim()
+        .lines()
+        .map(|line| parse_id(input, line.trim()))
+        .collect()
+}
+
+/// The highest seat ID on a boarding pass.
+pub fn star1(ids: &[u32]) -> &u32 {
+    ids.iter().max().expect("No ids found!")
+}
+
+/// The missing seat ID between two taken ones, sorts the IDs on the way.
+pub fn star2(ids: &mut [u32]) -> u32 {
+    ids.sort_unstable();
+    ids.iter()
+        .zip(ids.iter().skip(1))
+        .find(|(&left, &right)| left + 1 < right)
+        .map(|(left, _)| left + 1)
+        .expect("No empty seat found!")
+}
+
+// The seat code is just a binary number, with F and L as zeros and B and R as ones
+fn parse_id(input: &str, line: &str) -> Result<u32, ParseError> {
+    if line.len() != 10 {
+        return Err(ParseError::at(input, line, "a seat code of 10 letters"));
+    }
+    line.char_indices().try_fold(0, |id, (idx, ch)| match ch {
+        'F' | 'L' => Ok(id << 1),
+        'B' | 'R' => Ok(id << 1 | 1),
+        _ => Err(ParseError::at(
+            input,
+            &line[idx..idx + ch.len_utf8()],
+            "one of F, B, L or R",
+        )),
+    })
+}
+
+/// Binary Boarding, decoding seat IDs to find the one that is free.
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        (*star1(input)).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&mut input.clone()).into())
+    }
+}
+
+impl Generator for Day05 {
+    const SIZE: &'static str = "boarding passes";
+    const DEFAULT_SIZE: usize = 800;
+    const MIN_SIZE: usize = 2;
+    // There are only 1024 seats, and the first and last ones are never taken
+    const MAX_SIZE: usize = 1021;
+
+    // A block of taken seats with a single empty one inside it
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let first = rng.range(1, 1023 - size as u64) as u32;
+        let last = first + size as u32;
+        let empty = rng.range(first as u64 + 1, last as u64) as u32;
+        let mut ids: Vec<u32> = (first..=last).filter(|id| *id != empty).collect();
+        rng.shuffle(&mut ids);
+
+        let passes = ids.iter().map(|id| {
+            (0..10)
+                .rev()
+                .map(|bit| match (bit >= 3, id >> bit & 1 == 1) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                })
+                .collect::<String>()
+        });
+        Generated::new(generate::lines(passes))
+            .with_part1(last)
+            .with_part2(empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+
+    const INPUT: &str = include_str!("./../../inputs/day-05.txt");
+
+    #[test]
+    fn full_star1() {
+        let ids = parse_input(INPUT).unwrap();
+        let max = star1(&ids);
+        assert_eq!(*max, answers::expected(5, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let mut ids = parse_input(INPUT).unwrap();
+        let my_seat = star2(&mut ids);
+        assert_eq!(my_seat, answers::expected(5, "full", 2));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day05>(20, &[2, 100, 1021]);
+    }
+}
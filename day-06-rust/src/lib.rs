@@ -0,0 +1,166 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::input;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+/// The questions each person of a group answered "yes" to.
+pub type Group<'a> = Vec<&'a str>;
+
+/// Sum over the groups of the questions anyone in the group answered.
+pub fn star1(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .flat_map(|person| person.chars())
+                .collect::<HashSet<char>>()
+                .len()
+        })
+        .sum()
+}
+
+/// Sum over the groups of the questions everyone in the group answered.
+pub fn star2(groups: &[Group]) -> usize {
+    let alphabet: HashSet<char> = ('a'..='z').collect();
+
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .fold(alphabet.clone(), |set, person| {
+                    let choices: HashSet<char> = person.chars().collect();
+                    set.intersection(&choices).copied().collect()
+                })
+                .len()
+        })
+        .sum()
+}
+
+/// Parses the groups, separated by blank lines, with one person per line.
+pub fn parse_input(input: &str) -> Result<Vec<Group<'_>>, ParseError> {
+    input::paragraphs(input)
+        .map(|group| {
+            group
+                .lines()
+                .map(|person| {
+                    let person = person.trim();
+                    match person
+                        .char_indices()
+                        .find(|(_, ch)| !ch.is_ascii_lowercase())
+                    {
+                        Some((idx, ch)) => Err(ParseError::at(
+                            input,
+                            &person[idx..idx + ch.len_utf8()],
+                            "a question from a to z",
+                        )),
+                        None => Ok(person),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Custom Customs, tallying the answers on the customs forms.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input<'a> = Vec<Group<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day06 {
+    const SIZE: &'static str = "groups";
+    const DEFAULT_SIZE: usize = 480;
+
+    // Everyone in a group answers the questions the group shares, plus some of their own
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let (mut anyone, mut everyone) = (0, 0);
+        let mut groups = Vec::with_capacity(size);
+        for _ in 0..size {
+            let shared: u32 = (0..26)
+                .map(|bit| ((rng.range(0, 8) == 0) as u32) << bit)
+                .sum();
+            let people: Vec<u32> = (0..rng.range(1, 6))
+                .map(|_| {
+                    let own: u32 = (0..26)
+                        .map(|bit| ((rng.range(0, 4) == 0) as u32) << bit)
+                        .sum();
+                    // Nobody answers nothing at all
+                    match shared | own {
+                        0 => 1 << rng.range(0, 26),
+                        answers => answers,
+                    }
+                })
+                .collect();
+            anyone += people
+                .iter()
+                .fold(0, |all, person| all | person)
+                .count_ones();
+            everyone += people
+                .iter()
+                .fold(!0, |all, person| all & person)
+                .count_ones();
+
+            let lines: Vec<String> = people
+                .iter()
+                .map(|person| {
+                    let mut questions: Vec<char> = ('a'..='z')
+                        .enumerate()
+                        .filter(|(bit, _)| person >> bit & 1 == 1)
+                        .map(|(_, question)| question)
+                        .collect();
+                    rng.shuffle(&mut questions);
+                    questions.into_iter().collect()
+                })
+                .collect();
+            groups.push(lines.join("\n"));
+        }
+        Generated::new(groups.join("\n\n") + "\n")
+            .with_part1(anyone)
+            .with_part2(everyone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-06.txt");
+
+    #[test]
+    fn full_star1() {
+        let anyone_yes = star1(&parse_input(INPUT).unwrap());
+        assert_eq!(anyone_yes, answers::expected(6, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let everyone_yes = star2(&parse_input(INPUT).unwrap());
+        assert_eq!(everyone_yes, answers::expected(6, "full", 2));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day06>(20, &[1, 100]);
+    }
+}
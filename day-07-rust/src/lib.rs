@@ -0,0 +1,286 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::graph::{Direction, Graph, NodeId};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashMap;
+
+/// The bag rules as a graph, every bag color is a node with an edge to each bag it contains,
+/// weighted by how many.
+pub struct Bags {
+    graph: Graph<String, u32>,
+    gold: Option<NodeId>,
+}
+
+impl Bags {
+    fn gold(&self) -> NodeId {
+        self.gold.expect("No shiny gold bag found!")
+    }
+}
+
+/// How many bag colors can eventually contain a shiny gold bag.
+pub fn star1(bags: &Bags) -> usize {
+    // Everything the gold bag is reached from, except itself
+    bags.graph.bfs(bags.gold(), Direction::Incoming).len() - 1
+}
+
+/// How many bags a shiny gold bag has to contain.
+pub fn star2(bags: &Bags) -> u32 {
+    // Only the bags inside the gold one, a cycle among other bags doesn't matter
+    let order = bags
+        .graph
+        .topological_order_from(bags.gold())
+        .expect("Bags can't contain themselves!");
+
+    // Contained bags come later in the order, so they are done first
+    let mut bags_inside = vec![0; bags.graph.len()];
+    for &bag in order.iter().rev() {
+        bags_inside[bag] = bags
+            .graph
+            .successors(bag)
+            .map(|(inner, amount)| amount * (1 + bags_inside[inner]))
+            .sum();
+    }
+    bags_inside[bags.gold()]
+}
+
+struct Rule {
+    bag: String,
+    contents: Vec<(u32, String)>,
+}
+
+/// Parses the rules like "light red bags contain 1 bright white bag, 2 muted yellow bags.".
+pub fn parse_input(input: &str) -> Result<Bags, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "a rule like \"light red bags contain 1 bright white bag.\"",
+        ));
+    }
+    let rules = input
+        .trim()
+        .lines()
+        .map(|line| parse_rule(input, line.trim()))
+        .collect::<Result<Vec<Rule>, _>>()?;
+
+    let mut graph = Graph::new();
+    let mut ids: HashMap<String, NodeId> = HashMap::new();
+    let mut id = |graph: &mut Graph<String, u32>, bag: &str| {
+        *ids.entry(bag.to_owned())
+            .or_insert_with(|| graph.add_node(bag.to_owned()))
+    };
+    for rule in rules.iter() {
+        let container = id(&mut graph, &rule.bag);
+        for (amount, bag) in rule.contents.iter() {
+            let inner = id(&mut graph, bag);
+            graph.add_edge(container, inner, *amount);
+        }
+    }
+    let gold = ids.get("shiny gold").copied();
+    Ok(Bags { graph, gold })
+}
+
+// Rules look like "light red bags contain 1 bright white bag, 2 muted yellow bags."
+fn parse_rule(input: &str, line: &str) -> Result<Rule, ParseError> {
+    let mut words = line.split(' ');
+    let mut next_word = |expected: &str| {
+        words
+            .next()
+            .ok_or_else(|| ParseError::at(input, &line[line.len()..], expected))
+    };
+
+    let container = format!(
+        "{} {}",
+        next_word("a bag color")?,
+        next_word("a bag color")?
+    );
+    let mut contained: Vec<(u32, String)> = Vec::new();
+    next_word("\"bags\"")?;
+    let contain = next_word("\"contain\"")?;
+    if contain != "contain" {
+        return Err(ParseError::at(input, contain, "\"contain\""));
+    }
+    loop {
+        let s = next_word("a bag amount or \"no\"")?;
+        if s == "no" {
+            break;
+        }
+        // There are containments left
+        let amount: u32 = parse_at(input, s, "a bag amount or \"no\"")?;
+        let bag = format!(
+            "{} {}",
+            next_word("a bag color")?,
+            next_word("a bag color")?
+        );
+        contained.push((amount, bag));
+        let bags = next_word("\"bags,\" or \"bags.\"")?; // bags, or bags.
+        if bags.ends_with('.') {
+            break;
+        }
+    }
+
+    Ok(Rule {
+        bag: container,
+        contents: contained,
+    })
+}
+
+/// Handy Haversacks, following the rules of which bags go inside which.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input<'a> = Bags;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+// Bags that fit into the shiny gold one, few enough that the amount of bags inside it fits
+const BAGS_IN_GOLD: usize = 8;
+
+impl Generator for Day07 {
+    const SIZE: &'static str = "rules";
+    const DEFAULT_SIZE: usize = 594;
+    const MIN_SIZE: usize = BAGS_IN_GOLD + 1;
+
+    // Bags only contain bags that come after them in a random order, so there are no cycles
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let words_per_part = (size as f64).sqrt() as usize + 1;
+        let adjectives = generate::words(rng, words_per_part, 6);
+        let colors = generate::words(rng, words_per_part, 6);
+        let mut names: Vec<String> = adjectives
+            .iter()
+            .flat_map(|adjective| {
+                colors
+                    .iter()
+                    .map(move |color| format!("{} {}", adjective, color))
+            })
+            .collect();
+        rng.shuffle(&mut names);
+        names.truncate(size);
+        let gold = size - BAGS_IN_GOLD - 1;
+        names[gold] = "shiny gold".to_owned();
+
+        let contents: Vec<Vec<(u64, usize)>> = (0..size)
+            .map(|bag| {
+                let (max_inside, max_amount) = if bag < gold { (5, 6) } else { (3, 4) };
+                let mut inside: Vec<usize> = (bag + 1..size).collect();
+                rng.shuffle(&mut inside);
+                inside.truncate(rng.range(0, max_inside) as usize);
+                inside
+                    .into_iter()
+                    .map(|inner| (rng.range(1, max_amount), inner))
+                    .collect()
+            })
+            .collect();
+
+        // Later bags are done first, as they're the ones the earlier ones contain
+        let mut holds_gold = vec![false; size];
+        let mut bags_inside = vec![0; size];
+        for bag in (0..size).rev() {
+            holds_gold[bag] = contents[bag]
+                .iter()
+                .any(|(_, inner)| *inner == gold || holds_gold[*inner]);
+            // Bags before the shiny gold one could hold more bags than fit into a number
+            if bag >= gold {
+                bags_inside[bag] = contents[bag]
+                    .iter()
+                    .map(|(amount, inner)| amount * (1 + bags_inside[*inner]))
+                    .sum();
+            }
+        }
+
+        let mut rules: Vec<String> = contents
+            .iter()
+            .enumerate()
+            .map(|(bag, inside)| {
+                let inside: Vec<String> = inside
+                    .iter()
+                    .map(|(amount, inner)| {
+                        let bags = if *amount == 1 { "bag" } else { "bags" };
+                        format!("{} {} {}", amount, names[*inner], bags)
+                    })
+                    .collect();
+                let inside = if inside.is_empty() {
+                    "no other bags".to_owned()
+                } else {
+                    inside.join(", ")
+                };
+                format!("{} bags contain {}.", names[bag], inside)
+            })
+            .collect();
+        rng.shuffle(&mut rules);
+        Generated::new(generate::lines(rules))
+            .with_part1(holds_gold.iter().filter(|holds| **holds).count())
+            .with_part2(bags_inside[gold])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+
+    const INPUT: &str = include_str!("./../../inputs/day-07.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-07.txt");
+
+    #[test]
+    fn simple_star1() {
+        let bags = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&bags), answers::expected(7, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let rules = parse_input(INPUT).unwrap();
+        let bag_count = star1(&rules);
+        assert_eq!(bag_count, answers::expected(7, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let rules = parse_input(INPUT).unwrap();
+        let in_gold_bag = star2(&rules);
+        assert_eq!(in_gold_bag, answers::expected(7, "full", 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bags can't contain themselves!")]
+    fn bags_in_a_cycle() {
+        let bags = parse_input(
+            "shiny gold bags contain 1 dark red bag.\n\
+             dark red bags contain 2 shiny gold bags.",
+        )
+        .unwrap();
+        star2(&bags);
+    }
+
+    #[test]
+    fn unrelated_cycle() {
+        let bags = parse_input(
+            "shiny gold bags contain 2 dark red bags.\n\
+             dark red bags contain no other bags.\n\
+             faded blue bags contain 1 dotted black bag.\n\
+             dotted black bags contain 3 faded blue bags.",
+        )
+        .unwrap();
+        assert_eq!(star2(&bags), 2);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day07>(10, &[9, 100, 500]);
+    }
+}
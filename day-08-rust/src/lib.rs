@@ -0,0 +1,292 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::convert::TryFrom;
+use std::convert::TryInto;
+
+/// Parses the program, one instruction like "acc +1" per line.
+pub fn parse_input(input: &str) -> Result<Vec<Instr>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "an instruction like \"acc +1\"",
+        ));
+    }
+    input
+        .trim()
+        .lines()
+        .map(|line| Instr::parse(input, line.trim()))
+        .collect()
+}
+
+/// Value of the accumulator right before any instruction is executed a second time.
+pub fn star1(instrs: &Vec<Instr>) -> i32 {
+    let mut instr_keeper = InstrKeeper::new(instrs);
+    let mut regs = Regs::new();
+    loop {
+        if instr_keeper.terminated(&regs) {
+            panic!("Didn't encounter an infinite loop!");
+        }
+
+        if instr_keeper.already_visited(&regs) {
+            break;
+        }
+
+        regs = instr_keeper.visit(&regs).execute_on(regs);
+    }
+
+    regs.acc
+}
+
+/// Value of the accumulator once the program ends, after turning the one `jmp` or `nop` that
+/// makes it end into the other.
+pub fn star2(instrs: &Vec<Instr>) -> i32 {
+    let mut instr_keeper = InstrKeeper::new(instrs);
+    find_fix(&mut instr_keeper, Regs::new(), false).unwrap()
+}
+
+fn find_fix(instr_keeper: &mut InstrKeeper, regs: Regs, already_modified: bool) -> Option<i32> {
+    if instr_keeper.terminated(&regs) {
+        return Some(regs.acc);
+    }
+
+    if instr_keeper.already_visited(&regs) {
+        return None;
+    }
+
+    let instr = instr_keeper.visit(&regs);
+
+    // See what happens if we don't change anything
+    let new_regs = instr.execute_on(regs);
+    let result = find_fix(instr_keeper, new_regs, already_modified);
+    if result.is_some() {
+        return result;
+    }
+
+    // Try with modification
+    if let (false, Some(new_instr)) = (already_modified, instr.swap()) {
+        let new_regs = new_instr.execute_on(regs);
+        let result = find_fix(instr_keeper, new_regs, true);
+        if result.is_some() {
+            return result;
+        }
+    }
+
+    // Reset and return
+    instr_keeper.reset_visit(&regs);
+    None
+}
+
+/// The accumulator and the index of the next instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Regs {
+    pub acc: i32,
+    pub pc: usize,
+}
+
+impl Default for Regs {
+    fn default() -> Regs {
+        Regs::new()
+    }
+}
+
+impl Regs {
+    /// Registers at the start of a program, both zero.
+    pub fn new() -> Regs {
+        Regs { acc: 0, pc: 0 }
+    }
+}
+
+/// A single instruction with its signed operand.
+#[derive(Debug, Copy, Clone)]
+pub enum Instr {
+    NOP(i32),
+    ACC(i32),
+    JMP(i32),
+}
+
+impl Instr {
+    fn parse(input: &str, line: &str) -> Result<Instr, ParseError> {
+        let (mnemonic, operand) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at(input, line, "an instruction like \"acc +1\""))?;
+        let operand: i32 = parse_at(input, operand, "a signed number")?;
+        match mnemonic {
+            "nop" => Ok(Instr::NOP(operand)),
+            "acc" => Ok(Instr::ACC(operand)),
+            "jmp" => Ok(Instr::JMP(operand)),
+            _ => Err(ParseError::at(input, mnemonic, "one of nop, acc or jmp")),
+        }
+    }
+
+    /// A `jmp` turned into a `nop` or the other way around, `acc` can't be swapped.
+    pub fn swap(&self) -> Option<Instr> {
+        match self {
+            Instr::NOP(x) => Some(Instr::JMP(*x)),
+            Instr::JMP(x) => Some(Instr::NOP(*x)),
+            _ => None,
+        }
+    }
+
+    /// Registers after executing the instruction on them.
+    ///
+    /// Panics if a jump goes before the first instruction.
+    pub fn execute_on(&self, registers: Regs) -> Regs {
+        let Regs { acc, pc } = registers;
+
+        let (new_acc, new_pc) = match self {
+            Instr::NOP(_) => (acc, pc + 1),
+            Instr::ACC(x) => (acc + *x, pc + 1),
+            Instr::JMP(x) => (acc, (i32::try_from(pc).unwrap() + *x).try_into().unwrap()),
+        };
+
+        Regs {
+            acc: new_acc,
+            pc: new_pc,
+        }
+    }
+}
+
+/// Runs a program while keeping track of the instructions it already executed, so loops can be
+/// found.
+pub struct InstrKeeper<'a> {
+    instrs: &'a Vec<Instr>,
+    visited: Vec<bool>,
+}
+
+impl InstrKeeper<'_> {
+    /// A keeper for the program with none of its instructions executed yet.
+    pub fn new(instrs: &Vec<Instr>) -> InstrKeeper<'_> {
+        InstrKeeper {
+            instrs,
+            visited: vec![false; instrs.len()],
+        }
+    }
+
+    /// Whether the program counter went past the last instruction.
+    pub fn terminated(&self, regs: &Regs) -> bool {
+        regs.pc >= self.instrs.len()
+    }
+
+    /// Whether the instruction at the program counter was executed already.
+    pub fn already_visited(&self, regs: &Regs) -> bool {
+        self.visited[regs.pc]
+    }
+
+    /// Marks the instruction at the program counter as executed and returns it.
+    pub fn visit(&mut self, regs: &Regs) -> Instr {
+        self.visited[regs.pc] = true;
+        self.instrs[regs.pc]
+    }
+
+    /// Marks the instruction at the program counter as not executed again, for backtracking.
+    pub fn reset_visit(&mut self, regs: &Regs) {
+        self.visited[regs.pc] = false;
+    }
+}
+
+/// Handheld Halting, fixing the boot code of the handheld game console.
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input<'a> = Vec<Instr>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day08 {
+    const SIZE: &'static str = "instructions";
+    const DEFAULT_SIZE: usize = 650;
+    const MIN_SIZE: usize = 3;
+
+    // Every jump before the trap goes forward, up to the trap at most, and the trap jumps back.
+    // So the only way past the trap is to turn it into a nop, which makes the fix unique.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let trap = rng.range(size as u64 / 2, size as u64 - 1) as usize;
+        let mut instrs: Vec<Instr> = (0..size)
+            .map(|idx| {
+                let forward_to = if idx < trap { trap } else { size };
+                match rng.range(0, 3) {
+                    0 => Instr::ACC(rng.range(0, 100) as i32 - 50),
+                    // Turned into a jump, a nop must not jump before the first instruction
+                    1 => Instr::NOP(-(rng.range(0, idx as u64 + 1) as i32)),
+                    _ => Instr::JMP(rng.range(1, (forward_to - idx) as u64 + 1) as i32),
+                }
+            })
+            .collect();
+        instrs[trap] = Instr::JMP(-(rng.range(1, trap as u64 + 1) as i32));
+
+        let (mut regs, mut visited) = (Regs::new(), vec![false; size]);
+        while !visited[regs.pc] {
+            visited[regs.pc] = true;
+            regs = instrs[regs.pc].execute_on(regs);
+        }
+        let loop_acc = regs.acc;
+
+        let mut fixed = instrs.clone();
+        fixed[trap] = Instr::NOP(0);
+        let mut regs = Regs::new();
+        while regs.pc < size {
+            regs = fixed[regs.pc].execute_on(regs);
+        }
+
+        let lines = instrs.iter().map(|instr| match instr {
+            Instr::NOP(x) => format!("nop {:+}", x),
+            Instr::ACC(x) => format!("acc {:+}", x),
+            Instr::JMP(x) => format!("jmp {:+}", x),
+        });
+        Generated::new(generate::lines(lines))
+            .with_part1(loop_acc)
+            .with_part2(regs.acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+
+    const INPUT: &str = include_str!("./../../inputs/day-08.txt");
+
+    #[test]
+    fn full_star1() {
+        let instrs = parse_input(INPUT).unwrap();
+        let acc = star1(&instrs);
+        assert_eq!(acc, answers::expected(8, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let instrs = parse_input(INPUT).unwrap();
+        let acc = star2(&instrs);
+        assert_eq!(acc, answers::expected(8, "full", 2));
+    }
+
+    #[test]
+    fn invalid_instruction() {
+        let err = parse_input("nop +0\nmul +3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "mul"));
+
+        let err = parse_input("nop +0\njmp four\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "four"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day08>(20, &[3, 10, 1000]);
+    }
+}
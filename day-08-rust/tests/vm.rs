@@ -0,0 +1,34 @@
+use day_08_rust::{parse_input, Instr, InstrKeeper, Regs};
+
+const PROGRAM: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+// Runs until an instruction comes up a second time, or the program ends. True if it ended.
+fn run(instrs: &Vec<Instr>) -> (Regs, bool) {
+    let mut keeper = InstrKeeper::new(instrs);
+    let mut regs = Regs::new();
+    while !keeper.terminated(&regs) {
+        if keeper.already_visited(&regs) {
+            return (regs, false);
+        }
+        regs = keeper.visit(&regs).execute_on(regs);
+    }
+    (regs, true)
+}
+
+#[test]
+fn stops_at_the_loop() {
+    let instrs = parse_input(PROGRAM).unwrap();
+    let (regs, terminated) = run(&instrs);
+    assert_eq!(regs, Regs { acc: 5, pc: 1 });
+    assert!(!terminated);
+}
+
+#[test]
+fn swapped_instruction_ends_the_program() {
+    let mut instrs = parse_input(PROGRAM).unwrap();
+    assert!(instrs[1].swap().is_none());
+    instrs[7] = instrs[7].swap().unwrap();
+    let (regs, terminated) = run(&instrs);
+    assert_eq!(regs.acc, 8);
+    assert!(terminated);
+}
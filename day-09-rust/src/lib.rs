@@ -0,0 +1,372 @@
+use aoc_common::cancel;
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution, Strategy};
+use std::cmp::min;
+use std::collections::HashMap;
+
+/// Length of the preamble, and of the window of numbers each one has to be a sum from.
+pub const AMOUNT: usize = 25;
+
+/// The first number that isn't the sum of two of the `amount` numbers before it.
+pub fn star1(numbers: &[u64], amount: usize) -> &u64 {
+    find_invalid_buffer(numbers, amount)
+}
+
+/// Sum of the smallest and the largest number in the contiguous range adding up to `invalid`.
+pub fn star2(numbers: &[u64], invalid: u64) -> u64 {
+    let (idx_low, idx_high) = find_zone(numbers, invalid);
+    let range = numbers[idx_low..=idx_high].iter();
+    let (min, max) = (range.clone().min().unwrap(), range.clone().max().unwrap());
+    min + max
+}
+
+/// Parses the XMAS data, one number per line.
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(input, &input[..0], "a number"));
+    }
+    input
+        .trim()
+        .lines()
+        .map(|line| parse_at(input, line.trim(), "a number"))
+        .collect()
+}
+
+// Checks every pair of numbers, the sum buffer turns out not to be that much faster
+fn find_invalid(numbers: &[u64], amount: usize) -> &u64 {
+    numbers
+        .iter()
+        .enumerate()
+        .skip(amount)
+        .find(|(idx, &x)| !contained(x, &numbers[idx - amount..*idx]))
+        .expect("No invalid number found!")
+        .1
+}
+
+fn contained(sum: u64, range: &[u64]) -> bool {
+    for x in range {
+        for y in range {
+            if x + y == sum {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn find_zone(numbers: &[u64], target: u64) -> (usize, usize) {
+    let mut idx_low = 0;
+    let mut idx_high = 0;
+    let mut sum = numbers[0];
+    while sum != target {
+        cancel::checkpoint();
+        if sum < target {
+            idx_high += 1;
+            sum += numbers[idx_high];
+        } else if sum > target {
+            sum -= numbers[idx_low];
+            idx_low += 1;
+        }
+    }
+    (idx_low, idx_high)
+}
+
+// Smarter solution, that uses a circular buffer for calculating sum size.
+// However, it turns out its not really any faster.
+// Probably, because the input is too small.
+fn find_invalid_buffer(numbers: &[u64], amount: usize) -> &u64 {
+    if numbers.len() <= amount {
+        panic!("Need more than {} numbers!", amount);
+    }
+    let mut buffer = SumBuffer::new(numbers, amount);
+    for x in numbers[amount..].iter() {
+        if !buffer.inside(*x) {
+            return x;
+        }
+        buffer.compute_next_line();
+    }
+    panic!("No number found!");
+}
+
+// Sums of every number in the window with the ones after it, one row per number
+struct SumBuffer<'a> {
+    origin: &'a [u64],
+    amount: usize,
+    sums: Vec<u64>,
+    idx: usize,
+}
+
+impl SumBuffer<'_> {
+    fn new(origin: &[u64], amount: usize) -> SumBuffer<'_> {
+        let mut buffer = SumBuffer {
+            origin,
+            amount,
+            sums: vec![0; amount * amount],
+            idx: 0,
+        };
+
+        for _idx in 0..amount {
+            buffer.compute_next_line();
+        }
+
+        buffer
+    }
+
+    fn inside(&self, value: u64) -> bool {
+        let amount = self.amount;
+        let mut row_offset = self.idx * amount % self.sums.len();
+        for row in 0..amount {
+            let max_col = amount - row;
+            if self.sums[row_offset..row_offset + max_col].contains(&value) {
+                return true;
+            }
+            row_offset = (row_offset + amount) % self.sums.len();
+        }
+        false
+    }
+
+    fn compute_next_line(&mut self) {
+        let offset = self.idx * self.amount % self.sums.len();
+        let left = self.origin.len() - self.idx;
+        let val1 = self.origin[self.idx];
+        for _col in 0..min(left, self.amount) {
+            let sum = val1 + self.origin[self.idx + _col];
+            self.sums[offset + _col] = sum;
+        }
+        self.idx += 1;
+    }
+}
+
+/// Encoding Error, finding the weakness in the XMAS encrypted data.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+
+    // Numbers with the length of the preamble
+    type Input<'a> = (Vec<u64>, usize);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day09::parse_with(input, &Params::defaults(Day09::PARAMS))
+    }
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "amount",
+        default: "25",
+        description: "Length of the preamble, and of the window valid numbers are sums of",
+        check: |value| params::between(value, 2usize, 1000),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        let numbers = parse_input(input)?;
+        let amount = params.get("amount");
+        // Some number after the preamble has to be checked against it
+        if numbers.len() <= amount {
+            let trimmed = input.trim_end();
+            let expected = format!("more than {} numbers", amount);
+            return Err(ParseError::at(input, &trimmed[trimmed.len()..], expected));
+        }
+        Ok((numbers, amount))
+    }
+
+    fn part1((numbers, amount): &Self::Input<'_>) -> Answer {
+        (*star1(numbers, *amount)).into()
+    }
+
+    fn part2((numbers, amount): &Self::Input<'_>) -> Option<Answer> {
+        let invalid = *star1(numbers, *amount);
+        Some(star2(numbers, invalid).into())
+    }
+
+    const DEFAULT_STRATEGY: &'static str = "buffer";
+
+    fn strategies() -> Vec<Strategy<Self>> {
+        vec![Strategy {
+            name: "pairs",
+            slow: false,
+            part1: Some(|(numbers, amount)| (*find_invalid(numbers, *amount)).into()),
+            part2: Some(|(numbers, amount)| star2(numbers, *find_invalid(numbers, *amount)).into()),
+        }]
+    }
+}
+
+impl Generator for Day09 {
+    const SIZE: &'static str = "numbers";
+    const DEFAULT_SIZE: usize = 1000;
+    const MIN_SIZE: usize = AMOUNT + 3;
+    // Every number is the sum of two earlier ones, so they at least double every AMOUNT numbers
+    const MAX_SIZE: usize = 1200;
+
+    // Valid numbers add up the smallest ones in their window, to grow as slowly as possible.
+    // The invalid number is the sum of a contiguous range before it.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        loop {
+            let invalid_idx = rng.range(AMOUNT as u64 + 2, size as u64) as usize;
+            let mut numbers: Vec<u64> = (0..AMOUNT).map(|_| rng.range(1, 51)).collect();
+            while numbers.len() < size {
+                if numbers.len() == invalid_idx {
+                    match invalid_range_sum(rng, &numbers) {
+                        Some(invalid) => numbers.push(invalid),
+                        None => break,
+                    }
+                    continue;
+                }
+                let mut window = numbers[numbers.len() - AMOUNT..].to_vec();
+                window.sort_unstable();
+                numbers.push(window[0] + window[rng.range(1, 4) as usize]);
+            }
+            if numbers.len() < size {
+                continue;
+            }
+
+            let invalid = numbers[invalid_idx];
+            let (low, high) = first_range_with_sum(&numbers, invalid);
+            let range = &numbers[low..=high];
+            let weakness = range.iter().min().unwrap() + range.iter().max().unwrap();
+            return Generated::new(generate::lines(numbers))
+                .with_part1(invalid)
+                .with_part2(weakness);
+        }
+    }
+}
+
+// Sum of a contiguous range that isn't the sum of two numbers in the last window, and isn't
+// one of the numbers either
+fn invalid_range_sum(rng: &mut Rng, numbers: &[u64]) -> Option<u64> {
+    let window = &numbers[numbers.len() - AMOUNT..];
+    (0..100).find_map(|_| {
+        let high = rng.range(1, numbers.len() as u64) as usize;
+        let low = rng.range(high.saturating_sub(16) as u64, high as u64) as usize;
+        let sum: u64 = numbers[low..=high].iter().sum();
+        Some(sum).filter(|sum| !contained(*sum, window) && !numbers.contains(sum))
+    })
+}
+
+// The range ending first, which is the one `find_zone` finds
+fn first_range_with_sum(numbers: &[u64], target: u64) -> (usize, usize) {
+    let mut starts: HashMap<u64, usize> = HashMap::new();
+    let mut prefix = 0;
+    for (idx, number) in numbers.iter().enumerate() {
+        starts.insert(prefix, idx);
+        prefix += number;
+        if let Some(start) = prefix
+            .checked_sub(target)
+            .and_then(|rest| starts.get(&rest))
+        {
+            return (*start, idx);
+        }
+    }
+    panic!("No range adds up to {}!", target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::differential::{self, shrink_vec, Case};
+    use aoc_common::rng::Rng;
+
+    const INPUT: &str = include_str!("./../../inputs/day-09.txt");
+
+    #[test]
+    fn full_star1() {
+        let numbers = parse_input(INPUT).unwrap();
+        let invalid = star1(&numbers, AMOUNT);
+        assert_eq!(*invalid, answers::expected(9, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let numbers = parse_input(INPUT).unwrap();
+        let zone_sum = star2(&numbers, answers::expected(9, "full", 1));
+        assert_eq!(zone_sum, answers::expected(9, "full", 2));
+    }
+
+    #[test]
+    fn only_a_preamble() {
+        let numbers: Vec<String> = (1..=AMOUNT).map(|number| number.to_string()).collect();
+        let input = numbers.join("\n") + "\n";
+        let err = Day09::parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (25, 3));
+        assert_eq!(err.expected, "more than 25 numbers");
+        assert!(Day09::parse(&(input + "3\n")).is_ok());
+    }
+
+    // A preamble of random length, followed by sums of pairs from the numbers before them,
+    // an invalid number and a few random ones
+    #[derive(Debug, Clone)]
+    struct Sequence {
+        numbers: Vec<u64>,
+        amount: usize,
+    }
+
+    impl Sequence {
+        fn has_invalid(&self) -> bool {
+            let amount = self.amount;
+            self.numbers
+                .windows(amount + 1)
+                .any(|window| !contained(window[amount], &window[..amount]))
+        }
+    }
+
+    impl Case for Sequence {
+        fn generate(rng: &mut Rng) -> Sequence {
+            let amount = rng.range(2, AMOUNT as u64 + 1) as usize;
+            let mut numbers: Vec<u64> = (1..100).collect();
+            rng.shuffle(&mut numbers);
+            numbers.truncate(amount);
+
+            for _ in 0..rng.range(0, 30) {
+                let window = &numbers[numbers.len() - amount..];
+                let first = rng.range(0, amount as u64) as usize;
+                let second = (first + rng.range(1, amount as u64) as usize) % amount;
+                numbers.push(window[first] + window[second]);
+            }
+            loop {
+                let candidate = rng.range(1, 1000);
+                if !contained(candidate, &numbers[numbers.len() - amount..]) {
+                    numbers.push(candidate);
+                    break;
+                }
+            }
+            for _ in 0..rng.range(0, 5) {
+                numbers.push(rng.range(1, 1000));
+            }
+            Sequence { numbers, amount }
+        }
+
+        fn shrink(&self) -> Vec<Sequence> {
+            shrink_vec(&self.numbers, self.amount + 1)
+                .into_iter()
+                .map(|numbers| Sequence {
+                    numbers,
+                    amount: self.amount,
+                })
+                .filter(Sequence::has_invalid)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree::<Sequence, u64>(
+            300,
+            &[
+                ("buffer", |sequence| {
+                    *find_invalid_buffer(&sequence.numbers, sequence.amount)
+                }),
+                ("pairs", |sequence| {
+                    *find_invalid(&sequence.numbers, sequence.amount)
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day09>(20, &[AMOUNT + 3, 100, 1200]);
+    }
+}
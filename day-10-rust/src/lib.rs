@@ -0,0 +1,196 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// Parses the joltage ratings of the adapters and sorts them, with the outlet's 0 in front and
+/// the device's rating at the end.
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let mut numbers = input
+        .trim()
+        .lines()
+        .map(|line| parse_at(input, line.trim(), "a joltage rating"))
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    // Adapt input to add start and end
+    numbers.sort_unstable();
+    numbers.insert(0, 0);
+    numbers.push(numbers.last().unwrap() + 3);
+    Ok(numbers)
+}
+
+/// The number of 1-jolt differences multiplied by the number of 3-jolt differences in the
+/// chain of every adapter.
+pub fn star1(numbers: &[u32]) -> usize {
+    let differences: Vec<u32> = numbers
+        .iter()
+        .zip(numbers.iter().skip(1))
+        .map(|(x, next)| next - x)
+        .collect();
+
+    let (diff1, rest): (Vec<u32>, Vec<u32>) = differences.iter().partition(|d| **d == 1);
+    let (diff3, _rest): (Vec<u32>, Vec<u32>) = rest.iter().partition(|d| **d == 3);
+    diff1.len() * diff3.len()
+}
+
+/// In how many ways the adapters can connect the outlet to the device.
+pub fn star2(numbers: &[u32]) -> usize {
+    let mut comb_counter = CombinationCounter::new(numbers);
+    comb_counter.ways_possible(numbers, 0)
+}
+
+struct CombinationCounter {
+    cache: Vec<Option<usize>>,
+}
+
+impl CombinationCounter {
+    fn new(numbers: &[u32]) -> CombinationCounter {
+        CombinationCounter {
+            cache: vec![None; numbers.len()],
+        }
+    }
+
+    fn ways_cache(&mut self, numbers: &[u32], full_idx: usize) -> usize {
+        if let Some(x) = self.cache[full_idx] {
+            x
+        } else {
+            let result = self.ways_possible(numbers, full_idx);
+            self.cache[full_idx] = Some(result);
+            result
+        }
+    }
+
+    fn ways_possible(&mut self, numbers: &[u32], full_idx: usize) -> usize {
+        let previous = numbers[0];
+        let rest = &numbers[1..];
+        if rest.is_empty() {
+            // End reached; this is one possible combination
+            return 1;
+        }
+
+        let next_count = rest.iter().take(3).filter(|x| **x <= previous + 3).count();
+
+        if next_count == 0 {
+            0 // Cannot reach the next number via this candidate
+        } else {
+            (0..next_count)
+                .map(|idx| self.ways_cache(&rest[idx..], full_idx + 1 + idx))
+                .sum()
+        }
+    }
+}
+
+/// Adapter Array, chaining the joltage adapters between the outlet and the device.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day10 {
+    const SIZE: &'static str = "adapters";
+    const DEFAULT_SIZE: usize = 100;
+
+    // Runs of up to four steps of 1 between steps of 3. Every step of 3 has to be taken, so the
+    // arrangements multiply up over the runs. Once they wouldn't fit anymore, runs stay short.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let arrangements_of_run: [u64; 5] = [1, 1, 2, 4, 7];
+        let mut steps: Vec<u32> = Vec::with_capacity(size + 4);
+        let mut arrangements: u64 = 1;
+        while steps.len() < size {
+            let mut run = rng.range(0, 5) as usize;
+            if arrangements
+                .checked_mul(7 * arrangements_of_run[run])
+                .is_none_or(|a| a > 1 << 62)
+            {
+                run = run.min(1);
+            }
+            let run = run.min(size - steps.len());
+            arrangements *= arrangements_of_run[run];
+            steps.extend(std::iter::repeat_n(1, run));
+            steps.push(3);
+        }
+        steps.truncate(size);
+
+        let ones = steps.iter().filter(|step| **step == 1).count();
+        // The device is always 3 above the last adapter
+        let threes = steps.len() - ones + 1;
+        let mut adapters: Vec<u32> = steps
+            .iter()
+            .scan(0, |joltage, step| {
+                *joltage += step;
+                Some(*joltage)
+            })
+            .collect();
+        rng.shuffle(&mut adapters);
+        Generated::new(generate::lines(adapters))
+            .with_part1(ones * threes)
+            .with_part2(arrangements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-10.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-10.txt");
+    const SIMPLE_SMALL_INPUT: &str = include_str!("./../../inputs/simple/day-10-small.txt");
+
+    #[test]
+    fn simple_small_star1() {
+        let numbers = parse_input(SIMPLE_SMALL_INPUT).unwrap();
+        assert_eq!(star1(&numbers), answers::expected(10, "simple-small", 1));
+    }
+
+    #[test]
+    fn simple_star1() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&numbers), answers::expected(10, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let numbers = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&numbers), answers::expected(10, "full", 1));
+    }
+
+    #[test]
+    fn simple_small_star2() {
+        let numbers = parse_input(SIMPLE_SMALL_INPUT).unwrap();
+        assert_eq!(star2(&numbers), answers::expected(10, "simple-small", 2));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&numbers), answers::expected(10, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let numbers = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&numbers), answers::expected(10, "full", 2));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day10>(20, &[1, 30, 500]);
+    }
+}
@@ -0,0 +1,388 @@
+use aoc_common::frames::Frames;
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::geometry::Dir8;
+use aoc_common::grid::Grid2D;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::simulation::{self, Simulation, Stop};
+use aoc_common::solution::{Answer, Solution};
+use std::fmt;
+
+/// Parses the seat layout of floor '.', empty seats 'L' and taken seats '#'.
+pub fn parse_input(input: &str) -> Result<Simulator, ParseError> {
+    Simulator::new(input)
+}
+
+/// Taken seats once the layout settles, with people looking at the eight seats around them.
+/// Panics if it doesn't settle.
+pub fn star1(simulator: &mut Simulator) -> usize {
+    simulator.simulate(Rule::Neighbors)
+}
+
+/// Taken seats once the layout settles, with people looking at the first seat in each
+/// direction instead.
+pub fn star2(simulator: &mut Simulator) -> usize {
+    simulator.simulate(Rule::FirstSeats)
+}
+
+// Layouts that haven't settled after this many rounds are given up on
+const MAX_ROUNDS: usize = 1000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum PositionStatus {
+    Floor,
+    EmptySeat,
+    TakenSeat,
+}
+
+impl fmt::Display for PositionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionStatus::Floor => write!(f, "."),
+            PositionStatus::EmptySeat => write!(f, "L"),
+            PositionStatus::TakenSeat => write!(f, "#"),
+        }
+    }
+}
+
+/// One state of the seat layout.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid {
+    seats: Grid2D<PositionStatus>,
+}
+
+impl Grid {
+    fn new(input: &str) -> Result<Grid, ParseError> {
+        let seats = Grid2D::parse(input, input, "one of '.', 'L' or '#'", |mark| match mark {
+            '.' => Some(PositionStatus::Floor),
+            'L' => Some(PositionStatus::EmptySeat),
+            '#' => Some(PositionStatus::TakenSeat),
+            _ => None,
+        })?;
+        Ok(Grid { seats })
+    }
+
+    fn at(&self, row: usize, col: usize) -> PositionStatus {
+        self.seats[(col, row)]
+    }
+
+    fn edit(&mut self, row: usize, col: usize, status: PositionStatus) {
+        self.seats.set(col, row, status);
+    }
+
+    fn count_occupied_neighbors(&self, row: usize, col: usize) -> u32 {
+        self.seats
+            .neighbors8(col, row)
+            .filter(|position| self.seats[*position] == PositionStatus::TakenSeat)
+            .count() as u32 // Always <= 8
+    }
+
+    // Skips the floor, looking past the edge finds nothing but floor
+    fn find_first_seat(&self, row: usize, col: usize, direction: Dir8) -> PositionStatus {
+        self.seats
+            .line_of_sight(col, row, direction.offset())
+            .map(|position| self.seats[position])
+            .find(|seat| *seat != PositionStatus::Floor)
+            .unwrap_or(PositionStatus::Floor)
+    }
+
+    fn count_occupied_first_seats(&self, row: usize, col: usize) -> u32 {
+        Dir8::ALL
+            .iter()
+            .filter(|direction| {
+                self.find_first_seat(row, col, **direction) == PositionStatus::TakenSeat
+            })
+            .count() as u32 // Always <= 8
+    }
+
+    fn count_occupied_seats(&self) -> usize {
+        self.seats
+            .iter()
+            .filter(|seat| **seat == PositionStatus::TakenSeat)
+            .count()
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.seats)
+    }
+}
+
+// Which seats people look at before sitting down or leaving
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Rule {
+    Neighbors,
+    FirstSeats,
+}
+
+/// Runs the rounds of people sitting down and leaving, switching between two grids.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    grid1: Grid,
+    grid2: Grid,
+    current_grid: u32,
+    width: usize,
+    height: usize,
+    rule: Rule,
+}
+
+impl Simulator {
+    fn new(input: &str) -> Result<Simulator, ParseError> {
+        Ok(Simulator::from_grid(Grid::new(input)?))
+    }
+
+    fn from_grid(grid: Grid) -> Simulator {
+        Simulator {
+            width: grid.seats.width(),
+            height: grid.seats.height(),
+            grid1: grid.clone(),
+            grid2: grid,
+            current_grid: 1,
+            rule: Rule::Neighbors,
+        }
+    }
+
+    fn current_grid_ref(&self) -> &Grid {
+        if self.current_grid == 1 {
+            &self.grid1
+        } else {
+            &self.grid2
+        }
+    }
+
+    fn next_grid_ref(&self) -> &Grid {
+        if self.current_grid == 1 {
+            &self.grid2
+        } else {
+            &self.grid1
+        }
+    }
+
+    fn next_grid_mut_ref(&mut self) -> &mut Grid {
+        if self.current_grid == 1 {
+            &mut self.grid2
+        } else {
+            &mut self.grid1
+        }
+    }
+
+    fn iterate_neighbors(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let current_state = self.current_grid_ref().at(row, col);
+                if current_state == PositionStatus::Floor {
+                    // Floor shouldn't ever change; currently it is default for empty
+                    continue;
+                };
+                let occupied_neighbors = self.current_grid_ref().count_occupied_neighbors(row, col);
+                let next_status = match (current_state, occupied_neighbors) {
+                    (PositionStatus::EmptySeat, 0) => PositionStatus::TakenSeat,
+                    (PositionStatus::TakenSeat, x) if x >= 4 => PositionStatus::EmptySeat,
+                    _ => current_state,
+                };
+                self.next_grid_mut_ref().edit(row, col, next_status);
+            }
+        }
+        self.current_grid = if self.current_grid == 1 { 2 } else { 1 };
+    }
+
+    fn iterate_first_seat(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let current_state = self.current_grid_ref().at(row, col);
+                if current_state == PositionStatus::Floor {
+                    // Floor shouldn't ever change; currently it is default for empty
+                    continue;
+                };
+                let occupied_neighbors =
+                    self.current_grid_ref().count_occupied_first_seats(row, col);
+                let next_status = match (current_state, occupied_neighbors) {
+                    (PositionStatus::EmptySeat, 0) => PositionStatus::TakenSeat,
+                    (PositionStatus::TakenSeat, x) if x >= 5 => PositionStatus::EmptySeat,
+                    _ => current_state,
+                };
+                self.next_grid_mut_ref().edit(row, col, next_status);
+            }
+        }
+        self.current_grid = if self.current_grid == 1 { 2 } else { 1 };
+    }
+
+    // Whether the seats stop changing within a reasonable number of rounds
+    fn settles(&mut self, rule: Rule) -> bool {
+        self.rule = rule;
+        matches!(
+            simulation::until_fixpoint(self, MAX_ROUNDS),
+            Stop::Fixpoint { .. }
+        )
+    }
+
+    // Every round until the seats settle, or the given number of rounds
+    fn record(&mut self, rule: Rule, rounds: Option<usize>) -> Frames {
+        self.rule = rule;
+        Frames::record(self, rounds.unwrap_or(MAX_ROUNDS), |simulator| {
+            simulator.current_grid_ref().to_string()
+        })
+    }
+
+    fn simulate(&mut self, rule: Rule) -> usize {
+        self.rule = rule;
+        match simulation::until_fixpoint(self, MAX_ROUNDS) {
+            Stop::Fixpoint { .. } => self.current_grid_ref().count_occupied_seats(),
+            Stop::Cycle { period, .. } => {
+                panic!("Seats never settle, they repeat every {} rounds!", period)
+            }
+            _ => panic!("Seats didn't settle within {} rounds!", MAX_ROUNDS),
+        }
+    }
+}
+
+impl Simulation for Simulator {
+    type State = Grid;
+
+    fn step(&mut self) {
+        match self.rule {
+            Rule::Neighbors => self.iterate_neighbors(),
+            Rule::FirstSeats => self.iterate_first_seat(),
+        }
+    }
+
+    fn state(&self) -> Grid {
+        self.current_grid_ref().clone()
+    }
+}
+
+impl fmt::Display for Simulator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Current grid:").unwrap();
+        write!(f, "{}", self.current_grid_ref()).unwrap();
+        writeln!(f).unwrap();
+        writeln!(f, "Next grid:").unwrap();
+        write!(f, "{}", self.next_grid_ref()).unwrap();
+        Ok(())
+    }
+}
+
+/// Seating System, predicting where people sit in the waiting area.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input<'a> = Simulator;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(&mut input.clone()).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&mut input.clone()).into())
+    }
+
+    fn frames(input: &Self::Input<'_>, part: u32, steps: Option<usize>) -> Option<Frames> {
+        let rule = if part == 1 {
+            Rule::Neighbors
+        } else {
+            Rule::FirstSeats
+        };
+        Some(input.clone().record(rule, steps))
+    }
+}
+
+impl Generator for Day11 {
+    const SIZE: &'static str = "rows and columns";
+    const DEFAULT_SIZE: usize = 95;
+
+    // Seats with some floor in between. Not every layout settles, so the ones that keep
+    // changing for too long are thrown away.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        loop {
+            let layout = Grid2D::from_vec(
+                size,
+                (0..size * size)
+                    .map(|_| match rng.range(0, 10) {
+                        0..=2 => PositionStatus::Floor,
+                        _ => PositionStatus::EmptySeat,
+                    })
+                    .collect(),
+            );
+            let simulator = Simulator::from_grid(Grid { seats: layout });
+            let settles = simulator.clone().settles(Rule::Neighbors)
+                && simulator.clone().settles(Rule::FirstSeats);
+            if settles {
+                return Generated::new(simulator.current_grid_ref().to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-11.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-11.txt");
+
+    #[test]
+    fn simple_star1() {
+        let mut simulator = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&mut simulator), answers::expected(11, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let mut simulator = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&mut simulator), answers::expected(11, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let mut simulator = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&mut simulator), answers::expected(11, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let mut simulator = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&mut simulator), answers::expected(11, "full", 2));
+    }
+
+    #[test]
+    fn rounds_until_settled() {
+        let mut simulator = parse_input(SIMPLE_INPUT).unwrap();
+        simulator.rule = Rule::Neighbors;
+        // The example settles after five rounds
+        assert_eq!(
+            simulation::until_fixpoint(&mut simulator, MAX_ROUNDS),
+            Stop::Fixpoint { steps: 5 }
+        );
+        let mut simulator = parse_input(SIMPLE_INPUT).unwrap();
+        simulator.rule = Rule::FirstSeats;
+        assert_eq!(
+            simulation::until_fixpoint(&mut simulator, MAX_ROUNDS),
+            Stop::Fixpoint { steps: 6 }
+        );
+    }
+
+    #[test]
+    fn frames_of_every_round() {
+        let simulator = parse_input(SIMPLE_INPUT).unwrap();
+        // The initial layout and the five rounds it takes to settle
+        let frames = Day11::frames(&simulator, 1, None).unwrap();
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames.iter().next().unwrap(), simulator.grid1.to_string());
+        assert!(frames.iter().nth(1).unwrap().starts_with("#.##.##.##"));
+        assert_eq!(Day11::frames(&simulator, 2, Some(2)).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day11>(5, &[1, 10, 50]);
+    }
+}
@@ -0,0 +1,226 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::geometry::{Dir4, Point2};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// Parses the navigation instructions, an action letter followed by a number like "F10".
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Instruction::new(input, line.trim()))
+        .collect()
+}
+
+/// Manhattan distance of the ship from its start, with the instructions moving the ship.
+pub fn star1(instructions: &[Instruction]) -> u64 {
+    let mut position = Position::new();
+    for instr in instructions {
+        position.apply(instr);
+    }
+    position.manhattan_distance_from_start()
+}
+
+/// Manhattan distance of the ship from its start, with the instructions moving a waypoint
+/// the ship sails towards.
+pub fn star2(instructions: &[Instruction]) -> u64 {
+    let mut position = Position::new();
+    for instr in instructions {
+        position.apply_waypoint(instr);
+    }
+    position.manhattan_distance_from_start()
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Action {
+    Move(Dir4),
+    Forward,
+    Left,
+    Right,
+}
+
+impl Action {
+    fn new(letter: char) -> Option<Action> {
+        match letter {
+            'N' => Some(Action::Move(Dir4::North)),
+            'E' => Some(Action::Move(Dir4::East)),
+            'S' => Some(Action::Move(Dir4::South)),
+            'W' => Some(Action::Move(Dir4::West)),
+            'F' => Some(Action::Forward),
+            'L' => Some(Action::Left),
+            'R' => Some(Action::Right),
+            _ => None,
+        }
+    }
+}
+
+/// A single navigation instruction, turns are in degrees.
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    action: Action,
+    amount: u32,
+}
+
+impl Instruction {
+    fn new(input: &str, line: &str) -> Result<Instruction, ParseError> {
+        let letter = line.chars().next().unwrap_or(' ');
+        let (letter_text, amount_text) = line.split_at(letter.len_utf8().min(line.len()));
+        let action = Action::new(letter)
+            .ok_or_else(|| ParseError::at(input, letter_text, "one of N, E, S, W, F, L or R"))?;
+        let amount: u32 = parse_at(input, amount_text, "a number")?;
+
+        // Turns only work in quarters
+        if let Action::Left | Action::Right = action {
+            if !amount.is_multiple_of(90) || amount > 360 {
+                return Err(ParseError::at(
+                    input,
+                    amount_text,
+                    "a multiple of 90 up to 360",
+                ));
+            }
+        }
+
+        Ok(Instruction { action, amount })
+    }
+
+    // Quarter turns to the right, negative ones to the left
+    fn quarters(&self) -> i32 {
+        let quarters = (self.amount / 90) as i32;
+        match self.action {
+            Action::Left => -quarters,
+            _ => quarters,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Position {
+    ship: Point2,
+    heading: Dir4,
+    // Relative to the ship
+    waypoint: Point2,
+}
+
+impl Position {
+    fn new() -> Position {
+        Position {
+            ship: Point2::ORIGIN,
+            heading: Dir4::East,
+            waypoint: Point2::new(10, -1),
+        }
+    }
+
+    fn apply(&mut self, instruction: &Instruction) {
+        let amount = i64::from(instruction.amount);
+        match instruction.action {
+            Action::Move(direction) => self.ship += direction.offset() * amount,
+            Action::Forward => self.ship += self.heading.offset() * amount,
+            Action::Left | Action::Right => {
+                self.heading = self.heading.turn(instruction.quarters())
+            }
+        }
+    }
+
+    fn apply_waypoint(&mut self, instruction: &Instruction) {
+        let amount = i64::from(instruction.amount);
+        match instruction.action {
+            Action::Move(direction) => self.waypoint += direction.offset() * amount,
+            Action::Forward => self.ship += self.waypoint * amount,
+            // The waypoint turns around the ship
+            Action::Left | Action::Right => {
+                self.waypoint = self.waypoint.rotate(instruction.quarters())
+            }
+        }
+    }
+
+    fn manhattan_distance_from_start(&self) -> u64 {
+        self.ship.manhattan(Point2::ORIGIN)
+    }
+}
+
+/// Rain Risk, following the ferry's navigation instructions.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day12 {
+    const SIZE: &'static str = "instructions";
+    const DEFAULT_SIZE: usize = 780;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let instructions = (0..size).map(|_| {
+            let action = *rng.pick(&['N', 'E', 'S', 'W', 'F', 'F', 'L', 'R']);
+            let amount = match action {
+                'L' | 'R' => 90 * rng.range(1, 4),
+                _ => rng.range(1, 100),
+            };
+            format!("{}{}", action, amount)
+        });
+        Generated::new(generate::lines(instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-12.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-12.txt");
+
+    #[test]
+    fn simple_star1() {
+        let input = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&input), answers::expected(12, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let input = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&input), answers::expected(12, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let input = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&input), answers::expected(12, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let input = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&input), answers::expected(12, "full", 2));
+    }
+
+    #[test]
+    fn invalid_instructions() {
+        let err = parse_input("F10\nX3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "X"));
+
+        let err = parse_input("F10\nR45\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "45"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day12>(5, &[1, 1000]);
+    }
+}
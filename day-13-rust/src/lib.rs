@@ -0,0 +1,430 @@
+use aoc_common::cancel;
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::math;
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution, Strategy};
+use std::cmp::Reverse;
+
+/// Parses the earliest departure time and the bus IDs, with 'x' for buses out of service.
+pub fn parse_input(input: &str) -> Result<(u32, Vec<Bus>), ParseError> {
+    let trimmed = input.trim();
+    let mut lines = trimmed.lines();
+    let mut next_line = |expected: &str| {
+        lines
+            .next()
+            .map(str::trim)
+            .ok_or_else(|| ParseError::at(input, &trimmed[trimmed.len()..], expected))
+    };
+
+    let depart = parse_at(
+        input,
+        next_line("the departure time")?,
+        "the departure time",
+    )?;
+    let line = next_line("a list of bus IDs")?;
+    let buses = line
+        .split(',')
+        .map(|id| Bus::new(input, id))
+        .collect::<Result<Vec<Bus>, _>>()?;
+    if buses.iter().all(|bus| matches!(bus, Bus::Unknown)) {
+        return Err(ParseError::at(input, line, "at least one bus ID"));
+    }
+    Ok((depart, buses))
+}
+
+/// ID of the first bus leaving after `timestamp`, multiplied by the minutes to wait for it.
+pub fn star1(timestamp: u32, buses: &[Bus]) -> u32 {
+    let (id, diff): (&u32, u32) = buses
+        .iter()
+        .filter(|bus| !matches!(bus, Bus::Unknown))
+        .map(|bus| match bus {
+            Bus::ID(id) => (id, id - (timestamp % id)),
+            Bus::Unknown => panic!("This variant should have been already filtered out!"),
+        })
+        .min_by_key(|(_id, diff)| *diff)
+        .unwrap();
+    id * diff
+}
+
+/// The earliest timestamp with every bus leaving as many minutes after it as its position
+/// in the list. Bus IDs don't have to be coprime, as long as some timestamp lines them all up.
+pub fn star2(buses: &[Bus]) -> u64 {
+    let congruences: Vec<(u64, u64)> = requirements(buses)
+        .into_iter()
+        .map(|(delay, id)| {
+            let id = id as u64;
+            (id - delay as u64 % id, id)
+        })
+        .collect();
+    let (timestamp, _) = math::crt(&congruences).expect("The buses never line up!");
+    timestamp
+}
+
+// Pairs of (delay, id) for every known bus
+fn requirements(buses: &[Bus]) -> Vec<(usize, u32)> {
+    buses
+        .iter()
+        .enumerate()
+        .filter(|(_idx, bus)| match bus {
+            Bus::ID(_) => true,
+            Bus::Unknown => false,
+        })
+        .map(|(idx, bus)| match bus {
+            Bus::ID(id) => (idx, *id),
+            Bus::Unknown => panic!("This variant should have been already filtered out!"),
+        })
+        .collect()
+}
+
+// IDs that share a factor are fine, the step then only grows by the part that isn't shared. The
+// remainders repeat after id / gcd steps, so if the bus hasn't lined up by then it never will.
+fn chinese_remainders_sieving(requirements: &mut [(usize, u32)]) -> u64 {
+    requirements.sort_by_key(|&(_, id)| Reverse(id));
+
+    let input: Vec<(u64, u64)> = requirements
+        .iter()
+        .map(|&(delay, id)| {
+            let delay = delay as u64;
+            let id = id as u64;
+            ((id - (delay % id)) % id, id)
+        })
+        .collect();
+
+    let mut solution = input.first().unwrap().0;
+    let mut add = input.first().unwrap().1;
+    for &(delay_next, id_next) in input.iter().skip(1) {
+        let period = id_next / math::gcd(add, id_next);
+        let steps = (0..period)
+            .position(|step| (solution + step * add) % id_next == delay_next)
+            .expect("The buses never line up!");
+        solution += steps as u64 * add;
+        add *= period;
+    }
+
+    solution
+}
+
+// Original solution, since I didn't Google anything and I could just let it
+// run while eating lunch and doing other things, so it finished as I had finished :)
+// It (only) took 3 hours.
+fn brute_force_star2(requirements: &mut [(usize, u32)]) -> u64 {
+    requirements.sort_by_key(|&(_, id)| Reverse(id));
+
+    // Searching for number, from the first timestamp where the first bus leaves on time
+    let &(first_delay, first_id) = requirements.first().unwrap();
+    let (first_delay, first_id) = (first_delay as u64, first_id as u64);
+    let mut timestamp = (first_id - first_delay % first_id) % first_id;
+    loop {
+        cancel::checkpoint();
+        let holds = requirements
+            .iter()
+            .skip(1)
+            .all(|&(delay, id)| (timestamp + delay as u64).is_multiple_of(id as u64));
+
+        if holds {
+            return timestamp;
+        }
+
+        timestamp += first_id;
+    }
+}
+
+/// A bus from the list, either with its ID or out of service.
+#[derive(Debug, Copy, Clone)]
+pub enum Bus {
+    ID(u32),
+    Unknown,
+}
+
+impl Bus {
+    fn new(input: &str, id: &str) -> Result<Bus, ParseError> {
+        if id == "x" {
+            return Ok(Bus::Unknown);
+        }
+        match parse_at(input, id, "a bus ID or 'x'")? {
+            0 => Err(ParseError::at(input, id, "a bus ID above zero")),
+            id => Ok(Bus::ID(id)),
+        }
+    }
+}
+
+/// Shuttle Search, finding when the shuttle buses line up.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input<'a> = (u32, Vec<Bus>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        let (timestamp, buses) = input;
+        star1(*timestamp, buses).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&input.1).into())
+    }
+
+    const DEFAULT_STRATEGY: &'static str = "crt";
+
+    fn strategies() -> Vec<Strategy<Self>> {
+        vec![
+            Strategy {
+                name: "sieving",
+                slow: false,
+                part1: None,
+                part2: Some(|(_, buses)| {
+                    chinese_remainders_sieving(&mut requirements(buses)).into()
+                }),
+            },
+            Strategy {
+                name: "brute-force",
+                slow: true,
+                part1: None,
+                part2: Some(|(_, buses)| brute_force_star2(&mut requirements(buses)).into()),
+            },
+        ]
+    }
+}
+
+impl Generator for Day13 {
+    const SIZE: &'static str = "bus slots";
+    const DEFAULT_SIZE: usize = 70;
+
+    // Distinct primes keep the IDs coprime, and like in the real input none of them is tiny.
+    // Every bus gets a slot where it leaves on time for a timestamp picked up front, so the
+    // earliest one is that timestamp modulo the product of the IDs.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut primes: Vec<u64> = (11..1000u64)
+            .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+            .collect();
+        rng.shuffle(&mut primes);
+
+        // The first slot always has a bus, so there is at least one
+        let mut timestamp = rng.next_u64() >> 2;
+        timestamp -= timestamp % primes[0];
+        let mut buses = vec![None; size];
+        buses[0] = Some(primes[0]);
+        let mut product = primes[0];
+        for &prime in primes.iter().skip(1) {
+            if product
+                .checked_mul(prime)
+                .is_none_or(|next| next >= 1 << 62)
+            {
+                continue;
+            }
+            let first = ((prime - timestamp % prime) % prime) as usize;
+            let free: Vec<usize> = (first..size)
+                .step_by(prime as usize)
+                .filter(|&slot| buses[slot].is_none())
+                .collect();
+            if !free.is_empty() {
+                buses[*rng.pick(&free)] = Some(prime);
+                product *= prime;
+            }
+        }
+
+        // A departure no bus leaves at, with a single bus leaving first after it
+        let ids: Vec<u64> = buses.iter().flatten().copied().collect();
+        let (depart, (id, wait)) = loop {
+            let depart = rng.range(1, 1_000_000);
+            let mut waits: Vec<(u64, u64)> = ids.iter().map(|id| (id - depart % id, *id)).collect();
+            waits.sort_unstable();
+            let unique = waits.len() == 1 || waits[0].0 < waits[1].0;
+            if unique && waits.iter().all(|(wait, id)| wait < id) {
+                break (depart, (waits[0].1, waits[0].0));
+            }
+        };
+
+        let slots: Vec<String> = buses
+            .iter()
+            .map(|bus| bus.map_or("x".to_owned(), |id| id.to_string()))
+            .collect();
+        Generated::new(format!("{}\n{}\n", depart, slots.join(",")))
+            .with_part1(id * wait)
+            .with_part2(timestamp % product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::differential::{self, Case};
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-13.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-13.txt");
+
+    #[test]
+    fn simple_star1() {
+        let (timestamp, buses) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(timestamp, &buses), answers::expected(13, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let (timestamp, buses) = parse_input(INPUT).unwrap();
+        assert_eq!(star1(timestamp, &buses), answers::expected(13, "full", 1));
+    }
+
+    #[test]
+    fn simpler_star2() {
+        let (_, buses) = parse_input("1\n17,x,13,19").unwrap();
+        assert_eq!(star2(&buses), 3417);
+    }
+
+    #[test]
+    fn simple_star2() {
+        let (_, buses) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&buses), answers::expected(13, "simple", 2));
+    }
+
+    #[test]
+    fn bigger_star2() {
+        let (_, buses) = parse_input("1\n1789,37,47,1889").unwrap();
+        assert_eq!(star2(&buses), 1202161486);
+    }
+
+    #[test]
+    fn shared_factors_star2() {
+        // 4 and 6 share a factor, the timestamps that work repeat every 12 minutes
+        let (_, buses) = parse_input("1\n4,x,6").unwrap();
+        assert_eq!(star2(&buses), 4);
+        assert_eq!(chinese_remainders_sieving(&mut requirements(&buses)), 4);
+    }
+
+    #[test]
+    fn late_first_bus_star2() {
+        // The brute force starts at the bus with the largest ID, here it leaves 6 minutes after
+        // the timestamp, more than its ID. Counting k * 3 - 6 from k = 1 went below zero.
+        let (_, buses) = parse_input("1\nx,x,x,x,x,x,3,2").unwrap();
+        assert_eq!(star2(&buses), 3);
+        assert_eq!(chinese_remainders_sieving(&mut requirements(&buses)), 3);
+        assert_eq!(brute_force_star2(&mut requirements(&buses)), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "The buses never line up!")]
+    fn buses_never_line_up() {
+        let (_, buses) = parse_input("1\n4,6").unwrap();
+        star2(&buses);
+    }
+
+    #[test]
+    #[should_panic(expected = "The buses never line up!")]
+    fn buses_never_line_up_sieving() {
+        let (_, buses) = parse_input("10\n4,6").unwrap();
+        chinese_remainders_sieving(&mut requirements(&buses));
+    }
+
+    #[test]
+    fn large_ids() {
+        let (_, buses) = parse_input("1\n4294967291,x,3").unwrap();
+        assert_eq!(
+            chinese_remainders_sieving(&mut requirements(&buses)),
+            star2(&buses)
+        );
+    }
+
+    #[test]
+    fn no_bus_ids() {
+        let err = parse_input("5\nx,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "x,x"));
+    }
+
+    #[test]
+    fn full_star2() {
+        let (_, buses) = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&buses), answers::expected(13, "full", 2));
+    }
+
+    // Small primes as bus IDs, so they are coprime and brute force finishes quickly
+    const PRIMES: [u32; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    #[derive(Debug, Clone)]
+    struct Schedule(Vec<Bus>);
+
+    impl Schedule {
+        fn ids(&self) -> Vec<u32> {
+            self.0
+                .iter()
+                .filter_map(|bus| match bus {
+                    Bus::ID(id) => Some(*id),
+                    Bus::Unknown => None,
+                })
+                .collect()
+        }
+    }
+
+    impl Case for Schedule {
+        fn generate(rng: &mut Rng) -> Schedule {
+            let mut ids = PRIMES.to_vec();
+            rng.shuffle(&mut ids);
+            ids.truncate(rng.range(1, 5) as usize);
+
+            // The first bus leaves at the timestamp itself, the others some minutes after it
+            let mut buses = vec![Bus::Unknown; rng.range(ids.len() as u64, 30) as usize];
+            let mut delays: Vec<usize> = (1..buses.len()).collect();
+            rng.shuffle(&mut delays);
+            for (id, delay) in ids.iter().zip(std::iter::once(0).chain(delays)) {
+                buses[delay] = Bus::ID(*id);
+            }
+            Schedule(buses)
+        }
+
+        fn shrink(&self) -> Vec<Schedule> {
+            let ids = self.ids();
+            let mut smaller = Vec::new();
+            for (idx, bus) in self.0.iter().enumerate() {
+                let mut without = self.0.clone();
+                match bus {
+                    Bus::Unknown => {
+                        without.remove(idx);
+                        smaller.push(Schedule(without));
+                    }
+                    Bus::ID(id) => {
+                        if ids.len() > 1 {
+                            without[idx] = Bus::Unknown;
+                            smaller.push(Schedule(without));
+                        }
+                        for prime in PRIMES.iter().filter(|prime| *prime < id) {
+                            if !ids.contains(prime) {
+                                let mut buses = self.0.clone();
+                                buses[idx] = Bus::ID(*prime);
+                                smaller.push(Schedule(buses));
+                            }
+                        }
+                    }
+                }
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree::<Schedule, u64>(
+            300,
+            &[
+                ("crt", |schedule| star2(&schedule.0)),
+                ("sieving", |schedule| {
+                    chinese_remainders_sieving(&mut requirements(&schedule.0))
+                }),
+                ("brute-force", |schedule| {
+                    brute_force_star2(&mut requirements(&schedule.0))
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day13>(20, &[1, 10, 70, 500]);
+    }
+}
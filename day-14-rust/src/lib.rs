@@ -0,0 +1,375 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+/// Bits of the memory values and addresses on the docking computer.
+pub const MEMORY_BITS: usize = 36;
+
+/// Parses the initialization program, masks have one letter for every bit of the memory.
+pub fn parse_input(input: &str, memory_bits: usize) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Instruction::new(input, line.trim(), memory_bits))
+        .collect()
+}
+
+/// Sum of the memory after the program runs with the masks applied to the values.
+pub fn star1(instructions: &[Instruction], memory_bits: usize) -> u64 {
+    let mut machine = Machine::new(memory_bits);
+    for instr in instructions.iter() {
+        machine.execute(instr);
+    }
+    machine.memory_sum()
+}
+
+/// Sum of the memory after the program runs with the masks applied to the addresses, the
+/// floating bits writing to every address they can stand for.
+pub fn star2(instructions: &[Instruction], memory_bits: usize) -> u64 {
+    let mut machine = Machine::new(memory_bits);
+    for instr in instructions.iter() {
+        machine.execute_version2(instr);
+    }
+    machine.memory_sum()
+}
+
+/// A bitmask with the bits it sets to one, to zero, and the floating ones.
+#[derive(Clone, Debug)]
+pub struct Mask {
+    memory: u64, // All bits of the memory
+    ones: u64,
+    zeros: u64,
+    floating: Vec<u8>,
+}
+
+impl Mask {
+    // Keeps values as they are, until the program sets a mask
+    fn empty(memory_bits: usize) -> Mask {
+        Mask {
+            memory: u64::MAX >> (64 - memory_bits),
+            ones: 0,
+            zeros: 0,
+            floating: Vec::new(),
+        }
+    }
+
+    fn new(input: &str, str_mask: &str, memory_bits: usize) -> Result<Mask, ParseError> {
+        if str_mask.len() != memory_bits {
+            let expected = format!("a mask of {} bits", memory_bits);
+            return Err(ParseError::at(input, str_mask, expected));
+        }
+        if let Some((idx, ch)) = str_mask.char_indices().find(|(_, ch)| !"01X".contains(*ch)) {
+            let ch = &str_mask[idx..idx + ch.len_utf8()];
+            return Err(ParseError::at(input, ch, "one of '0', '1' or 'X'"));
+        }
+
+        let chars_with_idx = Mask::get_chars_with_bit_idx(str_mask);
+        let ones = Mask::binary_mask(&chars_with_idx, '1');
+        let zeros = Mask::binary_mask(&chars_with_idx, '0');
+        let floating = Mask::get_floating(&chars_with_idx);
+        Ok(Mask {
+            memory: Mask::empty(memory_bits).memory,
+            ones,
+            zeros,
+            floating,
+        })
+    }
+
+    fn get_chars_with_bit_idx(input: &str) -> Vec<(u8, char)> {
+        let max_idx = input.len() - 1;
+        input
+            .chars()
+            .enumerate()
+            .map(|(idx, letter)| ((max_idx - idx).try_into().unwrap(), letter))
+            .collect()
+    }
+
+    fn binary_mask(chars_with_idx: &[(u8, char)], letter: char) -> u64 {
+        chars_with_idx
+            .iter()
+            .filter(|(_, ch)| *ch == letter)
+            .map(|(idx, _)| 1 << idx)
+            .fold(0, |mask, bit| mask | bit)
+    }
+
+    fn get_floating(chars_with_idx: &[(u8, char)]) -> Vec<u8> {
+        chars_with_idx
+            .iter()
+            .filter(|&(_, ch)| *ch == 'X')
+            .map(|(idx, _)| *idx)
+            .collect()
+    }
+
+    fn transform_value(&self, value: u64) -> u64 {
+        let with_ones = value | self.ones;
+
+        // 0 x 0 -> 0
+        // 1 x 0 -> 1
+        // 0 x 1 -> 0
+        // 1 x 1 -> 0
+        with_ones & (self.memory ^ self.zeros)
+    }
+
+    fn obtain_addresses(&self, address: usize) -> impl Iterator<Item = usize> + '_ {
+        let address = self.ones as usize | address;
+
+        // With 64 floating bits there are more addresses than a usize can count
+        let floating_combinations = 1usize
+            .checked_shl(self.floating.len() as u32)
+            .expect("Too many floating bits to write every address!");
+        (0..floating_combinations).map(move |floating_i| {
+            // This closure maps the number (for example, numbers from 0 to 8),
+            // to the addresses where floating bits are modified to represent the given numbers
+            let mut address = address;
+            let mut modifier: usize = floating_i;
+            for idx in self.floating.iter().rev() {
+                let bit = modifier & 0x1;
+                let bit_mask: usize = self.memory as usize ^ (1 << idx);
+                address = (address & bit_mask) | (bit << idx);
+                modifier >>= 1;
+            }
+            address
+        })
+    }
+}
+
+/// A line of the program, setting the mask or writing a value to an address.
+#[derive(Clone, Debug)]
+pub enum Instruction {
+    Mask(Mask),
+    Write(usize, u64),
+}
+
+impl Instruction {
+    fn new(input: &str, line: &str, memory_bits: usize) -> Result<Instruction, ParseError> {
+        if let Some(mask) = line.strip_prefix("mask = ") {
+            Ok(Instruction::Mask(Mask::new(input, mask, memory_bits)?))
+        } else if let Some(write) = line.strip_prefix("mem[") {
+            let (address, value) = write
+                .split_once("] = ")
+                .ok_or_else(|| ParseError::at(input, line, "a write like \"mem[8] = 11\""))?;
+            let address: usize = parse_at(input, address, "a memory address")?;
+            let value: u64 = parse_at(input, value, "a value")?;
+            Ok(Instruction::Write(address, value))
+        } else {
+            Err(ParseError::at(
+                input,
+                line,
+                "\"mask = ...\" or \"mem[...] = ...\"",
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Machine {
+    mask: Mask,
+    memory: HashMap<usize, u64>,
+}
+
+impl Machine {
+    fn new(memory_bits: usize) -> Machine {
+        Machine {
+            mask: Mask::empty(memory_bits),
+            memory: HashMap::new(),
+        }
+    }
+
+    fn execute(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Mask(mask) => self.mask = mask.clone(),
+            Instruction::Write(address, value) => {
+                self.memory
+                    .insert(*address, self.mask.transform_value(*value));
+            }
+        }
+    }
+
+    fn execute_version2(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Mask(mask) => self.mask = mask.clone(),
+            Instruction::Write(address, value) => {
+                for new_address in self.mask.obtain_addresses(*address) {
+                    self.memory.insert(new_address, *value);
+                }
+            }
+        }
+    }
+
+    fn memory_sum(&self) -> u64 {
+        self.memory.values().copied().sum()
+    }
+}
+
+/// Docking Data, running the initialization program of the ferry's docking computer.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    // Instructions with the size of the memory
+    type Input<'a> = (Vec<Instruction>, usize);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day14::parse_with(input, &Params::defaults(Day14::PARAMS))
+    }
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "memory-bits",
+        default: "36",
+        description: "Bits of values and addresses, and letters in a mask",
+        check: |value| params::between(value, 1usize, 64),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        let memory_bits = params.get("memory-bits");
+        Ok((parse_input(input, memory_bits)?, memory_bits))
+    }
+
+    fn part1((instructions, memory_bits): &Self::Input<'_>) -> Answer {
+        star1(instructions, *memory_bits).into()
+    }
+
+    fn part2((instructions, memory_bits): &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(instructions, *memory_bits).into())
+    }
+}
+
+impl Generator for Day14 {
+    const SIZE: &'static str = "instructions";
+    const DEFAULT_SIZE: usize = 580;
+
+    // Masks with at most 9 floating bits, like the real input, so every write of the second
+    // version touches at most 512 addresses
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut lines = Vec::new();
+        let mut memory1 = HashMap::new();
+        let mut memory2 = HashMap::new();
+        let (mut ones, mut zeros, mut floating) = (0u64, 0u64, 0u64);
+        for idx in 0..size {
+            if idx == 0 || rng.range(0, 5) == 0 {
+                let mut bits = vec!['0'; MEMORY_BITS - 9];
+                bits.extend((0..9).map(|_| 'X'));
+                for bit in bits.iter_mut().filter(|bit| **bit == '0') {
+                    if rng.bool() {
+                        *bit = '1';
+                    }
+                }
+                rng.shuffle(&mut bits);
+                // Lose some of the floating bits, so not every write has all 512 addresses
+                for _ in 0..rng.range(0, 4) {
+                    let bit = rng.range(0, MEMORY_BITS as u64) as usize;
+                    if bits[bit] == 'X' {
+                        bits[bit] = '0';
+                    }
+                }
+
+                let of = |letter| {
+                    bits.iter()
+                        .rev()
+                        .enumerate()
+                        .filter(|(_, bit)| **bit == letter)
+                        .fold(0, |mask, (idx, _)| mask | 1 << idx)
+                };
+                ones = of('1');
+                zeros = of('0');
+                floating = of('X');
+                lines.push(format!("mask = {}", bits.iter().collect::<String>()));
+            } else {
+                let address = rng.range(0, 1 << 16);
+                let value = rng.range(0, 1 << 30);
+                memory1.insert(address, (value | ones) & !zeros);
+                // Every subset of the floating bits, from all of them down to none
+                let mut subset = floating;
+                loop {
+                    memory2.insert((address | ones) & !floating | subset, value);
+                    if subset == 0 {
+                        break;
+                    }
+                    subset = (subset - 1) & floating;
+                }
+                lines.push(format!("mem[{}] = {}", address, value));
+            }
+        }
+        Generated::new(generate::lines(lines))
+            .with_part1(memory1.values().sum::<u64>())
+            .with_part2(memory2.values().sum::<u64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-14.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-14.txt");
+    const SIMPLE_STAR2_INPUT: &str = include_str!("./../../inputs/simple/day-14-star2.txt");
+
+    #[test]
+    fn simple_star1() {
+        let instructions = parse_input(SIMPLE_INPUT, MEMORY_BITS).unwrap();
+        assert_eq!(
+            star1(&instructions, MEMORY_BITS),
+            answers::expected(14, "simple", 1)
+        );
+    }
+
+    #[test]
+    fn full_star1() {
+        let instructions = parse_input(INPUT, MEMORY_BITS).unwrap();
+        assert_eq!(
+            star1(&instructions, MEMORY_BITS),
+            answers::expected(14, "full", 1)
+        );
+    }
+
+    #[test]
+    fn simple_star2() {
+        let instructions = parse_input(SIMPLE_STAR2_INPUT, MEMORY_BITS).unwrap();
+        assert_eq!(
+            star2(&instructions, MEMORY_BITS),
+            answers::expected(14, "simple-star2", 2)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        let instructions = parse_input(INPUT, MEMORY_BITS).unwrap();
+        assert_eq!(
+            star2(&instructions, MEMORY_BITS),
+            answers::expected(14, "full", 2)
+        );
+    }
+
+    #[test]
+    fn other_memory_sizes() {
+        let input = "mask = X1\nmem[8] = 6\nmask = 0X\nmem[1] = 3\n";
+        let instructions = parse_input(input, 2).unwrap();
+        // 6 with the low bit set is 3 in two bits, the second mask clears the high bit of 3
+        assert_eq!(star1(&instructions, 2), 3 + 1);
+        assert!(parse_input(input, 3).is_err());
+
+        let input = format!("mask = 1{}\nmem[0] = 1\n", "X".repeat(63));
+        let instructions = parse_input(&input, 64).unwrap();
+        assert_eq!(star1(&instructions, 64), 1 << 63 | 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many floating bits to write every address!")]
+    fn every_bit_floating() {
+        let input = format!("mask = {}\nmem[0] = 1\n", "X".repeat(64));
+        let instructions = parse_input(&input, 64).unwrap();
+        star2(&instructions, 64);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day14>(10, &[1, 20, 580]);
+    }
+}
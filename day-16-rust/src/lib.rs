@@ -0,0 +1,329 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::graph;
+use aoc_common::input;
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use parse_display::{Display, FromStr};
+
+/// Parses the rules for the ticket fields, your ticket and the nearby tickets, separated by
+/// blank lines.
+pub fn parse_input(input: &str) -> Result<(Vec<Rule>, Ticket, Vec<Ticket>), ParseError> {
+    let trimmed = input.trim();
+    let end = &trimmed[trimmed.len()..];
+    let mut parts = input::paragraphs(input);
+    let mut next_part = |expected: &str| {
+        parts
+            .next()
+            .ok_or_else(|| ParseError::at(input, end, expected))
+    };
+
+    let rules = next_part("the ticket rules")?
+        .lines()
+        .map(|line| parse_at(input, line.trim(), "a rule like \"class: 1-3 or 5-7\""))
+        .collect::<Result<Vec<Rule>, _>>()?;
+
+    let my_numbers = next_part("your ticket")?
+        .lines()
+        .nth(1)
+        .ok_or_else(|| ParseError::at(input, end, "your ticket"))?;
+    let mine = Ticket::new(input, my_numbers, rules.len())?;
+
+    let nearby = next_part("nearby tickets")?
+        .lines()
+        .skip(1)
+        .map(|line| Ticket::new(input, line, rules.len()))
+        .collect::<Result<Vec<Ticket>, _>>()?;
+
+    Ok((rules, mine, nearby))
+}
+
+/// Sum of the values on nearby tickets that fit none of the rules.
+pub fn star1(rules: &[Rule], nearby: &[Ticket]) -> u64 {
+    let mut invalid_sum = 0;
+    for ticket in nearby {
+        invalid_sum += ticket.find_invalid_value(rules).map_or(0, |val| *val) as u64;
+    }
+    invalid_sum
+}
+
+/// Product of the values in the fields starting with "departure" on your ticket, once the
+/// fields are matched with the columns. Panics if there isn't exactly one way to match them.
+pub fn star2(rules: &[Rule], mine: &Ticket, nearby: &[Ticket]) -> usize {
+    let mut valid_tickets: Vec<Ticket> = get_valid_tickets(rules, nearby);
+    valid_tickets.push(mine.clone());
+
+    // Columns are matched with the rules that fit every value in them
+    let candidates = valid_rules(rules, &valid_tickets);
+    let matching = graph::max_matching(&candidates, rules.len());
+    if !matching.is_perfect() {
+        panic!("No order of the fields fits all tickets!");
+    }
+    if !matching.is_unique(&candidates) {
+        panic!("Multiple variants possible!");
+    }
+
+    matching
+        .left
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| rules[rule.unwrap()].field.starts_with("departure"))
+        .map(|(idx, _)| mine.values[idx] as usize)
+        .product()
+}
+
+// Indices of the rules every ticket's value fits, for every column
+fn valid_rules(rules: &[Rule], tickets: &[Ticket]) -> Vec<Vec<usize>> {
+    (0..rules.len())
+        .map(|col_idx| {
+            (0..rules.len())
+                .filter(|&rule| {
+                    tickets
+                        .iter()
+                        .all(|ticket| rules[rule].value_valid(ticket.values[col_idx]))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn get_valid_tickets(rules: &[Rule], tickets: &[Ticket]) -> Vec<Ticket> {
+    tickets
+        .iter()
+        .filter(|ticket| ticket.is_valid(rules))
+        .cloned()
+        .collect()
+}
+
+/// A ticket field with the two ranges its values can be in, like "class: 1-3 or 5-7".
+#[derive(Clone, Debug, Display, FromStr, Eq, PartialEq)]
+#[display("{field}: {min1}-{max1} or {min2}-{max2}")]
+pub struct Rule {
+    field: String,
+    min1: u32,
+    max1: u32,
+    min2: u32,
+    max2: u32,
+}
+
+impl Rule {
+    fn value_valid(&self, value: u32) -> bool {
+        (self.min1 <= value && value <= self.max1) || (self.min2 <= value && value <= self.max2)
+    }
+}
+
+/// The values on a ticket, in the order of its unknown fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ticket {
+    values: Vec<u32>,
+}
+
+impl Ticket {
+    // Every ticket needs a value for each rule, the columns are matched with them
+    fn new(input: &str, line: &str, rule_count: usize) -> Result<Ticket, ParseError> {
+        let line = line.trim();
+        let values = line
+            .split(',')
+            .map(|number| parse_at(input, number, "a ticket value"))
+            .collect::<Result<Vec<u32>, _>>()?;
+        if values.len() != rule_count {
+            return Err(ParseError::at(
+                input,
+                line,
+                format!("a ticket with {} values, one per rule", rule_count),
+            ));
+        }
+        Ok(Ticket { values })
+    }
+
+    fn find_invalid_value(&self, rules: &[Rule]) -> Option<&u32> {
+        self.values
+            .iter()
+            .find(|value| !rules.iter().any(|rule| rule.value_valid(**value)))
+    }
+
+    fn is_valid(&self, rules: &[Rule]) -> bool {
+        self.find_invalid_value(rules).is_none()
+    }
+}
+
+/// Ticket Translation, working out which field of the tickets is which.
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input<'a> = (Vec<Rule>, Ticket, Vec<Ticket>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        let (rules, _, nearby) = input;
+        star1(rules, nearby).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        let (rules, mine, nearby) = input;
+        Some(star2(rules, mine, nearby).into())
+    }
+}
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+impl Generator for Day16 {
+    const SIZE: &'static str = "nearby tickets";
+    const DEFAULT_SIZE: usize = 240;
+
+    // The k-th field gets values from (50k, 50k + 50) and its rule accepts everything from
+    // 50k + 1 up to 1000. Then the column of the first field fits only its own rule, the next
+    // column two rules and so on, so there's a single order to find. Invalid tickets have
+    // exactly one value above 1000.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let count = FIELDS.len() as u64;
+        let mut names = FIELDS.to_vec();
+        rng.shuffle(&mut names);
+        let mut columns: Vec<usize> = (0..FIELDS.len()).collect();
+        rng.shuffle(&mut columns);
+
+        let mut rules: Vec<String> = (0..count)
+            .map(|field| {
+                let split = 50 * field + rng.range(2, 50);
+                format!(
+                    "{}: {}-{} or {}-{}",
+                    names[field as usize],
+                    50 * field + 1,
+                    split - 1,
+                    split,
+                    50 * count
+                )
+            })
+            .collect();
+        rng.shuffle(&mut rules);
+
+        let ticket = |rng: &mut Rng| {
+            let mut values = vec![0; FIELDS.len()];
+            for (field, &column) in columns.iter().enumerate() {
+                values[column] = 50 * field as u64 + rng.range(1, 50);
+            }
+            values
+        };
+        let line = |values: &[u64]| {
+            let values: Vec<String> = values.iter().map(u64::to_string).collect();
+            values.join(",")
+        };
+
+        let mine = ticket(rng);
+        let departures: u64 = (0..FIELDS.len())
+            .filter(|&field| names[field].starts_with("departure"))
+            .map(|field| mine[columns[field]])
+            .product();
+        let mut invalid_sum = 0;
+        let nearby: Vec<String> = (0..size)
+            .map(|_| {
+                let mut values = ticket(rng);
+                if rng.range(0, 4) == 0 {
+                    let invalid = rng.range(50 * count + 1, 50 * count + 100);
+                    invalid_sum += invalid;
+                    values[rng.range(0, count) as usize] = invalid;
+                }
+                line(&values)
+            })
+            .collect();
+
+        let input = format!(
+            "{}\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            generate::lines(rules),
+            line(&mine),
+            generate::lines(nearby)
+        );
+        Generated::new(input)
+            .with_part1(invalid_sum)
+            .with_part2(departures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-16.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-16.txt");
+
+    #[test]
+    fn simple_star1() {
+        let (rules, _, nearby) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&rules, &nearby), answers::expected(16, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let (rules, _, nearby) = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&rules, &nearby), answers::expected(16, "full", 1));
+    }
+
+    #[test]
+    fn full_star2() {
+        let (rules, mine, nearby) = parse_input(INPUT).unwrap();
+        assert_eq!(
+            star2(&rules, &mine, &nearby),
+            answers::expected(16, "full", 2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Multiple variants possible!")]
+    fn ambiguous_fields() {
+        let (rules, mine, nearby) = parse_input(
+            "a: 1-5 or 7-9\nb: 1-5 or 7-9\n\nyour ticket:\n1,2\n\nnearby tickets:\n3,4",
+        )
+        .unwrap();
+        star2(&rules, &mine, &nearby);
+    }
+
+    #[test]
+    fn ticket_without_every_value() {
+        let err = parse_input(
+            "a: 1-5 or 7-9\nb: 1-5 or 7-9\nc: 1-5 or 7-9\n\nyour ticket:\n1,2,3\n\nnearby tickets:\n3,9",
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (9, 1, "3,9"));
+    }
+
+    #[test]
+    fn get_valid_tickets_works() {
+        let (rules, _, nearby) = parse_input(SIMPLE_INPUT).unwrap();
+        let valid = vec![Ticket {
+            values: vec![7, 3, 47],
+        }];
+        assert_eq!(get_valid_tickets(&rules, &nearby), valid);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day16>(20, &[1, 10, 240]);
+    }
+}
@@ -0,0 +1,344 @@
+use aoc_common::frames::Frames;
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::geometry::PointN;
+use aoc_common::grid::Grid2D;
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::simulation::{self, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Cycles of the boot process.
+pub const CYCLES: usize = 6;
+
+/// Active cubes in three dimensions once the grid ran its cycles.
+pub fn star1(grid: &mut Grid3D) -> usize {
+    grid.boot_cycle();
+    grid.count_active()
+}
+
+/// Active cubes in four dimensions once the grid ran its cycles.
+pub fn star2(grid: &mut Grid4D) -> usize {
+    grid.boot_cycle();
+    grid.count_active()
+}
+
+/// Parses the starting slice, true for active cubes '#'. Both grids are built from it.
+pub fn parse_input(input: &str) -> Result<Grid2D<bool>, ParseError> {
+    Grid2D::parse(input, input, "'.' or '#'", |ch| match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+// Names of the axes in the labels of the layers
+const AXES: [&str; 4] = ["x", "y", "z", "w"];
+
+/// Conway cubes in N dimensions, with room for the cubes to grow by one in every direction each
+/// cycle. Coordinates start at 0 on the far side of that room.
+#[derive(Clone)]
+pub struct Cubes<const N: usize> {
+    cycles: usize,
+    active_grid: Vec<bool>,
+    neighbor_count_grid: Vec<u8>,
+    active_list: VecDeque<PointN<N>>,
+    // How far apart neighbours along each axis are in the grids
+    strides: [usize; N],
+    neighbor_offsets: Vec<PointN<N>>,
+    current_min: PointN<N>,
+    current_max: PointN<N>,
+}
+
+/// The pocket dimension of the first star.
+pub type Grid3D = Cubes<3>;
+/// The pocket dimension of the second star.
+pub type Grid4D = Cubes<4>;
+
+impl<const N: usize> Cubes<N> {
+    /// Cubes that can run for `cycles` cycles, with the slice in the first two dimensions.
+    pub fn new(slice: &Grid2D<bool>, cycles: usize) -> Cubes<N> {
+        let mut max_dimensions = [1 + 2 * cycles; N];
+        max_dimensions[0] = slice.width() + 2 * cycles;
+        max_dimensions[1] = slice.height() + 2 * cycles;
+
+        let mut strides = [1; N];
+        for axis in 1..N {
+            strides[axis] = strides[axis - 1] * max_dimensions[axis - 1];
+        }
+        let max_len = strides[N - 1] * max_dimensions[N - 1];
+
+        let current_min = PointN::splat(cycles as i64);
+        let mut current_max = current_min + PointN::splat(1);
+        current_max.0[0] += slice.width() as i64 - 1;
+        current_max.0[1] += slice.height() as i64 - 1;
+
+        let mut cubes = Cubes {
+            cycles,
+            active_grid: vec![false; max_len],
+            neighbor_count_grid: vec![0; max_len],
+            active_list: VecDeque::new(),
+            strides,
+            neighbor_offsets: PointN::ORIGIN.neighbors().collect(),
+            current_min,
+            current_max,
+        };
+        for ((col_idx, row_idx), &active) in slice.enumerate() {
+            let mut coords = current_min;
+            coords.0[0] += col_idx as i64;
+            coords.0[1] += row_idx as i64;
+            let idx = cubes.get_idx(&coords);
+            cubes.active_grid[idx] = active;
+            if active {
+                cubes.active_list.push_back(coords);
+            }
+        }
+        cubes
+    }
+
+    fn get_idx(&self, coords: &PointN<N>) -> usize {
+        coords
+            .0
+            .iter()
+            .zip(self.strides.iter())
+            .map(|(coord, stride)| *coord as usize * stride)
+            .sum()
+    }
+
+    fn mark_neighbors(&mut self) {
+        while let Some(active_cube) = self.active_list.pop_front() {
+            for offset in self.neighbor_offsets.iter() {
+                let idx = self.get_idx(&(active_cube + *offset));
+                self.neighbor_count_grid[idx] += 1;
+            }
+        }
+    }
+
+    fn update_active(&mut self) {
+        for coords in PointN::points_between(self.current_min, self.current_max) {
+            let idx = self.get_idx(&coords);
+            let neighbor_count = self.neighbor_count_grid[idx];
+            let is_active = self.active_grid[idx];
+            self.neighbor_count_grid[idx] = 0;
+            if neighbor_count == 3 || (is_active && neighbor_count == 2) {
+                // Becomes / stays active
+                self.active_grid[idx] = true;
+                self.active_list.push_back(coords);
+            } else {
+                // Becomes / stays inactive
+                self.active_grid[idx] = false;
+            }
+        }
+    }
+
+    fn cycle(&mut self) {
+        self.mark_neighbors();
+
+        // Expand for one iteration
+        self.current_min -= PointN::splat(1);
+        self.current_max += PointN::splat(1);
+
+        // Update the actives
+        self.update_active();
+    }
+
+    fn boot_cycle(&mut self) {
+        simulation::run(self, self.cycles);
+    }
+
+    fn count_active(&self) -> usize {
+        self.active_list.len()
+    }
+}
+
+// The layers the cubes have spread to, the first axes beyond y change fastest
+impl<const N: usize> fmt::Display for Cubes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = (self.current_min.0, self.current_max.0);
+        for coords in PointN::points_between(self.current_min, self.current_max) {
+            let [x, y] = [coords.0[0], coords.0[1]];
+            if x == min[0] && y == min[1] {
+                let labels: Vec<String> = (2..N)
+                    .map(|axis| {
+                        let coord = coords.0[axis] - self.cycles as i64;
+                        format!("{}={}", AXES[axis], coord)
+                    })
+                    .collect();
+                writeln!(f, "{}", labels.join(", "))?;
+            }
+            let idx = self.get_idx(&coords);
+            write!(f, "{}", if self.active_grid[idx] { '#' } else { '.' })?;
+            if x == max[0] - 1 {
+                writeln!(f)?;
+                if y == max[1] - 1 {
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// The grid only has room for the cycles it was made for
+impl<const N: usize> Simulation for Cubes<N> {
+    type State = Vec<PointN<N>>;
+
+    fn step(&mut self) {
+        self.cycle();
+    }
+
+    fn state(&self) -> Vec<PointN<N>> {
+        let mut active: Vec<PointN<N>> = self.active_list.iter().copied().collect();
+        active.sort_unstable();
+        active
+    }
+
+    fn finished(&self) -> bool {
+        self.current_min.0[N - 1] == 0
+    }
+}
+
+/// Conway Cubes, booting the experimental energy source in three and four dimensions.
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    type Input<'a> = (Grid3D, Grid4D);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day17::parse_with(input, &Params::defaults(Day17::PARAMS))
+    }
+
+    // The grids are sized for all cycles up front, so they can't get much larger
+    const PARAMS: &'static [Param] = &[Param {
+        name: "cycles",
+        default: "6",
+        description: "Boot cycles to run",
+        check: |value| params::between(value, 0usize, 30),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        let slice = parse_input(input)?;
+        let cycles = params.get("cycles");
+        Ok((Grid3D::new(&slice, cycles), Grid4D::new(&slice, cycles)))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(&mut input.0.clone()).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&mut input.1.clone()).into())
+    }
+
+    // The grids have no room for more cycles than they were made for
+    fn frames(input: &Self::Input<'_>, part: u32, steps: Option<usize>) -> Option<Frames> {
+        let (grid3d, grid4d) = input;
+        let frames = if part == 1 {
+            Frames::record(
+                &mut grid3d.clone(),
+                steps.unwrap_or(grid3d.cycles),
+                Grid3D::to_string,
+            )
+        } else {
+            Frames::record(
+                &mut grid4d.clone(),
+                steps.unwrap_or(grid4d.cycles),
+                Grid4D::to_string,
+            )
+        };
+        Some(frames)
+    }
+}
+
+impl Generator for Day17 {
+    const SIZE: &'static str = "slice width and height";
+    const DEFAULT_SIZE: usize = 8;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let cells = (0..size * size)
+            .map(|_| if rng.bool() { '#' } else { '.' })
+            .collect();
+        Generated::new(Grid2D::from_vec(size, cells).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-17.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-17.txt");
+
+    #[test]
+    fn simple_star1() {
+        let mut grid = Grid3D::new(&parse_input(SIMPLE_INPUT).unwrap(), CYCLES);
+        assert_eq!(star1(&mut grid), answers::expected(17, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let mut grid = Grid3D::new(&parse_input(INPUT).unwrap(), CYCLES);
+        assert_eq!(star1(&mut grid), answers::expected(17, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let mut grid = Grid4D::new(&parse_input(SIMPLE_INPUT).unwrap(), CYCLES);
+        assert_eq!(star2(&mut grid), answers::expected(17, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let mut grid = Grid4D::new(&parse_input(INPUT).unwrap(), CYCLES);
+        assert_eq!(star2(&mut grid), answers::expected(17, "full", 2));
+    }
+
+    #[test]
+    fn other_cycles() {
+        let slice = parse_input(SIMPLE_INPUT).unwrap();
+        // The counts after the first cycles of the example
+        assert_eq!(star1(&mut Grid3D::new(&slice, 0)), 5);
+        assert_eq!(star1(&mut Grid3D::new(&slice, 1)), 11);
+        assert_eq!(star1(&mut Grid3D::new(&slice, 3)), 38);
+        assert_eq!(star2(&mut Grid4D::new(&slice, 1)), 29);
+        assert!(star1(&mut Grid3D::new(&slice, 10)) > 112);
+    }
+
+    #[test]
+    fn cycles_stop_at_the_edge() {
+        let slice = parse_input(SIMPLE_INPUT).unwrap();
+        let mut grid = Grid3D::new(&slice, 2);
+        assert_eq!(simulation::run(&mut grid, 5), 2);
+        assert!(grid.finished());
+        let mut grid = Grid4D::new(&slice, 0);
+        assert_eq!(simulation::run(&mut grid, 1), 0);
+        assert_eq!(grid.state().len(), 5);
+    }
+
+    #[test]
+    fn frames_of_every_cycle() {
+        let input = Day17::parse(SIMPLE_INPUT).unwrap();
+        let frames = Day17::frames(&input, 1, None).unwrap();
+        assert_eq!(frames.len(), CYCLES + 1);
+        assert_eq!(frames.iter().next().unwrap(), "z=0\n.#.\n..#\n###\n\n");
+        // The example after one cycle, the layers grow by one in every direction
+        assert!(frames
+            .iter()
+            .nth(1)
+            .unwrap()
+            .starts_with("z=-1\n.....\n.....\n.#...\n...#.\n..#..\n\nz=0\n"));
+        let frames = Day17::frames(&input, 2, Some(1)).unwrap();
+        assert!(frames.iter().nth(1).unwrap().contains("z=-1, w=-1\n"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day17>(3, &[1, 8, 20]);
+    }
+}
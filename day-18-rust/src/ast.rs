@@ -1,15 +1,20 @@
+// Expression trees both parsers build, evaluated from the leaves up
+
+/// Operator of a [`BinaryExpr`].
 #[derive(Clone, Copy, Debug)]
 pub enum Op {
     Add,
     Multiply,
 }
 
+/// A node of an expression tree, the leaves are single digits.
 pub enum Expr {
     Binary(Box<BinaryExpr>),
     Digit(u32),
 }
 
 impl Expr {
+    /// Value of the whole tree below this node.
     pub fn evaluate(&self) -> u64 {
         match self {
             Expr::Binary(binary) => binary.evaluate(),
@@ -18,6 +23,7 @@ impl Expr {
     }
 }
 
+/// Two expressions combined with an operator.
 pub struct BinaryExpr {
     pub left: Expr,
     pub op: Op,
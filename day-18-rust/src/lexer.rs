@@ -1,8 +1,12 @@
+use aoc_common::parse::ParseError;
+
+/// An operator as written in the expression.
 #[derive(Debug, Clone, Copy)]
 pub enum Op {
     Plus,
     Star,
 }
+/// A single character of an expression, spaces are skipped.
 #[derive(Debug, Clone, Copy)]
 pub enum Token {
     Digit(u32),
@@ -11,19 +15,45 @@ pub enum Token {
     RightPar,
 }
 
-pub fn lex_line(line: &str) -> Vec<Token> {
-    line.trim()
-        .chars()
-        .filter(|c| *c != ' ')
-        .map(|c| match c {
+/// Lexes a line (a slice of the whole input) and makes sure the tokens form a valid expression,
+/// so the parsers can rely on it.
+///
+/// The error points at the first token that doesn't fit, or at the end of the line if the
+/// expression isn't finished.
+pub fn lex_line(input: &str, line: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut expect_operand = true;
+    let mut depth = 0;
+    for (idx, c) in line.char_indices().filter(|(_, c)| *c != ' ') {
+        let text = &line[idx..idx + c.len_utf8()];
+        let token = match c {
             '+' => Token::Operator(Op::Plus),
             '*' => Token::Operator(Op::Star),
             '(' => Token::LeftPar,
             ')' => Token::RightPar,
-            d if d.is_digit(10) => Token::Digit(d.to_digit(10).unwrap()),
-            _ => panic!("Unknown character encountered!"),
-        })
-        .collect()
+            d if d.is_ascii_digit() => Token::Digit(d.to_digit(10).unwrap()),
+            _ => return Err(ParseError::at(input, text, "a digit, '+', '*', '(' or ')'")),
+        };
+
+        match (expect_operand, token) {
+            (true, Token::Digit(_)) => expect_operand = false,
+            (true, Token::LeftPar) => depth += 1,
+            (false, Token::Operator(_)) => expect_operand = true,
+            (false, Token::RightPar) if depth > 0 => depth -= 1,
+            (true, _) => return Err(ParseError::at(input, text, "a digit or '('")),
+            (false, _) => return Err(ParseError::at(input, text, "an operator or ')'")),
+        }
+        tokens.push(token);
+    }
+
+    let end = &line[line.len()..];
+    if expect_operand {
+        return Err(ParseError::at(input, end, "a digit or '('"));
+    }
+    if depth > 0 {
+        return Err(ParseError::at(input, end, "')'"));
+    }
+    Ok(tokens)
 }
 
 /*
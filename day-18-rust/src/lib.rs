@@ -0,0 +1,180 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+// Lines are lexed into tokens first, either parser turns those into an expression tree
+/// Expression trees and their evaluation.
+pub mod ast;
+/// Tokens of an expression and the lexer that checks them.
+pub mod lexer;
+/// Parser for the first star, `+` and `*` evaluated from left to right.
+pub mod parser;
+/// Parser for the second star, `+` before `*`.
+pub mod parser_star2;
+
+/// Lexes every line of the homework into the tokens of its expression, see
+/// [`lexer::lex_line`].
+pub fn parse_input(input: &str) -> Result<Vec<Vec<lexer::Token>>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| lexer::lex_line(input, line.trim()))
+        .collect()
+}
+
+/// Sum of the expressions with `+` and `*` on the same precedence.
+pub fn star1(tokens: &[Vec<lexer::Token>]) -> u64 {
+    tokens
+        .iter()
+        .map(|tokens| {
+            let mut parser = parser::Parser::new(tokens);
+            let expr = parser.parse();
+            expr.evaluate()
+        })
+        .sum()
+}
+
+/// Sum of the expressions with `+` evaluated before `*`.
+pub fn star2(tokens: &[Vec<lexer::Token>]) -> u64 {
+    tokens
+        .iter()
+        .map(|tokens| {
+            let mut parser = parser_star2::Parser::new(tokens);
+            let expr = parser.parse();
+            expr.evaluate()
+        })
+        .sum()
+}
+
+/// Operation Order, doing the math homework with its odd precedence rules.
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input<'a> = Vec<Vec<lexer::Token>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+
+impl Generator for Day18 {
+    const SIZE: &'static str = "expressions";
+    const DEFAULT_SIZE: usize = 376;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // Every expression gets an equal share of what an answer can hold, the rare ones above
+        // it are thrown away
+        let limit = i64::MAX as u64 / size as u64;
+        let mut lines = Vec::new();
+        let (mut sum1, mut sum2) = (0, 0);
+        while lines.len() < size {
+            if let Some((line, value1, value2)) = expression(rng, 0) {
+                if value1 <= limit && value2 <= limit {
+                    lines.push(line);
+                    sum1 += value1;
+                    sum2 += value2;
+                }
+            }
+        }
+        Generated::new(generate::lines(lines))
+            .with_part1(sum1)
+            .with_part2(sum2)
+    }
+}
+
+// A random expression with its value under both sets of rules, None if one overflows
+fn expression(rng: &mut Rng, depth: usize) -> Option<(String, u64, u64)> {
+    let mut text = String::new();
+    let (mut value1, mut product2, mut sum2) = (0u64, 1u64, 0u64);
+    for idx in 0..rng.range(2, 7) {
+        let (operand, operand1, operand2) = if depth < 2 && rng.range(0, 4) == 0 {
+            let (inner, inner1, inner2) = expression(rng, depth + 1)?;
+            (format!("({})", inner), inner1, inner2)
+        } else {
+            let number = rng.range(1, 10);
+            (number.to_string(), number, number)
+        };
+
+        // Left to right for the first part, additions before multiplications for the second
+        if idx == 0 {
+            value1 = operand1;
+            sum2 = operand2;
+        } else if rng.bool() {
+            text.push_str(" + ");
+            value1 = value1.checked_add(operand1)?;
+            sum2 = sum2.checked_add(operand2)?;
+        } else {
+            text.push_str(" * ");
+            value1 = value1.checked_mul(operand1)?;
+            product2 = product2.checked_mul(sum2)?;
+            sum2 = operand2;
+        }
+        text.push_str(&operand);
+    }
+    Some((text, value1, product2.checked_mul(sum2)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-18.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-18.txt");
+
+    #[test]
+    fn simple_star1() {
+        let tokens = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&tokens), answers::expected(18, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let tokens = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&tokens), answers::expected(18, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let tokens = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&tokens), answers::expected(18, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let tokens = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&tokens), answers::expected(18, "full", 2));
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        let err = parse_input("1 + 2\n2 * (3 + 4").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 11, "')'")
+        );
+
+        let err = parse_input("1 + 2 - 3").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 7, "-"));
+
+        let err = parse_input("(1 + ) * 3").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 6, ")"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day18>(20, &[1, 50, 376]);
+    }
+}
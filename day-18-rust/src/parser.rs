@@ -3,20 +3,23 @@ use crate::lexer;
 
 use lexer::Token;
 
+/// Parses with `+` and `*` on the same precedence, from left to right.
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
 }
 
 impl<'a> Parser<'a> {
+    /// A parser for the tokens of a single line, as checked by [`lexer::lex_line`].
     pub fn new(tokens: &'a Vec<Token>) -> Parser<'a> {
         Parser { tokens, current: 0 }
     }
 
+    /// The expression tree of the tokens.
     pub fn parse(&mut self) -> ast::Expr {
         let parser_tree = self.parse_binary_expr();
-        let ast_tree = Parser::transform_binary(&parser_tree);
-        ast_tree
+
+        Parser::transform_binary(&parser_tree)
     }
 
     fn peek(&self) -> Option<&Token> {
@@ -5,20 +5,23 @@ use lexer::Token;
 
 use parse_tree::*;
 
+/// Parses with `+` binding tighter than `*`.
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
 }
 
 impl<'a> Parser<'a> {
+    /// A parser for the tokens of a single line, as checked by [`lexer::lex_line`].
     pub fn new(tokens: &'a Vec<Token>) -> Parser<'a> {
         Parser { tokens, current: 0 }
     }
 
+    /// The expression tree of the tokens.
     pub fn parse(&mut self) -> ast::Expr {
         let parse_tree = self.parse_mul_binary_expr();
-        let ast_tree = transformer::make_ast(&parse_tree);
-        ast_tree
+
+        transformer::make_ast(&parse_tree)
     }
 
     fn peek(&self) -> Option<&Token> {
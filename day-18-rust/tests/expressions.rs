@@ -0,0 +1,41 @@
+use day_18_rust::ast::{BinaryExpr, Expr, Op};
+use day_18_rust::lexer::{lex_line, Token};
+use day_18_rust::{parser, parser_star2};
+
+// The value with + and * on the same precedence, and with + first
+fn evaluate(line: &str) -> (u64, u64) {
+    let tokens = lex_line(line, line).unwrap();
+    (
+        parser::Parser::new(&tokens).parse().evaluate(),
+        parser_star2::Parser::new(&tokens).parse().evaluate(),
+    )
+}
+
+#[test]
+fn both_precedences() {
+    assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6"), (71, 231));
+    assert_eq!(evaluate("2 * 3 + (4 * 5)"), (26, 46));
+    assert_eq!(
+        evaluate("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        (13632, 23340)
+    );
+}
+
+#[test]
+fn tokens_and_trees() {
+    let tokens = lex_line("(7)", "(7)").unwrap();
+    assert!(matches!(
+        tokens.as_slice(),
+        [Token::LeftPar, Token::Digit(7), Token::RightPar]
+    ));
+    let err = lex_line("1 + * 2", "1 + * 2").unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (5, "a digit or '('"));
+
+    let sum = Expr::Binary(Box::new(BinaryExpr::new(
+        Expr::Digit(2),
+        Op::Add,
+        Expr::Digit(3),
+    )));
+    let product = BinaryExpr::new(sum, Op::Multiply, Expr::Digit(4));
+    assert_eq!(Expr::Binary(Box::new(product)).evaluate(), 20);
+}
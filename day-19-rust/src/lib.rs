@@ -0,0 +1,488 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::input;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution, Strategy};
+
+/// Parses the rules numbered from 0 and the received messages, separated by a blank line.
+pub fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<&str>), ParseError> {
+    let trimmed = input.trim();
+    let mut parts = input::paragraphs(input);
+
+    // Rules are numbered from 0 without gaps, so they can be stored in order
+    let rule_part = parts.next().unwrap_or("");
+    let rule_lines: Vec<&str> = rule_part.lines().collect();
+    if rule_lines.is_empty() {
+        return Err(ParseError::at(
+            input,
+            &trimmed[trimmed.len()..],
+            "the rules",
+        ));
+    }
+    let mut rules: Vec<Option<Rule>> = vec![None; rule_lines.len()];
+    for line in rule_lines {
+        let (idx, rule) = Rule::new(input, line.trim(), rules.len())?;
+        let slot = &mut rules[idx];
+        if slot.is_some() {
+            return Err(ParseError::at(
+                input,
+                line.trim(),
+                "a rule with a new number",
+            ));
+        }
+        *slot = Some(rule);
+    }
+    let rules: Vec<Rule> = rules.into_iter().map(Option::unwrap).collect();
+
+    // The second star turns 8 and 11 into loops of 42 and 31, the first example has none of them
+    if has_loops(&rules) {
+        if let Some(missing) = [31, 42].iter().find(|&&idx| idx >= rules.len()) {
+            let end = &rule_part[rule_part.len()..];
+            let expected = format!("rule {}, which the loops of rules 8 and 11 repeat", missing);
+            return Err(ParseError::at(input, end, expected));
+        }
+    }
+
+    let messages = parts
+        .next()
+        .ok_or_else(|| ParseError::at(input, &trimmed[trimmed.len()..], "a list of messages"))?
+        .lines()
+        .map(|line| line.trim())
+        .collect();
+
+    Ok((rules, messages))
+}
+
+// Whether there are rules 8 and 11 for the second star to replace
+fn has_loops(rules: &[Rule]) -> bool {
+    rules.len() > 11
+}
+
+/// How many messages match rule 0 completely.
+pub fn star1(rules: &Vec<Rule>, messages: &[&str]) -> usize {
+    messages
+        .iter()
+        .filter(|message| {
+            let matches = rules[0].matches(rules, message);
+
+            matches.is_some() && matches.unwrap() == message.len()
+        })
+        .count()
+}
+
+/// How many messages match rule 0 completely, once rules 8 and 11 loop. Panics without rules
+/// 31 and 42, which the loops are made of.
+pub fn star2(rules: &Vec<Rule>, messages: &[&str]) -> usize {
+    let generated_42 = rules[42].generate_matches(rules);
+    let generated_31 = rules[31].generate_matches(rules);
+    let one_rule_8_len = generated_42[0].len();
+    let one_rule_11_len = generated_42[0].len() + generated_31[0].len();
+
+    messages
+        .iter()
+        .filter(|message| {
+            let max_times_11 = (message.len() - one_rule_8_len) / one_rule_11_len;
+            for times_11 in 1..=max_times_11 {
+                let times_8 = (message.len() - times_11 * one_rule_11_len) / one_rule_8_len;
+
+                let mut first_rules = vec![42; times_8 + times_11];
+                let mut second_rules = vec![31; times_11];
+                first_rules.append(&mut second_rules);
+
+                let expanded = ExpandedRule::from(first_rules);
+                let matches = expanded.matches(rules, message);
+
+                let correct = matches.is_some() && matches.unwrap() == message.len();
+                if correct {
+                    return true;
+                }
+            }
+            false
+        })
+        .count()
+}
+
+// Another way to solve the second star, checking if chunks of string are in the generated ones
+fn star2_via_chunks(rules: &Vec<Rule>, messages: &[&str]) -> usize {
+    let generated_42 = rules[42].generate_matches(rules);
+    let generated_31 = rules[31].generate_matches(rules);
+    let one_rule_8_len = generated_42[0].len();
+    let one_rule_11_len = generated_42[0].len() + generated_31[0].len();
+
+    messages
+        .iter()
+        .filter(|message| {
+            let max_times_11 = (message.len() - one_rule_8_len) / one_rule_11_len;
+            for times_11 in 1..=max_times_11 {
+                let times_8 = (message.len() - times_11 * one_rule_11_len) / one_rule_8_len;
+                let mut chunks = Vec::new();
+                for i in 0..(times_8 + 2 * times_11) {
+                    let start = i * one_rule_8_len;
+                    let end = (i + 1) * one_rule_8_len;
+                    chunks.push(message[start..end].to_owned());
+                }
+                let correct = chunks.iter().enumerate().all(|(idx, chunk)| {
+                    if idx < (times_8 + times_11) {
+                        generated_42.contains(chunk)
+                    } else {
+                        generated_31.contains(chunk)
+                    }
+                });
+                if correct {
+                    return true;
+                }
+            }
+            false
+        })
+        .count()
+}
+
+/// A rule matching a single character, a sequence of other rules, or one of two sequences.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Char(char),
+    Expanded(Box<ExpandedRule>),
+    ExpandedOr(Box<ExpandedRule>, Box<ExpandedRule>),
+}
+
+impl Rule {
+    fn new(input: &str, line: &str, rule_count: usize) -> Result<(usize, Rule), ParseError> {
+        let (idx, rule_str) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at(input, line, "a rule like \"0: 4 1 5\""))?;
+        let idx = parse_rule_number(input, idx, rule_count)?;
+
+        let rule_str = rule_str.trim();
+        let rule: Rule = if rule_str.contains('"') {
+            let quoted = rule_str.strip_prefix('"').and_then(|s| s.strip_suffix('"'));
+            let mut chars = quoted.unwrap_or("").chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Rule::Char(c),
+                _ => return Err(ParseError::at(input, rule_str, "a single quoted character")),
+            }
+        } else if let Some((first, second)) = rule_str.split_once('|') {
+            let first = ExpandedRule::new(input, first, rule_count)?;
+            let second = ExpandedRule::new(input, second, rule_count)?;
+            Rule::ExpandedOr(Box::new(first), Box::new(second))
+        } else {
+            let expanded = ExpandedRule::new(input, rule_str, rule_count)?;
+            Rule::Expanded(Box::new(expanded))
+        };
+
+        Ok((idx, rule))
+    }
+
+    // Returns if matches and how many characters it consumes
+    fn matches(&self, rules: &Vec<Rule>, input: &str) -> Option<usize> {
+        match self {
+            Rule::Char(c) => {
+                if input.is_empty() {
+                    return None;
+                }
+
+                if input.chars().next().unwrap() == *c {
+                    Some(1)
+                } else {
+                    None
+                }
+            }
+            Rule::Expanded(expanded) => expanded.matches(rules, input),
+            Rule::ExpandedOr(first, second) => first
+                .matches(rules, input)
+                .or_else(|| second.matches(rules, input)),
+        }
+    }
+
+    fn generate_matches(&self, rules: &Vec<Rule>) -> Vec<String> {
+        match self {
+            Rule::Char(c) => vec![String::from(*c)],
+            Rule::Expanded(expanded) => expanded.generate_matches(rules),
+            Rule::ExpandedOr(first, second) => {
+                let mut first_generated = first.generate_matches(rules);
+                let mut second_generated = second.generate_matches(rules);
+                first_generated.append(&mut second_generated);
+                first_generated
+            }
+        }
+    }
+}
+
+fn parse_rule_number(input: &str, number: &str, rule_count: usize) -> Result<usize, ParseError> {
+    let expected = format!("a rule number below {}", rule_count);
+    match number.trim().parse() {
+        Ok(idx) if idx < rule_count => Ok(idx),
+        _ => Err(ParseError::at(input, number.trim(), expected)),
+    }
+}
+
+/// A sequence of rule numbers that have to match one after another.
+#[derive(Debug, Clone)]
+pub struct ExpandedRule {
+    subrules: Vec<u32>,
+}
+
+impl ExpandedRule {
+    fn new(input: &str, rule_str: &str, rule_count: usize) -> Result<ExpandedRule, ParseError> {
+        let rule_str = rule_str.trim();
+        if rule_str.is_empty() {
+            return Err(ParseError::at(input, rule_str, "a list of rule numbers"));
+        }
+        let subrules = rule_str
+            .split_whitespace()
+            .map(|n| parse_rule_number(input, n, rule_count).map(|n| n as u32))
+            .collect::<Result<Vec<u32>, _>>()?;
+        Ok(ExpandedRule { subrules })
+    }
+
+    fn from(subrules: Vec<u32>) -> ExpandedRule {
+        ExpandedRule { subrules }
+    }
+
+    fn matches(&self, rules: &Vec<Rule>, input: &str) -> Option<usize> {
+        let mut consumed = 0;
+        for subrule_idx in &self.subrules {
+            let rule = &rules[*subrule_idx as usize];
+            match rule.matches(rules, &input[consumed..]) {
+                Some(x) => {
+                    consumed += x;
+                }
+                None => return None,
+            }
+        }
+        Some(consumed)
+    }
+
+    fn generate_matches(&self, rules: &Vec<Rule>) -> Vec<String> {
+        let mut strings = vec!["".to_owned()];
+        for subrule_idx in &self.subrules {
+            let rule = &rules[*subrule_idx as usize];
+            let generated = rule.generate_matches(rules);
+            let mut new_strings = Vec::new();
+            for gen in &generated {
+                for current in &strings {
+                    new_strings.push(format!("{}{}", current, gen));
+                }
+            }
+            strings = new_strings;
+        }
+        strings
+    }
+}
+
+/// Monster Messages, checking the messages from the monsters against their rules.
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+
+    type Input<'a> = (Vec<Rule>, Vec<&'a str>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        let (rules, messages) = input;
+        star1(rules, messages).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        let (rules, messages) = input;
+        if !has_loops(rules) {
+            return None;
+        }
+        Some(star2(rules, messages).into())
+    }
+
+    const DEFAULT_STRATEGY: &'static str = "repeats";
+
+    fn strategies() -> Vec<Strategy<Self>> {
+        vec![Strategy {
+            name: "chunks",
+            slow: false,
+            part1: None,
+            part2: Some(|(rules, messages)| star2_via_chunks(rules, messages).into()),
+        }]
+    }
+}
+// Every level splits the strings of its length in two rules, by the first or last letter and
+// the rule of the rest. Level 1 are the letters themselves, the last level is 42 and 31.
+struct Level {
+    rules: (usize, usize),
+    flip: bool,
+    letter_last: bool,
+}
+
+impl Level {
+    // A random string matching the first rule of the level, or the second one
+    fn chunk(levels: &[Level], rng: &mut Rng, first: bool) -> String {
+        let (level, below) = levels.split_last().unwrap();
+        if below.is_empty() {
+            return if first { "a" } else { "b" }.to_owned();
+        }
+        let a = rng.bool();
+        let mut chunk = Level::chunk(below, rng, (first == a) != level.flip);
+        let letter = if a { 'a' } else { 'b' };
+        if level.letter_last {
+            chunk.push(letter);
+        } else {
+            chunk.insert(0, letter);
+        }
+        chunk
+    }
+
+    fn rule(&self, below: &Level, letters: &Level, first: bool) -> String {
+        let alternative = |letter, rule| {
+            if self.letter_last {
+                format!("{} {}", rule, letter)
+            } else {
+                format!("{} {}", letter, rule)
+            }
+        };
+        let (same, other) = if self.flip {
+            (below.rules.1, below.rules.0)
+        } else {
+            below.rules
+        };
+        let (with_a, with_b) = if first { (same, other) } else { (other, same) };
+        format!(
+            "{}: {} | {}",
+            if first { self.rules.0 } else { self.rules.1 },
+            alternative(letters.rules.0, with_a),
+            alternative(letters.rules.1, with_b)
+        )
+    }
+}
+
+impl Generator for Day19 {
+    const SIZE: &'static str = "messages";
+    const DEFAULT_SIZE: usize = 400;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let chunk_len = rng.range(3, 9) as usize;
+        let reserved = [0, 8, 11, 31, 42];
+        let count = 43 + rng.range(0, 2 * chunk_len as u64 + 20) as usize;
+        let mut free: Vec<usize> = (0..count).filter(|id| !reserved.contains(id)).collect();
+        rng.shuffle(&mut free);
+
+        let mut levels = Vec::new();
+        for level in 1..=chunk_len {
+            let rules = match level {
+                _ if level == chunk_len => (42, 31),
+                _ => (free.pop().unwrap(), free.pop().unwrap()),
+            };
+            let (flip, letter_last) = (rng.bool(), rng.bool());
+            levels.push(Level {
+                rules,
+                flip,
+                letter_last,
+            });
+        }
+
+        let letters = &levels[0];
+        let mut rules = vec![
+            "0: 8 11".to_owned(),
+            "8: 42".to_owned(),
+            "11: 42 31".to_owned(),
+            format!("{}: \"a\"", letters.rules.0),
+            format!("{}: \"b\"", letters.rules.1),
+        ];
+        for pair in levels.windows(2) {
+            rules.push(pair[1].rule(&pair[0], letters, true));
+            rules.push(pair[1].rule(&pair[0], letters, false));
+        }
+        // Numbers have no gaps, so the rest are rules nothing refers to
+        for id in free {
+            rules.push(format!("{}: {} {}", id, letters.rules.1, letters.rules.0));
+        }
+        rng.shuffle(&mut rules);
+
+        let (mut matching1, mut matching2) = (0, 0);
+        let messages: Vec<String> = (0..size)
+            .map(|_| {
+                // Chunks of 42 followed by chunks of 31, or anything at all
+                let chunks = rng.range(1, 13) as usize;
+                let firsts: Vec<bool> = if rng.bool() {
+                    let seconds = rng.range(0, chunks as u64 / 2 + 1) as usize;
+                    (0..chunks).map(|idx| idx < chunks - seconds).collect()
+                } else {
+                    (0..chunks).map(|_| rng.bool()).collect()
+                };
+                let seconds = firsts.iter().skip_while(|first| **first).count();
+                let ordered = firsts[chunks - seconds..].iter().all(|first| !first);
+                if ordered && firsts == [true, true, false] {
+                    matching1 += 1;
+                }
+                if ordered && seconds >= 1 && chunks - seconds > seconds {
+                    matching2 += 1;
+                }
+                firsts
+                    .iter()
+                    .map(|first| Level::chunk(&levels, rng, *first))
+                    .collect()
+            })
+            .collect();
+
+        let input = format!("{}\n{}", generate::lines(rules), generate::lines(messages));
+        Generated::new(input)
+            .with_part1(matching1)
+            .with_part2(matching2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-19.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-19.txt");
+    const SIMPLE_INPUT_STAR2: &str = include_str!("./../../inputs/simple/day-19-star2.txt");
+
+    #[test]
+    fn simple_star1() {
+        let (rules, messages) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&rules, &messages), answers::expected(19, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let (rules, messages) = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&rules, &messages), answers::expected(19, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let (rules, messages) = parse_input(SIMPLE_INPUT_STAR2).unwrap();
+        assert_eq!(
+            star2(&rules, &messages),
+            answers::expected(19, "simple-star2", 2)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        let (rules, messages) = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&rules, &messages), answers::expected(19, "full", 2));
+    }
+
+    #[test]
+    fn missing_loop_rules() {
+        let rules: Vec<String> = (0..40).map(|idx| format!("{}: \"a\"", idx)).collect();
+        let text = format!("{}\n\naaa\n", rules.join("\n"));
+        let err = parse_input(&text).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (40, 8, "rule 42, which the loops of rules 8 and 11 repeat")
+        );
+
+        let err = parse_input("").unwrap_err();
+        assert_eq!(err.expected, "the rules");
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day19>(20, &[1, 50, 400]);
+    }
+}
@@ -0,0 +1,827 @@
+use aoc_common::frames::Frames;
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::geometry::{Dir4, Symmetry};
+use aoc_common::grid::Grid2D;
+use aoc_common::input;
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+const MONSTER: &str = include_str!("./../../inputs/day-20-monster.txt");
+
+/// Parses the tiles, each a header like "Tile 2311:" followed by a square of '#' and '.',
+/// separated by blank lines.
+pub fn parse_input(input: &str) -> Result<Vec<Tile>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "a header like \"Tile 2311:\"",
+        ));
+    }
+    input::paragraphs(input)
+        .map(|tile| Tile::new(input, tile))
+        .collect()
+}
+
+/// Product of the IDs of the four corner tiles.
+pub fn star1(tiles: &mut [Tile]) -> u64 {
+    let mut matcher = TileMatcher::new(tiles);
+    matcher.find_matches();
+    let tiles_per_matches = matcher.get_tiles_per_matches();
+    tiles_per_matches[2]
+        .iter()
+        .map(|idx| tiles[*idx].id as u64)
+        .product()
+}
+
+/// Number of '#' in the assembled picture that aren't part of a sea monster. The tiles are
+/// turned and flipped in place on the way.
+pub fn star2(tiles: &mut [Tile]) -> usize {
+    let mut matcher = TileMatcher::new(tiles);
+    matcher.find_matches();
+    let mut picture = matcher.construct_picture();
+    picture.rotate_and_flip_until_monster_found(&mut |_| {});
+    picture.mark_monsters(&mut |_| {});
+    picture.count_roughness()
+}
+
+/// A square piece of the picture, borders included.
+#[derive(Debug, Clone)]
+pub struct Tile {
+    id: u32,
+    grid: Grid2D<char>,
+    // Indexed by the side they are on, clockwise from the top
+    borders: [Vec<char>; 4],
+    borders_flipped: [Vec<char>; 4],
+}
+
+impl Tile {
+    // Tiles are square, with a header like "Tile 2311:"
+    fn new(input: &str, tile_str: &str) -> Result<Tile, ParseError> {
+        let tile_str = tile_str.trim();
+        let header = tile_str.lines().next().unwrap_or("").trim();
+        let id_str = header
+            .strip_prefix("Tile ")
+            .and_then(|header| header.strip_suffix(':'))
+            .ok_or_else(|| ParseError::at(input, header, "a header like \"Tile 2311:\""))?;
+        let id = parse_at(input, id_str, "a tile ID")?;
+
+        let body = tile_str.split_once('\n').map_or("", |(_, body)| body);
+        let end = &tile_str[tile_str.len()..];
+        if body.trim().lines().count() < 2 {
+            return Err(ParseError::at(input, end, "at least two rows of the tile"));
+        }
+        let grid = Grid2D::parse(input, body, "'.' or '#'", |ch| match ch {
+            '.' | '#' => Some(ch),
+            _ => None,
+        })?;
+        if grid.width() != grid.height() {
+            let expected = format!("{} rows to make the tile square", grid.width());
+            return Err(ParseError::at(input, end, expected));
+        }
+
+        let mut tile = Tile {
+            id,
+            grid,
+            borders: Default::default(),
+            borders_flipped: Default::default(),
+        };
+        tile.update_borders();
+        Ok(tile)
+    }
+
+    /// The number from the tile's header.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn update_borders(&mut self) {
+        let last = self.grid.width() - 1;
+        let top_border = self.grid.row(0).to_owned();
+        let bottom_border = self.grid.row(last).to_owned();
+        let left_border = self.grid.column(0).copied().collect();
+        let right_border = self.grid.column(last).copied().collect();
+        self.borders = [top_border, right_border, bottom_border, left_border];
+
+        self.borders_flipped = self.borders.clone();
+        for border in self.borders_flipped.iter_mut() {
+            border.reverse();
+        }
+    }
+
+    fn find_match(&self, other: &Tile) -> Option<TileMatch> {
+        let id1 = self.id;
+        let id2 = other.id;
+        for (side1_idx, border) in self.borders.iter().enumerate() {
+            // Check if any of the borders or flipped borders matches
+            let matches = other
+                .borders
+                .iter()
+                .chain(other.borders_flipped.iter())
+                .any(|other| other == border);
+            if matches {
+                return Some(TileMatch::new(id1, id2, side1_idx));
+            }
+        }
+        None
+    }
+
+    fn transform(&mut self, symmetry: Symmetry) {
+        self.grid = self.grid.transform(symmetry);
+        self.update_borders();
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TileMatch {
+    id1: u32,
+    id2: u32,
+    border1: Dir4,
+}
+
+impl TileMatch {
+    fn new(id1: u32, id2: u32, side1_idx: usize) -> TileMatch {
+        TileMatch {
+            id1,
+            id2,
+            border1: Dir4::ALL[side1_idx],
+        }
+    }
+}
+
+/// Finds the tiles that share a border, and puts them together into the picture. Tiles are
+/// turned and flipped in place on the way.
+pub struct TileMatcher<'a> {
+    tiles: &'a mut [Tile],
+    tile_match: Vec<Vec<TileMatch>>,
+    id_idx_map: HashMap<u32, usize>,
+}
+
+impl<'a> TileMatcher<'a> {
+    /// A matcher for the tiles, with no matches found yet.
+    pub fn new(tiles: &mut [Tile]) -> TileMatcher<'_> {
+        let id_idx_map = tiles
+            .iter()
+            .enumerate()
+            .map(|(idx, tile)| (tile.id, idx))
+            .collect();
+        let tile_match = vec![Vec::new(); tiles.len()];
+        TileMatcher {
+            tiles,
+            tile_match,
+            id_idx_map,
+        }
+    }
+
+    /// Finds the neighbours of every tile, which has to be done before anything else.
+    pub fn find_matches(&mut self) {
+        for (idx1, tile1) in self.tiles.iter().enumerate() {
+            for (idx2, tile2) in self.tiles.iter().enumerate() {
+                if idx1 == idx2 {
+                    continue;
+                }
+
+                let matched = tile1.find_match(tile2);
+                if let Some(tile_match) = matched {
+                    self.tile_match[idx1].push(tile_match);
+                    // TODO: Add to tile_match of second idx as well
+                }
+            }
+        }
+    }
+
+    /// Indices of the tiles by how many neighbours they have, the corners have 2.
+    ///
+    /// Panics if the tiles can't form a square.
+    pub fn get_tiles_per_matches(&self) -> Vec<Vec<usize>> {
+        let match_counts: Vec<usize> = self
+            .tile_match
+            .iter()
+            .map(|matches| matches.len())
+            .collect();
+
+        let mut partitioned: Vec<Vec<usize>> = vec![];
+        for match_amount in 0..=4 {
+            partitioned.push(
+                match_counts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, count)| **count == match_amount)
+                    .map(|(idx, _)| idx)
+                    .collect(),
+            );
+        }
+
+        assert_eq!(partitioned[2].len(), 4);
+        assert_eq!(partitioned[3].len() % 4, 0);
+        let side_len = partitioned[2].len() / 2 + partitioned[3].len() / 4;
+        assert_eq!(
+            partitioned[2].len() + partitioned[3].len() + partitioned[4].len(),
+            side_len * side_len
+        );
+
+        partitioned
+    }
+
+    /// Puts the tiles together, turning and flipping them until their borders line up.
+    pub fn construct_picture(&mut self) -> Picture {
+        let tiles_per_matches = self.get_tiles_per_matches();
+        let side_len = tiles_per_matches[2].len() / 2 + tiles_per_matches[3].len() / 4;
+
+        // Each field in a grid consists of tile idx, flipped horizontally, flipped vertically, times rotated right
+        let mut grid: Vec<Option<usize>> = vec![None; side_len * side_len];
+
+        // Top left tile
+        let mut tile_idx = tiles_per_matches[2][0];
+        let mut inserted_tile_side: Dir4;
+        let mut inserted_tile_match: TileMatch;
+        {
+            let matches = &self.tile_match[tile_idx];
+            let mut side1 = matches[0].border1;
+            let mut side2 = matches[1].border1;
+            while !((side1 == Dir4::East && side2 == Dir4::South)
+                || (side1 == Dir4::South && side2 == Dir4::East))
+            {
+                self.rotate_tile_right(tile_idx);
+                let matches = &self.tile_match[tile_idx];
+                side1 = matches[0].border1;
+                side2 = matches[1].border1;
+            }
+            grid[0] = Some(tile_idx);
+
+            // Store the currently inserted tile
+            inserted_tile_side = Dir4::East;
+            let tm_idx = self.tile_match[tile_idx]
+                .iter()
+                .position(|tm| tm.border1 == inserted_tile_side)
+                .unwrap();
+            inserted_tile_match = self.tile_match[tile_idx].remove(tm_idx);
+            tile_idx = self.id_idx_map[&inserted_tile_match.id2];
+        }
+
+        // Top row
+        #[allow(clippy::needless_range_loop)]
+        for col_idx in 1..side_len {
+            // First, find the match to the previously inserted tile
+            let other_id = inserted_tile_match.id1;
+            let tm_idx = self.find_tile_match_idx(tile_idx, other_id);
+
+            // Then, rotate the tile until it fits correctly
+            self.rotate_tile_until_tile_match_opposite(tile_idx, tm_idx, inserted_tile_side);
+
+            // Check if it needed to be flipped along the horizon to match
+            self.flip_tile_to_match(tile_idx, other_id, inserted_tile_side);
+
+            // Then, insert it into the grid
+            grid[col_idx] = Some(tile_idx);
+
+            // Throw current tile out
+            self.tile_match[tile_idx].remove(tm_idx);
+
+            // Now, find the next tile. Skip for top right
+            if col_idx != side_len - 1 {
+                let tm_idx = self.tile_match[tile_idx]
+                    .iter()
+                    .position(|tm| tm.border1 == inserted_tile_side)
+                    .unwrap();
+                inserted_tile_match = self.tile_match[tile_idx].remove(tm_idx);
+                tile_idx = self.id_idx_map[&inserted_tile_match.id2];
+            }
+        }
+
+        // Other rows
+        for col_idx in 0..side_len {
+            inserted_tile_side = Dir4::South;
+            tile_idx = grid[col_idx].unwrap();
+            let tm_idx = self.tile_match[tile_idx]
+                .iter()
+                .position(|tm| tm.border1 == inserted_tile_side)
+                .unwrap();
+            inserted_tile_match = self.tile_match[tile_idx].remove(tm_idx);
+            tile_idx = self.id_idx_map[&inserted_tile_match.id2];
+
+            // Left column
+            {
+                for row_idx in 1..side_len {
+                    // First, find the match to the previously inserted tile
+                    let other_id = inserted_tile_match.id1;
+                    let tm_idx = self.find_tile_match_idx(tile_idx, other_id);
+
+                    // Then, rotate the tile until it fits correctly
+                    self.rotate_tile_until_tile_match_opposite(
+                        tile_idx,
+                        tm_idx,
+                        inserted_tile_side,
+                    );
+
+                    // Check if it needed to be flipped along the horizon to match
+                    self.flip_tile_to_match(tile_idx, other_id, inserted_tile_side);
+
+                    // Then, insert it into the grid
+                    grid[row_idx * side_len + col_idx] = Some(tile_idx);
+
+                    // Throw current tile out
+                    self.tile_match[tile_idx].remove(tm_idx);
+
+                    // Rotate if needed
+                    if row_idx == side_len - 1 {
+                        if col_idx == side_len - 1 {
+                            continue;
+                        }
+                        inserted_tile_side = Dir4::East;
+                    }
+                    // Now, find the next tile
+                    let tm_idx = self.tile_match[tile_idx]
+                        .iter()
+                        .position(|tm| tm.border1 == inserted_tile_side)
+                        .unwrap();
+                    inserted_tile_match = self.tile_match[tile_idx].remove(tm_idx);
+                    tile_idx = self.id_idx_map[&inserted_tile_match.id2];
+                }
+            }
+        }
+
+        // self.print_grid(&grid, side_len);
+
+        let final_grid: Vec<usize> = grid.iter().map(|idx| idx.unwrap()).collect();
+
+        Picture::new(self.tiles, &final_grid, side_len)
+    }
+
+    // Turns or flips the tile, its matches move to the sides their borders end up on
+    fn transform_tile(&mut self, tile_idx: usize, symmetry: Symmetry) {
+        self.tiles[tile_idx].transform(symmetry);
+        for tile_match in self.tile_match[tile_idx].iter_mut() {
+            tile_match.border1 = symmetry.apply_dir(tile_match.border1);
+        }
+    }
+
+    fn rotate_tile_right(&mut self, tile_idx: usize) {
+        self.transform_tile(tile_idx, Symmetry::rotation(1));
+    }
+
+    fn rotate_tile_until_tile_match_opposite(
+        &mut self,
+        tile_idx: usize,
+        tm_idx: usize,
+        opposite_side: Dir4,
+    ) -> u8 {
+        let wanted_side = opposite_side.reverse();
+        let mut times_rotated = 0;
+        while self.tile_match[tile_idx][tm_idx].border1 != wanted_side {
+            self.rotate_tile_right(tile_idx);
+            times_rotated += 1;
+        }
+        times_rotated
+    }
+
+    fn find_tile_match_idx(&self, tile_idx: usize, other_id: u32) -> usize {
+        self.tile_match[tile_idx]
+            .iter()
+            .position(|tm| tm.id2 == other_id)
+            .unwrap()
+    }
+
+    fn flip_tile_to_match(&mut self, tile_idx: usize, other_id: u32, other_side: Dir4) {
+        let my_side = other_side.reverse();
+        let other_idx = self.id_idx_map[&other_id];
+        let other_border: Vec<char> = self.tiles[other_idx].borders[other_side as usize].clone();
+
+        if self.tiles[tile_idx].borders[my_side as usize] != other_border {
+            // Mirrored along the line through the matching side, which stays where it is
+            let flip = match my_side {
+                Dir4::North | Dir4::South => Symmetry::FLIP_HORIZONTAL,
+                _ => Symmetry::FLIP_VERTICAL,
+            };
+            self.transform_tile(tile_idx, flip);
+
+            assert!(self.tiles[tile_idx].borders[my_side as usize] == other_border);
+        }
+    }
+
+    #[allow(dead_code)]
+    fn print_grid(&self, grid: &[Option<usize>], side_len: usize) {
+        let tile_len = self.tiles[0].grid.width();
+        // ! Only works for one row atm!!!
+        let cg_len = side_len * (tile_len + 1);
+        let mut char_grid: Vec<Vec<char>> = vec![vec![]; cg_len];
+
+        println!("Grid: {:?}", grid);
+        for (grid_idx, tile_idx) in grid.iter().enumerate() {
+            let grid_row_offset = (grid_idx / side_len) * (tile_len + 1);
+            if let Some(idx) = tile_idx {
+                for (c_idx, c) in self.tiles[*idx].grid.iter().enumerate() {
+                    let row = c_idx / tile_len;
+                    char_grid[grid_row_offset + row].push(*c);
+                    if (c_idx + 1) % tile_len == 0 {
+                        char_grid[grid_row_offset + row].push(' ');
+                    }
+                }
+            } else {
+                for row in 0..tile_len {
+                    for _ in 0..tile_len {
+                        char_grid[grid_row_offset + row].push(' ');
+                    }
+                    char_grid[grid_row_offset + row].push(' ');
+                }
+            }
+        }
+
+        for row in char_grid {
+            for c in row {
+                print!("{}", c);
+            }
+            println!();
+        }
+    }
+}
+
+// Positions of the monster's '#', relative to its top left corner, and its width and height
+type Shape = (Vec<(usize, usize)>, usize, usize);
+
+fn monster() -> Shape {
+    let offsets = MONSTER
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|(_, ch)| *ch == '#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+    let width = MONSTER.lines().map(str::len).max().unwrap();
+    (offsets, width, MONSTER.lines().count())
+}
+
+/// The tiles put together without their borders.
+#[derive(Debug, Clone)]
+pub struct Picture {
+    grid: Grid2D<char>,
+}
+
+impl Picture {
+    fn new(tiles: &[Tile], order: &[usize], tiles_per_side: usize) -> Self {
+        let chars_per_tile = tiles[0].grid.width() - 2;
+        let line_len = chars_per_tile * tiles_per_side;
+        let mut grid = Grid2D::new(line_len, line_len, 'O');
+        for (order_idx, tile_idx) in order.iter().enumerate() {
+            let offset_x = (order_idx % tiles_per_side) * chars_per_tile;
+            let offset_y = (order_idx / tiles_per_side) * chars_per_tile;
+            // Tiles lose their borders in the picture
+            let interior = tiles[*tile_idx]
+                .grid
+                .sub_grid(1, 1, chars_per_tile, chars_per_tile);
+            for ((x, y), ch) in interior.enumerate() {
+                grid.set(offset_x + x, offset_y + y, *ch);
+            }
+        }
+        Picture { grid }
+    }
+
+    fn find_monster(&self) -> Option<(usize, usize)> {
+        let (monster, mon_width, mon_height) = monster();
+        for row in 0..self.grid.height() - mon_height + 1 {
+            for col in 0..self.grid.width() - mon_width + 1 {
+                let matches = monster
+                    .iter()
+                    .all(|(mon_col, mon_row)| self.grid[(col + mon_col, row + mon_row)] == '#');
+                if matches {
+                    return Some((row, col));
+                }
+            }
+        }
+        None
+    }
+
+    /// Replaces the '#' of every sea monster in the picture with 'O'. Calls `observe` after every
+    /// marked monster.
+    pub fn mark_monsters(&mut self, observe: &mut dyn FnMut(&Picture)) {
+        let (monster, _, _) = monster();
+        while let Some((row, col)) = self.find_monster() {
+            // Mark the monster!
+            for (mon_col, mon_row) in monster.iter() {
+                self.grid.set(col + mon_col, row + mon_row, 'O');
+            }
+            observe(self);
+        }
+    }
+
+    /// Water that isn't part of a monster.
+    pub fn count_roughness(&self) -> usize {
+        self.grid.iter().filter(|c| **c == '#').count()
+    }
+
+    /// Turns and flips the picture until a sea monster shows up. Calls `observe` with every
+    /// orientation that is tried.
+    ///
+    /// Panics if there's no monster in any of them.
+    pub fn rotate_and_flip_until_monster_found(&mut self, observe: &mut dyn FnMut(&Picture)) {
+        let original = self.grid.clone();
+        for symmetry in Symmetry::ALL.iter() {
+            self.grid = original.transform(*symmetry);
+            observe(self);
+            if self.find_monster().is_some() {
+                return;
+            }
+        }
+        panic!("No monster found!");
+    }
+}
+
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+/// Jurassic Jigsaw, putting the image tiles together and looking for sea monsters.
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+
+    type Input<'a> = Vec<Tile>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(&mut input.clone()).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&mut input.clone()).into())
+    }
+
+    // Part 2 shows the orientations of the picture that are tried and then every monster
+    // being marked
+    fn frames(input: &Self::Input<'_>, part: u32, steps: Option<usize>) -> Option<Frames> {
+        if part == 1 {
+            return None;
+        }
+        let mut tiles = input.clone();
+        let mut matcher = TileMatcher::new(&mut tiles);
+        matcher.find_matches();
+        let mut picture = matcher.construct_picture();
+
+        let mut frames = Frames::new();
+        let limit = steps.map_or(usize::MAX, |steps| steps + 1);
+        let mut observe = |picture: &Picture| {
+            if frames.len() < limit {
+                frames.push(picture.to_string());
+            }
+        };
+        picture.rotate_and_flip_until_monster_found(&mut observe);
+        picture.mark_monsters(&mut observe);
+        Some(frames)
+    }
+}
+
+impl Generator for Day20 {
+    const SIZE: &'static str = "tiles per side";
+    const DEFAULT_SIZE: usize = 12;
+    const MIN_SIZE: usize = 2;
+    // Tile IDs have four digits
+    const MAX_SIZE: usize = 94;
+
+    // The image is cut into tiles that share their borders with their neighbors. Borders
+    // are unique even when flipped, so the only matches are the neighbors.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // The image has to be wide enough for a monster
+        let (_, monster_width, _) = monster();
+        let mut tile_len = 10.max(2 + monster_width.div_ceil(size));
+        let borders = loop {
+            match borders(rng, size, tile_len) {
+                Some(borders) => break borders,
+                None => tile_len += 1,
+            }
+        };
+        let (image, monsters) = loop {
+            if let Some(sea) = sea(rng, size * (tile_len - 2)) {
+                break sea;
+            }
+        };
+        let roughness = image.iter().filter(|ch| **ch == '#').count() - 15 * monsters;
+
+        // Fill the image in between the borders
+        let mut whole = borders;
+        let step = tile_len - 1;
+        for ((x, y), ch) in image.enumerate() {
+            whole.set(x + x / (step - 1) + 1, y + y / (step - 1) + 1, *ch);
+        }
+
+        let mut ids: Vec<u64> = (1000..10000).collect();
+        rng.shuffle(&mut ids);
+        let mut tiles: Vec<(u64, Grid2D<char>)> = (0..size * size)
+            .map(|idx| {
+                let (x, y) = (idx % size, idx / size);
+                let mut grid = whole.sub_grid(x * step, y * step, tile_len, tile_len);
+                for _ in 0..rng.range(0, 4) {
+                    grid = grid.rotate_right();
+                }
+                if rng.bool() {
+                    grid = grid.flip_horizontal();
+                }
+                (ids[idx], grid)
+            })
+            .collect();
+        let corners: u64 = [0, size - 1, size * (size - 1), size * size - 1]
+            .iter()
+            .map(|idx| tiles[*idx].0)
+            .product();
+        rng.shuffle(&mut tiles);
+
+        let tiles: Vec<String> = tiles
+            .iter()
+            .map(|(id, grid)| format!("Tile {}:\n{}", id, grid))
+            .collect();
+        Generated::new(tiles.join("\n"))
+            .with_part1(corners)
+            .with_part2(roughness)
+    }
+}
+
+// The lines between the tiles of the whole picture, None if the tiles are too small to keep
+// every border unique
+fn borders(rng: &mut Rng, tiles_per_side: usize, tile_len: usize) -> Option<Grid2D<char>> {
+    let step = tile_len - 1;
+    let len = tiles_per_side * step + 1;
+    let mut whole = Grid2D::new(len, len, '.');
+    let random = |rng: &mut Rng| if rng.bool() { '#' } else { '.' };
+    for y in (0..len).step_by(step) {
+        for x in (0..len).step_by(step) {
+            whole.set(x, y, random(rng));
+        }
+    }
+
+    let mut used = HashSet::new();
+    for line in (0..len).step_by(step) {
+        for start in (0..len - 1).step_by(step) {
+            for horizontal in [true, false] {
+                let cell = |offset| {
+                    if horizontal {
+                        (start + offset, line)
+                    } else {
+                        (line, start + offset)
+                    }
+                };
+                let mut found = false;
+                for _ in 0..100 {
+                    let mut border: Vec<char> = (0..tile_len).map(|_| random(rng)).collect();
+                    border[0] = whole[cell(0)];
+                    border[step] = whole[cell(step)];
+                    let flipped: Vec<char> = border.iter().rev().copied().collect();
+                    if border != flipped && !used.contains(&border) {
+                        for (offset, ch) in border.iter().enumerate() {
+                            whole[cell(offset)] = *ch;
+                        }
+                        used.insert(border);
+                        used.insert(flipped);
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(whole)
+}
+
+// Rough water with monsters in it, facing the way the monster is drawn, and how many there
+// are. None in the rare case that planted monsters form another one between them.
+fn sea(rng: &mut Rng, len: usize) -> Option<(Grid2D<char>, usize)> {
+    let variants = monster_variants();
+    let (offsets, width, height) = &variants[0];
+    let cells = (0..len * len)
+        .map(|_| if rng.range(0, 4) == 0 { '#' } else { '.' })
+        .collect();
+    let mut image = Grid2D::from_vec(len, cells);
+
+    // Monsters don't even share the rectangle around them
+    let mut taken = Grid2D::new(len, len, false);
+    let mut planted = HashSet::new();
+    for _ in 0..(len * len / 200).max(1) {
+        let x = rng.range(0, (len - width + 1) as u64) as usize;
+        let y = rng.range(0, (len - height + 1) as u64) as usize;
+        let free = (0..*height).all(|dy| (0..*width).all(|dx| !taken[(x + dx, y + dy)]));
+        if free {
+            for dy in 0..*height {
+                for dx in 0..*width {
+                    taken[(x + dx, y + dy)] = true;
+                }
+            }
+            for (dx, dy) in offsets.iter() {
+                image[(x + dx, y + dy)] = '#';
+            }
+            planted.insert((x, y));
+        }
+    }
+    let in_monster: HashSet<(usize, usize)> = planted
+        .iter()
+        .flat_map(|(x, y)| offsets.iter().map(move |(dx, dy)| (x + dx, y + dy)))
+        .collect();
+
+    // The water can form more monsters, in any direction. Taking away one of their '#' that
+    // isn't part of a planted monster gets rid of them, without making new ones.
+    for (idx, (offsets, width, height)) in variants.iter().enumerate() {
+        for y in 0..(len + 1).saturating_sub(*height) {
+            for x in 0..(len + 1).saturating_sub(*width) {
+                let cells = || offsets.iter().map(|(dx, dy)| (x + dx, y + dy));
+                if (idx == 0 && planted.contains(&(x, y))) || cells().any(|cell| image[cell] != '#')
+                {
+                    continue;
+                }
+                image[cells().find(|cell| !in_monster.contains(cell))?] = '.';
+            }
+        }
+    }
+    Some((image, planted.len()))
+}
+
+// The monster in all eight ways it can be turned and flipped, as drawn first
+fn monster_variants() -> Vec<Shape> {
+    let (offsets, width, height) = monster();
+    let mut drawing = Grid2D::new(width, height, false);
+    for (x, y) in offsets {
+        drawing[(x, y)] = true;
+    }
+
+    let mut variants = Vec::new();
+    for symmetry in Symmetry::ALL.iter() {
+        let turned = drawing.transform(*symmetry);
+        let offsets = turned
+            .enumerate()
+            .filter(|(_, cell)| **cell)
+            .map(|(position, _)| position)
+            .collect();
+        variants.push((offsets, turned.width(), turned.height()));
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-20.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-20.txt");
+
+    #[test]
+    fn simple_star1() {
+        let mut tiles = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&mut tiles), answers::expected(20, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let mut tiles = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&mut tiles), answers::expected(20, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let mut tiles = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&mut tiles), answers::expected(20, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let mut tiles = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&mut tiles), answers::expected(20, "full", 2));
+    }
+
+    #[test]
+    fn frames_of_the_monster_search() {
+        let tiles = parse_input(SIMPLE_INPUT).unwrap();
+        assert!(Day20::frames(&tiles, 1, None).is_none());
+        let frames = Day20::frames(&tiles, 2, None).unwrap();
+        // The example has two monsters, the last frame has both of them marked
+        let last = frames.iter().last().unwrap();
+        assert_eq!(last.matches('O').count(), 2 * monster().0.len());
+        assert_eq!(
+            last.matches('#').count(),
+            answers::expected(20, "simple", 2)
+        );
+        assert_eq!(Day20::frames(&tiles, 2, Some(0)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day20>(5, &[2, 3, 12]);
+    }
+}
@@ -0,0 +1,28 @@
+use day_20_rust::{parse_input, TileMatcher};
+
+const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-20.txt");
+
+#[test]
+fn assemble_the_example() {
+    let mut tiles = parse_input(SIMPLE_INPUT).unwrap();
+    let mut matcher = TileMatcher::new(&mut tiles);
+    matcher.find_matches();
+    let by_matches = matcher.get_tiles_per_matches();
+    // A 3 by 3 square has four corners, four edges and the middle
+    let counts: Vec<usize> = by_matches.iter().map(Vec::len).collect();
+    assert_eq!(counts, vec![0, 0, 4, 4, 1]);
+
+    let mut picture = matcher.construct_picture();
+    assert_eq!(picture.to_string().lines().count(), 24);
+    picture.rotate_and_flip_until_monster_found(&mut |_| {});
+    let mut monsters = 0;
+    picture.mark_monsters(&mut |_| monsters += 1);
+    assert_eq!(monsters, 2);
+    assert_eq!(picture.count_roughness(), 273);
+
+    let corners: u64 = by_matches[2]
+        .iter()
+        .map(|idx| tiles[*idx].id() as u64)
+        .product();
+    assert_eq!(corners, 20899048083289);
+}
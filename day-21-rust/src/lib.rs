@@ -0,0 +1,284 @@
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::graph;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::{BTreeSet, HashSet};
+
+/// Parses the foods, their ingredients followed by the allergens they contain, like
+/// "mxmxvkd kfcds (contains dairy, fish)".
+pub fn parse_input(input: &str) -> Result<Vec<Product<'_>>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Product::parse(input, line.trim()))
+        .collect()
+}
+
+/// How many times ingredients that can't contain any allergen appear in the foods.
+pub fn star1(products: &[Product]) -> usize {
+    let suspects = Suspects::new(products);
+    products
+        .iter()
+        .flat_map(|product| product.ingredients.iter())
+        .filter(|ingredient| suspects.ingredients.binary_search(ingredient).is_err())
+        .count()
+}
+
+/// The dangerous ingredients, separated by commas and sorted by the allergen they contain.
+pub fn star2(products: &[Product]) -> String {
+    let suspects = Suspects::new(products);
+    let matching = graph::max_matching(&suspects.candidates, suspects.ingredients.len());
+    if !matching.is_perfect() {
+        panic!("Every allergen needs an ingredient of its own!");
+    }
+    if !matching.is_unique(&suspects.candidates) {
+        panic!("Multiple ingredient lists possible!");
+    }
+
+    // In alphabetical order of the allergens, like the candidates
+    let ingredients: Vec<&str> = matching
+        .left
+        .iter()
+        .map(|ingredient| suspects.ingredients[ingredient.unwrap()])
+        .collect();
+    ingredients.join(",")
+}
+
+/// A food with its ingredients and the allergens it lists, which may not be all it has.
+pub struct Product<'a> {
+    ingredients: Vec<&'a str>,
+    allergens: Vec<&'a str>,
+}
+
+impl<'a> Product<'a> {
+    fn new(ingredients: Vec<&'a str>, allergens: Vec<&'a str>) -> Self {
+        Product {
+            ingredients,
+            allergens,
+        }
+    }
+
+    fn parse(input: &str, line: &'a str) -> Result<Self, ParseError> {
+        let expected = "a product like \"mxmxvkd kfcds (contains dairy, fish)\"";
+        let (ingredients, allergens) = line
+            .split_once("(contains")
+            .ok_or_else(|| ParseError::at(input, line, expected))?;
+        let allergens = allergens
+            .strip_suffix(')')
+            .ok_or_else(|| ParseError::at(input, &line[line.len()..], "')'"))?;
+
+        let ingredients = ingredients.split_whitespace().collect();
+        let allergens = allergens.trim().split(", ").collect();
+        Ok(Product::new(ingredients, allergens))
+    }
+}
+
+// An allergen can only be in the ingredients every product that lists it has. Those are its
+// candidates, and the ingredients that are a candidate for any allergen are the suspects.
+struct Suspects<'a> {
+    ingredients: Vec<&'a str>,
+    // Indices into `ingredients` for every allergen, in alphabetical order
+    candidates: Vec<Vec<usize>>,
+}
+
+impl<'a> Suspects<'a> {
+    fn new(products: &[Product<'a>]) -> Suspects<'a> {
+        let allergens: BTreeSet<&str> = products
+            .iter()
+            .flat_map(|product| product.allergens.iter().copied())
+            .collect();
+        let in_all: Vec<BTreeSet<&str>> = allergens
+            .iter()
+            .map(|allergen| {
+                let mut listing = products
+                    .iter()
+                    .filter(|product| product.allergens.contains(allergen))
+                    .map(|product| product.ingredients.iter().copied().collect::<HashSet<_>>());
+                let first = listing.next().unwrap_or_default();
+                listing
+                    .fold(first, |common, ingredients| {
+                        common.intersection(&ingredients).copied().collect()
+                    })
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+
+        let ingredients: Vec<&str> = in_all
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let candidates = in_all
+            .iter()
+            .map(|common| {
+                common
+                    .iter()
+                    .map(|ingredient| ingredients.binary_search(ingredient).unwrap())
+                    .collect()
+            })
+            .collect();
+        Suspects {
+            ingredients,
+            candidates,
+        }
+    }
+}
+
+/// Allergen Assessment, working out which ingredient contains which allergen.
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+
+    type Input<'a> = Vec<Product<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        star1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(input).into())
+    }
+}
+const ALLERGENS: [&str; 8] = [
+    "dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "soy", "wheat",
+];
+
+impl Generator for Day21 {
+    const SIZE: &'static str = "foods";
+    const DEFAULT_SIZE: usize = 48;
+
+    // Every allergen has one food that lists nothing else. Whatever is in all the foods with
+    // an allergen, besides its own ingredient, is taken out of that food, so each allergen can
+    // only be in its own ingredient.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut allergens = ALLERGENS.to_vec();
+        rng.shuffle(&mut allergens);
+        allergens.truncate(size.min(ALLERGENS.len()));
+        let words = generate::words(rng, 200 + allergens.len(), 5);
+        let (dangerous, safe) = words.split_at(allergens.len());
+
+        let mut foods: Vec<(Vec<&str>, Vec<usize>)> = (0..size)
+            .map(|idx| {
+                let mut listed: Vec<usize> = (0..allergens.len()).collect();
+                rng.shuffle(&mut listed);
+                if idx < allergens.len() {
+                    listed = vec![idx];
+                } else {
+                    listed.truncate(rng.range(1, 4) as usize);
+                }
+
+                let mut ingredients: Vec<&str> = (0..rng.range(20, 60))
+                    .map(|_| rng.pick(safe).as_str())
+                    .collect();
+                // Not every allergen is listed where it's in
+                for (allergen, ingredient) in dangerous.iter().enumerate() {
+                    if listed.contains(&allergen) || rng.range(0, 4) == 0 {
+                        ingredients.push(ingredient);
+                    }
+                }
+                ingredients.sort_unstable();
+                ingredients.dedup();
+                rng.shuffle(&mut ingredients);
+                (ingredients, listed)
+            })
+            .collect();
+
+        for allergen in 0..allergens.len() {
+            let common: Vec<&str> = foods[allergen]
+                .0
+                .iter()
+                .copied()
+                .filter(|ingredient| *ingredient != dangerous[allergen])
+                .filter(|ingredient| {
+                    foods
+                        .iter()
+                        .filter(|(_, listed)| listed.contains(&allergen))
+                        .all(|(ingredients, _)| ingredients.contains(ingredient))
+                })
+                .collect();
+            foods[allergen]
+                .0
+                .retain(|ingredient| !common.contains(ingredient));
+        }
+        rng.shuffle(&mut foods);
+
+        let safe_appearances = foods
+            .iter()
+            .flat_map(|(ingredients, _)| ingredients.iter())
+            .filter(|ingredient| !dangerous.iter().any(|word| word == *ingredient))
+            .count();
+        let mut by_allergen: Vec<(&str, &str)> = allergens
+            .iter()
+            .zip(dangerous.iter())
+            .map(|(allergen, ingredient)| (*allergen, ingredient.as_str()))
+            .collect();
+        by_allergen.sort_unstable();
+        let dangerous_list: Vec<&str> = by_allergen.iter().map(|(_, word)| *word).collect();
+
+        let foods = foods.iter().map(|(ingredients, listed)| {
+            let listed: Vec<&str> = listed.iter().map(|idx| allergens[*idx]).collect();
+            format!("{} (contains {})", ingredients.join(" "), listed.join(", "))
+        });
+        Generated::new(generate::lines(foods))
+            .with_part1(safe_appearances)
+            .with_part2(dangerous_list.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-21.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-21.txt");
+
+    #[test]
+    fn simple_star1() {
+        let products = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&products), answers::expected(21, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let products = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&products), answers::expected(21, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let products = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(
+            star2(&products),
+            answers::expected::<String>(21, "simple", 2)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        let products = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&products), answers::expected::<String>(21, "full", 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Multiple ingredient lists possible!")]
+    fn ambiguous_allergens() {
+        let products = parse_input("a b (contains dairy, fish)\na b c (contains fish)").unwrap();
+        star2(&products);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day21>(20, &[1, 5, 48]);
+    }
+}
@@ -0,0 +1,379 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::input;
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::simulation::{self, Simulation, Stop};
+use aoc_common::solution::{Answer, Solution};
+use std::hash::Hash;
+use std::{collections::VecDeque, iter::FromIterator};
+
+const MAX_CARD_VALUE: usize = 50;
+const CARD_PER_U64: usize = 11; // log_51(2^64) = 11
+const REQUIRED_U64_BYTES: usize = 5; // MAX_CARD_VALUE / CARD_PER_U64
+const VALUE_MULTIPLIER: u64 = (MAX_CARD_VALUE as u64) + 1;
+
+/// Parses the decks of both players, separated by a blank line, top card first.
+pub fn parse_input(input: &str) -> Result<(VecDeque<u8>, VecDeque<u8>), ParseError> {
+    let trimmed = input.trim();
+    let mut players = input::paragraphs(input);
+    let mut next_player = || {
+        players
+            .next()
+            .ok_or_else(|| ParseError::at(input, &trimmed[trimmed.len()..], "a player's deck"))
+    };
+    let player1 = parse_player(input, next_player()?)?;
+    let player2 = parse_player(input, next_player()?)?;
+
+    Ok((player1, player2))
+}
+
+fn parse_player(input: &str, deck: &str) -> Result<VecDeque<u8>, ParseError> {
+    let expected = format!("a card from 1 to {}", MAX_CARD_VALUE);
+    deck.trim()
+        .lines()
+        .skip(1)
+        .map(|line| match line.trim().parse::<u8>() {
+            Ok(card) if card >= 1 && card as usize <= MAX_CARD_VALUE => Ok(card),
+            _ => Err(ParseError::at(input, line.trim(), expected.as_str())),
+        })
+        .collect()
+}
+
+/// Score of the winner's deck after a game of Combat.
+pub fn star1(player1: &VecDeque<u8>, player2: &VecDeque<u8>) -> usize {
+    let mut player1 = player1.clone();
+    let mut player2 = player2.clone();
+
+    // Play game
+    while !player1.is_empty() && !player2.is_empty() {
+        let play1 = player1.pop_front().unwrap();
+        let play2 = player2.pop_front().unwrap();
+        let winner = if play1 > play2 {
+            &mut player1
+        } else {
+            &mut player2
+        };
+        winner.push_back(play1.max(play2));
+        winner.push_back(play1.min(play2));
+    }
+
+    // Get the winner
+    let winner = if player1.is_empty() {
+        &player2
+    } else {
+        &player1
+    };
+
+    // Calculate winner's score
+    calculate_score(winner)
+}
+
+fn calculate_score(deck: &VecDeque<u8>) -> usize {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, value)| (idx + 1) * (*value as usize))
+        .sum()
+}
+
+/// Score of the winner's deck after a game of Recursive Combat.
+pub fn star2(player1: &VecDeque<u8>, player2: &VecDeque<u8>) -> usize {
+    let mut recursive_combat = RecursiveCombat::new(player1.clone(), player2.clone());
+    let winner = recursive_combat.play();
+    calculate_score(recursive_combat.get_players_deck(winner))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Player {
+    Player1,
+    Player2,
+}
+
+impl Player {
+    fn opponent(self) -> Self {
+        match self {
+            Player::Player1 => Player::Player2,
+            Player::Player2 => Player::Player1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeckState {
+    card_sums: [u64; REQUIRED_U64_BYTES], // Assertion checked in parse_input()
+}
+
+impl DeckState {
+    fn new(deck1: &VecDeque<u8>, deck2: &VecDeque<u8>) -> Self {
+        let mut card_sums = [0; REQUIRED_U64_BYTES];
+
+        // Insert deck1
+        let mut idx = 0;
+        for element in deck1.iter() {
+            let arr_idx = idx / CARD_PER_U64;
+            card_sums[arr_idx] = (card_sums[arr_idx] * VALUE_MULTIPLIER) + (*element as u64);
+            idx += 1;
+        }
+
+        // Add separator
+        card_sums[idx / CARD_PER_U64] *= VALUE_MULTIPLIER;
+        idx += 1;
+
+        // Insert deck2
+        for element in deck2.iter() {
+            let arr_idx = idx / CARD_PER_U64;
+            card_sums[arr_idx] = (card_sums[arr_idx] * VALUE_MULTIPLIER) + (*element as u64);
+            idx += 1;
+        }
+
+        // More elegant, but slower solution:
+        // let separator = Some(&0u8);
+        // let iterator = deck1
+        //     .iter()
+        //     .chain(separator.into_iter())
+        //     .chain(deck2.iter());
+        // for (idx, element) in iterator.enumerate() {
+        //     let arr_idx = idx / CARD_PER_U64;
+        //     card_sums[arr_idx] = (card_sums[arr_idx] * VALUE_MULTIPLIER) + (*element as u64);
+        // }
+        DeckState { card_sums }
+    }
+}
+
+impl Hash for DeckState {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let hash = self.card_sums[0] ^ self.card_sums[2];
+        state.write_u64(hash);
+    }
+}
+
+struct RecursiveCombat {
+    deck1: VecDeque<u8>,
+    deck2: VecDeque<u8>,
+}
+
+impl RecursiveCombat {
+    fn new(deck1: VecDeque<u8>, deck2: VecDeque<u8>) -> Self {
+        RecursiveCombat { deck1, deck2 }
+    }
+
+    // Play the game and return the winner. Player 1 wins as soon as the decks repeat.
+    fn play(&mut self) -> Player {
+        match simulation::until_repeat(self, usize::MAX) {
+            Stop::Cycle { .. } => Player::Player1,
+            _ => self.get_deck_winner(),
+        }
+    }
+
+    fn play_round(&mut self) {
+        let draw1 = self.deck1.pop_front().unwrap();
+        let draw2 = self.deck2.pop_front().unwrap();
+
+        let winner: Player = if Self::player_has_enough_cards(&self.deck1, draw1)
+            && Self::player_has_enough_cards(&self.deck2, draw2)
+        {
+            // Start a new round of recursive combat!
+            let copied_deck1: VecDeque<u8> =
+                VecDeque::from_iter(self.deck1.iter().take(draw1 as usize).copied());
+            let copied_deck2: VecDeque<u8> =
+                VecDeque::from_iter(self.deck2.iter().take(draw2 as usize).copied());
+            let mut recursive_round = RecursiveCombat::new(copied_deck1, copied_deck2);
+
+            recursive_round.play()
+        } else {
+            // Play with normal rules
+            if draw1 > draw2 {
+                Player::Player1
+            } else {
+                Player::Player2
+            }
+        };
+        let winning_deck = self.get_players_deck_mut(winner);
+        winning_deck.push_back(Self::players_card(winner, draw1, draw2));
+        winning_deck.push_back(Self::players_card(winner.opponent(), draw1, draw2));
+    }
+
+    fn player_has_enough_cards(deck: &VecDeque<u8>, amount: u8) -> bool {
+        deck.len() >= (amount as usize)
+    }
+
+    fn players_card(player: Player, card1: u8, card2: u8) -> u8 {
+        match player {
+            Player::Player1 => card1,
+            Player::Player2 => card2,
+        }
+    }
+
+    fn one_deck_empty(&self) -> bool {
+        self.deck1.is_empty() || self.deck2.is_empty()
+    }
+
+    fn get_players_deck(&self, player: Player) -> &VecDeque<u8> {
+        match player {
+            Player::Player1 => &self.deck1,
+            Player::Player2 => &self.deck2,
+        }
+    }
+
+    fn get_players_deck_mut(&mut self, player: Player) -> &mut VecDeque<u8> {
+        match player {
+            Player::Player1 => &mut self.deck1,
+            Player::Player2 => &mut self.deck2,
+        }
+    }
+
+    fn get_deck_winner(&self) -> Player {
+        assert!(self.one_deck_empty());
+        if self.deck1.is_empty() {
+            Player::Player2
+        } else {
+            Player::Player1
+        }
+    }
+}
+
+impl Simulation for RecursiveCombat {
+    type State = DeckState;
+
+    fn step(&mut self) {
+        self.play_round();
+    }
+
+    fn state(&self) -> DeckState {
+        DeckState::new(&self.deck1, &self.deck2)
+    }
+
+    fn finished(&self) -> bool {
+        self.one_deck_empty()
+    }
+}
+
+/// Crab Combat, playing cards against the crab.
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+
+    type Input<'a> = (VecDeque<u8>, VecDeque<u8>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        let (player1, player2) = input;
+        star1(player1, player2).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+        let (player1, player2) = input;
+        Some(star2(player1, player2).into())
+    }
+}
+
+impl Generator for Day22 {
+    const SIZE: &'static str = "cards";
+    const DEFAULT_SIZE: usize = MAX_CARD_VALUE;
+    const MIN_SIZE: usize = 2;
+    const MAX_SIZE: usize = MAX_CARD_VALUE;
+
+    // Some deals would keep the first game going forever, those are dealt again
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        const MAX_ROUNDS: usize = 100_000;
+        loop {
+            let mut cards: Vec<usize> = (1..=size).collect();
+            rng.shuffle(&mut cards);
+            let (first, second) = cards.split_at(size / 2);
+            let mut decks = [
+                first.iter().copied().collect::<VecDeque<usize>>(),
+                second.iter().copied().collect(),
+            ];
+
+            for _ in 0..MAX_ROUNDS {
+                if let Some(winner) = decks.iter().find(|deck| deck.len() == size) {
+                    let deck = |deck: &[usize]| {
+                        let cards: Vec<String> = deck.iter().map(usize::to_string).collect();
+                        cards.join("\n")
+                    };
+                    let input = format!(
+                        "Player 1:\n{}\n\nPlayer 2:\n{}\n",
+                        deck(first),
+                        deck(second)
+                    );
+                    let score: usize = winner
+                        .iter()
+                        .rev()
+                        .enumerate()
+                        .map(|(idx, card)| (idx + 1) * card)
+                        .sum();
+                    return Generated::new(input).with_part1(score);
+                }
+                let cards = [decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap()];
+                let winner = if cards[0] > cards[1] { 0 } else { 1 };
+                decks[winner].push_back(cards[winner]);
+                decks[winner].push_back(cards[1 - winner]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-22.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-22.txt");
+
+    #[test]
+    fn simple_star1() {
+        let (player1, player2) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(
+            star1(&player1, &player2),
+            answers::expected(22, "simple", 1)
+        );
+    }
+
+    #[test]
+    fn full_star1() {
+        let (player1, player2) = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&player1, &player2), answers::expected(22, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let (player1, player2) = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(
+            star2(&player1, &player2),
+            answers::expected(22, "simple", 2)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        let (player1, player2) = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&player1, &player2), answers::expected(22, "full", 2));
+    }
+
+    #[test]
+    fn repeated_rounds() {
+        // The example of a game that would go on forever without the repetition rule
+        let game = || {
+            RecursiveCombat::new(
+                VecDeque::from(vec![43, 19]),
+                VecDeque::from(vec![2, 29, 14]),
+            )
+        };
+        assert!(matches!(
+            simulation::until_repeat(&mut game(), usize::MAX),
+            Stop::Cycle { .. }
+        ));
+        assert_eq!(game().play(), Player::Player1);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day22>(5, &[2, 11, 30]);
+    }
+}
@@ -0,0 +1,534 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::simulation::{self, Simulation};
+use aoc_common::solution::{Answer, Solution, Strategy};
+
+/// A circular list of the labels 1 to n, stored as the label following each one.
+pub mod vector_linked_list;
+use vector_linked_list::VectorLinkedList;
+
+/// Cups are labeled with each of the digits from 1 up to their count, in the order they are
+/// placed in the circle.
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let line = input.trim();
+    if line.is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "cup labels like \"389125467\"",
+        ));
+    }
+    let count = line.chars().count() as u32;
+    // A move picks up three cups besides the current one, with fewer there's no destination
+    if count < 4 {
+        return Err(ParseError::at(input, line, "at least 4 cup labels"));
+    }
+    let expected = format!("a new cup label from 1 to {}", count);
+    let mut seen = vec![false; count as usize + 1];
+    line.char_indices()
+        .map(|(idx, ch)| match ch.to_digit(10) {
+            Some(label) if label >= 1 && label <= count && !seen[label as usize] => {
+                seen[label as usize] = true;
+                Ok(label)
+            }
+            _ => {
+                let ch = &line[idx..idx + ch.len_utf8()];
+                Err(ParseError::at(input, ch, expected.as_str()))
+            }
+        })
+        .collect()
+}
+
+/// Moves made in the first part.
+pub const MOVES1: u64 = 100;
+/// Cups in the circle of the second part, the labelled ones included.
+pub const CUPS2: u32 = 1_000_000;
+/// Moves made in the second part.
+pub const MOVES2: u64 = 10_000_000;
+
+/// Labels of the cups after cup 1 once all the moves are made, read as a single number.
+pub fn star1(numbers: &[u32], moves: u64) -> u64 {
+    let mut crab_game = CrabGameFaster::new(numbers);
+    simulation::run(&mut crab_game, moves as usize);
+    crab_game.calculate_order()
+}
+
+/// Product of the two labels following cup 1 once all the moves are made, with the cups after
+/// the labelled ones numbered on up to `cups`.
+pub fn star2(numbers: &[u32], cups: u32, moves: u64) -> u64 {
+    let mut crab_game = CrabGameFaster::new_with_cups(numbers, cups);
+    simulation::run(&mut crab_game, moves as usize);
+    crab_game.calculate_next_two()
+}
+
+/// Labels of the cups with the parameters of both parts.
+pub struct Game {
+    pub labels: Vec<u32>,
+    pub moves1: u64,
+    pub cups2: u32,
+    pub moves2: u64,
+}
+
+// The labelled cups followed by the next labels, up to the given count
+fn with_cups(numbers: &[u32], cups: u32) -> Vec<u32> {
+    let mut numbers = Vec::from(numbers);
+    let max = *numbers.iter().max().unwrap();
+    numbers.extend(max + 1..=cups);
+    numbers
+}
+
+#[derive(Debug, Clone)]
+struct CrabGameFaster {
+    numbers: VectorLinkedList,
+    current_cup_val: u32,
+    max: u32,
+}
+
+impl CrabGameFaster {
+    fn new(numbers: &[u32]) -> Self {
+        CrabGameFaster {
+            max: *numbers.iter().max().unwrap(),
+            numbers: VectorLinkedList::new(numbers),
+            current_cup_val: numbers[0],
+        }
+    }
+
+    fn new_with_cups(numbers: &[u32], cups: u32) -> Self {
+        CrabGameFaster::new(&with_cups(numbers, cups))
+    }
+
+    fn execute_move(&mut self) {
+        // Find the three cups
+        let taken_out = self.get_three_followers();
+
+        // Select the destination cup
+        let destination_cup_val = self.find_destination_cup_val(&taken_out);
+
+        // Move the cups
+        self.move_three_followers(destination_cup_val, &taken_out);
+
+        // Determine new current cup position
+        self.current_cup_val = self.numbers.follows(self.current_cup_val);
+    }
+
+    fn get_three_followers(&self) -> [u32; 3] {
+        let mut taken_out = [0; 3];
+        taken_out[0] = self.numbers.follows(self.current_cup_val);
+        taken_out[1] = self.numbers.follows(taken_out[0]);
+        taken_out[2] = self.numbers.follows(taken_out[1]);
+        taken_out
+    }
+
+    fn find_destination_cup_val(&self, taken_out: &[u32; 3]) -> u32 {
+        let mut cup_value = self.current_cup_val - 1;
+        while cup_value == 0 || taken_out.contains(&cup_value) {
+            if cup_value == 0 {
+                cup_value = self.max;
+            } else {
+                cup_value -= 1;
+            }
+        }
+        cup_value
+    }
+
+    fn move_three_followers(&mut self, destination_cup_val: u32, taken_out: &[u32; 3]) {
+        // Rewire the current cup
+        let new_current_cup_follower = self.numbers.follows(taken_out[2]);
+        self.numbers
+            .rewire(self.current_cup_val, new_current_cup_follower);
+        // Rewire the destination cup and taken out elements
+        let destination_cup_follower = self.numbers.follows(destination_cup_val);
+        self.numbers.rewire(destination_cup_val, taken_out[0]);
+        self.numbers.rewire(taken_out[2], destination_cup_follower);
+    }
+
+    fn calculate_order(&self) -> u64 {
+        let mut acc = 0;
+        let mut current = self.numbers.follows(1);
+        for _ in 0..self.numbers.len() - 1 {
+            acc = acc * 10 + (current as u64);
+            current = self.numbers.follows(current);
+        }
+        acc
+    }
+
+    fn calculate_next_two(&self) -> u64 {
+        let first_follower = self.numbers.follows(1);
+        let second_follower = self.numbers.follows(first_follower);
+        first_follower as u64 * second_follower as u64
+    }
+}
+
+impl Simulation for CrabGameFaster {
+    type State = (u32, VectorLinkedList);
+
+    fn step(&mut self) {
+        self.execute_move();
+    }
+
+    fn state(&self) -> Self::State {
+        (self.current_cup_val, self.numbers.clone())
+    }
+}
+
+// First version, which moves the cups around in a vector. Way too slow for a million cups.
+#[derive(Debug, Clone)]
+struct CrabGame {
+    numbers: Vec<u32>,
+    current_cup: usize,
+    max: u32,
+}
+
+impl CrabGame {
+    fn new(numbers: &[u32]) -> Self {
+        CrabGame {
+            max: *numbers.iter().max().unwrap(),
+            numbers: Vec::from(numbers),
+            current_cup: 0,
+        }
+    }
+
+    fn new_with_cups(numbers: &[u32], cups: u32) -> Self {
+        CrabGame::new(&with_cups(numbers, cups))
+    }
+
+    fn execute_move(&mut self) {
+        let current_cup_val = self.numbers[self.current_cup];
+
+        // Take out three cups
+        let taken_out = self.take_three_out();
+
+        // Select the destination cup
+        let destination_cup = self.find_destination_cup(current_cup_val, &taken_out);
+
+        // Insert the cups back
+        self.put_three_back(destination_cup, &taken_out);
+
+        // Determine new current cup position
+        self.update_current_cup(current_cup_val);
+    }
+
+    fn take_three_out(&mut self) -> [u32; 3] {
+        let mut taken_out = [0; 3];
+        let removing_idx = (self.current_cup + 1) % self.numbers.len();
+        for cup in taken_out.iter_mut() {
+            if removing_idx < self.numbers.len() {
+                *cup = self.numbers.remove(removing_idx);
+            } else {
+                *cup = self.numbers.remove(0);
+            }
+        }
+        taken_out
+    }
+
+    fn find_destination_cup(&self, current_cup_val: u32, taken_out: &[u32; 3]) -> usize {
+        let destination_cup_val = {
+            let mut val = current_cup_val - 1;
+            while val == 0 || taken_out.contains(&val) {
+                if val == 0 {
+                    val = self.max;
+                } else {
+                    val -= 1;
+                }
+            }
+            val
+        };
+
+        let destination_cup = self
+            .numbers
+            .iter()
+            .position(|x| *x == destination_cup_val)
+            .unwrap();
+
+        destination_cup
+    }
+
+    fn put_three_back(&mut self, destination_cup: usize, taken_out: &[u32; 3]) {
+        let insertion_point = (destination_cup + 1) % self.numbers.len();
+        for x in taken_out.iter().rev() {
+            self.numbers.insert(insertion_point, *x);
+        }
+    }
+
+    fn update_current_cup(&mut self, current_cup_val: u32) {
+        let new_current_cup_pos = self
+            .numbers
+            .iter()
+            .position(|x| *x == current_cup_val)
+            .unwrap();
+        self.current_cup = (new_current_cup_pos + 1) % self.numbers.len();
+    }
+
+    fn calculate_order(&self) -> u64 {
+        let idx1 = self.numbers.iter().position(|x| *x == 1).unwrap();
+        self.numbers
+            .iter()
+            .skip(idx1 + 1)
+            .chain(self.numbers.iter().take(idx1))
+            .fold(0, |acc, x| acc * 10 + (*x as u64))
+    }
+
+    fn calculate_next_two(&self) -> u64 {
+        let idx1 = self.numbers.iter().position(|x| *x == 1).unwrap();
+        // The cups are in a circle, so the followers can wrap around to the start
+        let len = self.numbers.len();
+        self.numbers[(idx1 + 1) % len] as u64 * self.numbers[(idx1 + 2) % len] as u64
+    }
+}
+
+impl Simulation for CrabGame {
+    type State = (usize, Vec<u32>);
+
+    fn step(&mut self) {
+        self.execute_move();
+    }
+
+    fn state(&self) -> Self::State {
+        (self.current_cup, self.numbers.clone())
+    }
+}
+
+/// Crab Cups, playing the crab's game of moving cups around the circle.
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+
+    type Input<'a> = Game;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day23::parse_with(input, &Params::defaults(Day23::PARAMS))
+    }
+
+    // Labels of the input are single digits, so there are always enough cups for nine of them
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "moves1",
+            default: "100",
+            description: "Moves to make in part 1",
+            check: |value| params::between(value, 0, u64::MAX),
+        },
+        Param {
+            name: "cups2",
+            default: "1000000",
+            description: "Cups in the circle in part 2",
+            check: |value| params::between(value, 9, 100_000_000),
+        },
+        Param {
+            name: "moves2",
+            default: "10000000",
+            description: "Moves to make in part 2",
+            check: |value| params::between(value, 0, u64::MAX),
+        },
+    ];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        Ok(Game {
+            labels: parse_input(input)?,
+            moves1: params.get("moves1"),
+            cups2: params.get("cups2"),
+            moves2: params.get("moves2"),
+        })
+    }
+
+    fn part1(game: &Self::Input<'_>) -> Answer {
+        star1(&game.labels, game.moves1).into()
+    }
+
+    fn part2(game: &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(&game.labels, game.cups2, game.moves2).into())
+    }
+
+    const DEFAULT_STRATEGY: &'static str = "linked-list";
+
+    fn strategies() -> Vec<Strategy<Self>> {
+        vec![Strategy {
+            name: "vector",
+            slow: true,
+            part1: Some(|game| {
+                let mut crab_game = CrabGame::new(&game.labels);
+                simulation::run(&mut crab_game, game.moves1 as usize);
+                crab_game.calculate_order().into()
+            }),
+            part2: Some(|game| {
+                let mut crab_game = CrabGame::new_with_cups(&game.labels, game.cups2);
+                simulation::run(&mut crab_game, game.moves2 as usize);
+                crab_game.calculate_next_two().into()
+            }),
+        }]
+    }
+}
+
+impl Generator for Day23 {
+    const SIZE: &'static str = "cups";
+    const DEFAULT_SIZE: usize = 9;
+    // Labels are single digits, and a move needs three cups picked up besides the current one
+    // and the destination
+    const MIN_SIZE: usize = 5;
+    const MAX_SIZE: usize = 9;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut cups: Vec<u32> = (1..=size as u32).collect();
+        rng.shuffle(&mut cups);
+        let labels: String = cups.iter().map(u32::to_string).collect();
+        Generated::new(format!("{}\n", labels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::differential::{self, shrink_number, Case};
+    use aoc_common::generate;
+    use aoc_common::simulation::Stop;
+
+    const INPUT: &str = include_str!("./../../inputs/day-23.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-23.txt");
+
+    #[test]
+    fn invalid_labels() {
+        let err = parse_input("38712536\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 7, "3"));
+        // No cups at all, there would be no current cup to start from
+        for input in ["", "\n\n"] {
+            let err = parse_input(input).unwrap_err();
+            assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, ""));
+        }
+        for input in ["1", "21\n", "123"] {
+            let err = parse_input(input).unwrap_err();
+            assert_eq!(
+                (err.line, err.column, err.text.as_str()),
+                (1, 1, input.trim())
+            );
+        }
+        // With four cups the picked up ones end up where they were, the circle never changes
+        assert_eq!(star1(&parse_input("4132").unwrap(), MOVES1), 324);
+    }
+
+    #[test]
+    fn simple_star1() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&numbers, MOVES1), answers::expected(23, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let numbers = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&numbers, MOVES1), answers::expected(23, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(
+            star2(&numbers, CUPS2, MOVES2),
+            answers::expected(23, "simple", 2)
+        );
+    }
+
+    #[test]
+    fn full_star2() {
+        let numbers = parse_input(INPUT).unwrap();
+        assert_eq!(
+            star2(&numbers, CUPS2, MOVES2),
+            answers::expected(23, "full", 2)
+        );
+    }
+
+    #[test]
+    fn other_games() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&numbers, 10), 92658374);
+        assert_eq!(star1(&numbers, 0), 25467389);
+        // Without extra cups, the second part multiplies the two cups after 1 of the first one
+        assert_eq!(star2(&numbers, 9, 10), 9 * 2);
+        let mut crab_game = CrabGame::new_with_cups(&numbers, 20);
+        simulation::run(&mut crab_game, 100);
+        assert_eq!(star2(&numbers, 20, 100), crab_game.calculate_next_two());
+    }
+
+    #[test]
+    fn cups_go_round_in_circles() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        let stop = simulation::until_repeat(&mut CrabGameFaster::new(&numbers), 100_000);
+        let (start, period) = match stop {
+            Stop::Cycle { start, period } => (start, period),
+            _ => panic!("No cycle in {:?}!", stop),
+        };
+        let mut crab_game = CrabGameFaster::new(&numbers);
+        simulation::run(&mut crab_game, start);
+        let state = crab_game.state();
+        simulation::run(&mut crab_game, period);
+        assert_eq!(crab_game.state(), state);
+    }
+
+    // Cups labeled from 1 up to their count, in any order, so the labels are still single digits
+    #[derive(Debug, Clone)]
+    struct Cups {
+        labels: Vec<u32>,
+        moves: u64,
+    }
+
+    impl Case for Cups {
+        fn generate(rng: &mut Rng) -> Cups {
+            let mut labels: Vec<u32> = (1..=rng.range(5, 10) as u32).collect();
+            rng.shuffle(&mut labels);
+            Cups {
+                labels,
+                moves: rng.range(0, 500),
+            }
+        }
+
+        fn shrink(&self) -> Vec<Cups> {
+            let mut smaller: Vec<Cups> = shrink_number(self.moves, 0)
+                .into_iter()
+                .map(|moves| Cups {
+                    moves,
+                    ..self.clone()
+                })
+                .collect();
+            // Taking out the cup with the highest label keeps the labels consecutive
+            if self.labels.len() > 5 {
+                let highest = self.labels.len() as u32;
+                smaller.push(Cups {
+                    labels: self
+                        .labels
+                        .iter()
+                        .copied()
+                        .filter(|x| *x != highest)
+                        .collect(),
+                    ..self.clone()
+                });
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree::<Cups, (u64, u64)>(
+            300,
+            &[
+                ("linked-list", |cups| {
+                    let mut crab_game = CrabGameFaster::new(&cups.labels);
+                    simulation::run(&mut crab_game, cups.moves as usize);
+                    (crab_game.calculate_order(), crab_game.calculate_next_two())
+                }),
+                ("vector", |cups| {
+                    let mut crab_game = CrabGame::new(&cups.labels);
+                    simulation::run(&mut crab_game, cups.moves as usize);
+                    (crab_game.calculate_order(), crab_game.calculate_next_two())
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn generated_inputs() {
+        // Ten million moves for the second part, so only a few inputs
+        generate::assert_known_answers::<Day23>(1, &[5, 9]);
+    }
+}
@@ -1,13 +1,14 @@
-// This structure is basically a list in a vector;
-// The value on given position gives us the value
-// that follows this value in the list.
+/// A circular list of the values 1 to n kept in a vector. The entry at the index of a value is
+/// the value that follows it, so moving a value takes constant time.
 // TODO: Make this also work for generic data?
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VectorLinkedList {
     data: Vec<usize>,
 }
 
 impl VectorLinkedList {
+    /// A circle of the values in the given order, which has to hold every value from 1 up to its
+    /// length exactly once.
     pub fn new(data: &[u32]) -> VectorLinkedList {
         let mut vll_data: Vec<usize> = vec![0; data.len() + 1];
         for idx in 0..data.len() {
@@ -19,15 +20,24 @@ impl VectorLinkedList {
         VectorLinkedList { data: vll_data }
     }
 
+    /// The value after `value` in the circle.
     pub fn follows(&self, value: u32) -> u32 {
         self.data[value as usize] as u32
     }
 
+    /// Makes `new_follower` come right after `value`. The list stays a single circle only if the
+    /// values in between are wired back in as well.
     pub fn rewire(&mut self, value: u32, new_follower: u32) {
         self.data[value as usize] = new_follower as usize;
     }
 
+    /// Number of values in the circle.
     pub fn len(&self) -> usize {
         self.data.len() - 1
     }
+
+    /// Whether the circle has no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
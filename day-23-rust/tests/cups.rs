@@ -0,0 +1,30 @@
+use day_23_rust::vector_linked_list::VectorLinkedList;
+use day_23_rust::{parse_input, star1};
+
+// The labels in the order they follow each other, starting at `first`
+fn circle(cups: &VectorLinkedList, first: u32) -> Vec<u32> {
+    let mut labels = vec![first];
+    while labels.len() < cups.len() {
+        labels.push(cups.follows(*labels.last().unwrap()));
+    }
+    labels
+}
+
+#[test]
+fn move_cups_around() {
+    let mut cups = VectorLinkedList::new(&[3, 8, 9, 1, 2, 5, 4, 6, 7]);
+    assert_eq!(cups.len(), 9);
+    assert_eq!(cups.follows(7), 3);
+
+    // The first move of the example, 8, 9 and 1 go after 2
+    cups.rewire(3, 2);
+    cups.rewire(1, 5);
+    cups.rewire(2, 8);
+    assert_eq!(circle(&cups, 3), vec![3, 2, 8, 9, 1, 5, 4, 6, 7]);
+}
+
+#[test]
+fn solve_the_example() {
+    let labels = parse_input("389125467").unwrap();
+    assert_eq!(star1(&labels, 10), 92658374);
+}
@@ -0,0 +1,465 @@
+use aoc_common::frames::Frames;
+use aoc_common::generate::{self, Generated, Generator};
+use aoc_common::geometry::Point2;
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::ParseError;
+use aoc_common::rng::Rng;
+use aoc_common::simulation::{self, Simulation};
+use aoc_common::solution::{Answer, Solution};
+use std::{collections::HashSet, convert::TryFrom, fmt};
+
+// Axial coordinates, x counts tiles to the east and y rows to the south east
+const NEIGHBOUR_DIFFS: [Point2; 6] = [
+    Point2::new(1, 0),
+    Point2::new(0, 1),
+    Point2::new(-1, 1),
+    Point2::new(-1, 0),
+    Point2::new(0, -1),
+    Point2::new(1, -1),
+];
+
+/// Parses the paths to the tiles to flip, one per line, as directions without delimiters like
+/// "esenee".
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            "paths of tiles like \"esenee\"",
+        ));
+    }
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let mut directions: Vec<Direction> = Vec::new();
+            let mut consumed = 0;
+            while consumed < line.len() {
+                let rest = &line[consumed..];
+                let (direction, chars) = Direction::from_larger_string(rest).ok_or_else(|| {
+                    let len = rest.chars().next().map_or(0, char::len_utf8);
+                    ParseError::at(input, &rest[..len], "one of e, se, sw, w, nw or ne")
+                })?;
+                directions.push(direction);
+                consumed += chars;
+            }
+            Ok(directions)
+        })
+        .collect()
+}
+
+/// Black tiles once every path flipped the tile it leads to.
+pub fn star1(paths: &Vec<Vec<Direction>>) -> usize {
+    let max_possible_size = paths.iter().map(|path| path.len()).max().unwrap();
+    let mut grid = HexGrid::new(max_possible_size);
+    grid.init_from_paths(paths);
+    grid.count_black()
+}
+
+/// Days of the living art exhibit in the second star.
+pub const DAYS: usize = 100;
+
+/// Black tiles after the tiles flipped for `amount_of_days` days.
+pub fn star2(paths: &Vec<Vec<Direction>>, amount_of_days: usize) -> usize {
+    let max_possible_size = paths.iter().map(|path| path.len()).max().unwrap();
+    // Black tiles spread one tile each day, and their neighbours one further are looked at
+    let mut grid = HexGrid::new(max_possible_size + amount_of_days + 2);
+    grid.init_from_paths(paths);
+    simulation::run(&mut grid, amount_of_days);
+    grid.count_black()
+}
+
+/// A step to one of the six neighbouring hexagonal tiles, in the order of `NEIGHBOUR_DIFFS`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    // Return the direction and the amount of characters consumed
+    fn from_larger_string(input: &str) -> Option<(Direction, usize)> {
+        let dir_two_chars = match input.get(0..2) {
+            Some("se") => Some(Direction::SouthEast),
+            Some("sw") => Some(Direction::SouthWest),
+            Some("ne") => Some(Direction::NorthEast),
+            Some("nw") => Some(Direction::NorthWest),
+            _ => None,
+        };
+
+        if let Some(dir) = dir_two_chars {
+            Some((dir, 2))
+        } else if input.is_empty() {
+            None
+        } else {
+            match input.get(0..1) {
+                Some("e") => Some((Direction::East, 1)),
+                Some("w") => Some((Direction::West, 1)),
+                _ => None,
+            }
+        }
+    }
+
+    fn to_diff(self) -> Point2 {
+        NEIGHBOUR_DIFFS[self as usize]
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    fn opposite(self) -> Self {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HexGrid {
+    // using the axial coordinate system
+    grid: Vec<Color>,
+    side_len: usize,
+    center: Point2,
+    black_list: HashSet<Point2>,
+}
+
+impl HexGrid {
+    fn new(max_size: usize) -> HexGrid {
+        let side_len = max_size * 2 + 1;
+        HexGrid {
+            grid: vec![Color::White; side_len * side_len],
+            side_len,
+            center: Point2::new(max_size as i64, max_size as i64),
+            black_list: HashSet::new(),
+        }
+    }
+
+    // The grid is made large enough for every tile that is looked at
+    fn index(&self, pos: &Point2) -> usize {
+        let q = usize::try_from(pos.x).unwrap();
+        let r = usize::try_from(pos.y).unwrap();
+        r * self.side_len + q
+    }
+
+    fn at(&self, pos: &Point2) -> Color {
+        self.grid[self.index(pos)]
+    }
+
+    fn change_at(&mut self, pos: &Point2, color: Color) {
+        let idx = self.index(pos);
+        self.grid[idx] = color;
+        if color == Color::Black {
+            self.black_list.insert(*pos);
+        } else {
+            self.black_list.remove(pos);
+        }
+    }
+
+    fn init_from_paths(&mut self, paths: &Vec<Vec<Direction>>) {
+        for path in paths {
+            self.flip_tile(path);
+        }
+    }
+
+    fn flip_tile(&mut self, path: &[Direction]) {
+        let pos = path.iter().fold(self.center, |acc, x| acc + x.to_diff());
+        let color = self.at(&pos);
+        self.change_at(&pos, color.opposite());
+    }
+
+    fn count_black(&self) -> usize {
+        self.black_list.len()
+    }
+
+    fn count_black_neighbours(&self, position: &Point2) -> usize {
+        let mut count = 0;
+        for diff in NEIGHBOUR_DIFFS.iter() {
+            let pos = *position + *diff;
+            let color = self.at(&pos);
+            if color == Color::Black {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn next_day(&mut self) {
+        let mut possible_changes = Vec::with_capacity(self.black_list.len() * 7);
+        for pos in self.black_list.iter() {
+            possible_changes.push(*pos);
+            for diff in NEIGHBOUR_DIFFS.iter() {
+                possible_changes.push(*pos + *diff);
+            }
+        }
+
+        let mut flip_list: Vec<(Point2, Color)> = Vec::new();
+
+        for pos in possible_changes.iter() {
+            let black = self.count_black_neighbours(pos);
+            let color = self.at(pos);
+            if color == Color::Black && (black == 0 || black > 2) {
+                flip_list.push((*pos, Color::White));
+            } else if color == Color::White && black == 2 {
+                flip_list.push((*pos, Color::Black));
+            }
+        }
+
+        for (pos, color) in flip_list {
+            self.change_at(&pos, color);
+        }
+    }
+}
+
+// Rows of tiles around the black ones, every row shifted half a tile to the right of the one
+// above. Black tiles are '#', white ones '.'.
+impl fmt::Display for HexGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Tiles are two columns apart, which leaves room for the shifts
+        let column = |pos: &Point2| 2 * pos.x + pos.y;
+        let (min_row, max_row, min_col, max_col) = self.black_list.iter().fold(
+            (i64::MAX, 0, i64::MAX, 0),
+            |(min_row, max_row, min_col, max_col), pos| {
+                (
+                    min_row.min(pos.y),
+                    max_row.max(pos.y),
+                    min_col.min(column(pos)),
+                    max_col.max(column(pos)),
+                )
+            },
+        );
+        for r in min_row..=max_row {
+            let line: String = (min_col..=max_col)
+                .map(|col| {
+                    if (col + r) % 2 == 1 || col < r {
+                        return ' ';
+                    }
+                    match self.at(&Point2::new((col - r) / 2, r)) {
+                        Color::Black => '#',
+                        Color::White => '.',
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl Simulation for HexGrid {
+    type State = Vec<Point2>;
+
+    fn step(&mut self) {
+        self.next_day();
+    }
+
+    fn state(&self) -> Vec<Point2> {
+        let mut black: Vec<Point2> = self.black_list.iter().copied().collect();
+        black.sort_unstable();
+        black
+    }
+}
+
+/// Lobby Layout, flipping the hexagonal tiles of the lobby floor.
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    // Paths with the days to flip tiles for
+    type Input<'a> = (Vec<Vec<Direction>>, usize);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day24::parse_with(input, &Params::defaults(Day24::PARAMS))
+    }
+
+    // The grid is sized for all days up front
+    const PARAMS: &'static [Param] = &[Param {
+        name: "days",
+        default: "100",
+        description: "Days of flipping tiles in part 2",
+        check: |value| params::between(value, 0usize, 2000),
+    }];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        Ok((parse_input(input)?, params.get("days")))
+    }
+
+    fn part1((paths, _): &Self::Input<'_>) -> Answer {
+        star1(paths).into()
+    }
+
+    fn part2((paths, days): &Self::Input<'_>) -> Option<Answer> {
+        Some(star2(paths, *days).into())
+    }
+
+    // Part 1 shows the tiles being flipped one path at a time, part 2 the days after that
+    fn frames((paths, days): &Self::Input<'_>, part: u32, steps: Option<usize>) -> Option<Frames> {
+        let max_possible_size = paths.iter().map(|path| path.len()).max().unwrap_or(0);
+        if part == 1 {
+            let mut grid = HexGrid::new(max_possible_size);
+            let mut frames = Frames::new();
+            frames.push(grid.to_string());
+            for path in paths.iter().take(steps.unwrap_or(paths.len())) {
+                grid.flip_tile(path);
+                frames.push(grid.to_string());
+            }
+            return Some(frames);
+        }
+        let days = steps.unwrap_or(*days);
+        let mut grid = HexGrid::new(max_possible_size + days + 2);
+        grid.init_from_paths(paths);
+        Some(Frames::record(&mut grid, days, HexGrid::to_string))
+    }
+}
+
+impl Generator for Day24 {
+    const SIZE: &'static str = "paths";
+    const DEFAULT_SIZE: usize = 600;
+
+    // Some paths lead to a tile an earlier path already flipped, taking another way there
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // Doubled columns, so east and west move two and the diagonals one
+        let steps = [
+            ("e", (2, 0)),
+            ("se", (1, 1)),
+            ("sw", (-1, 1)),
+            ("w", (-2, 0)),
+            ("nw", (-1, -1)),
+            ("ne", (1, -1)),
+        ];
+        let mut paths: Vec<Vec<usize>> = Vec::new();
+        let mut black = HashSet::new();
+        for _ in 0..size {
+            let path = if paths.is_empty() || rng.range(0, 5) > 0 {
+                (0..rng.range(1, 25))
+                    .map(|_| rng.range(0, 6) as usize)
+                    .collect()
+            } else {
+                let mut path = rng.pick(&paths).clone();
+                rng.shuffle(&mut path);
+                path
+            };
+            let tile = path.iter().fold((0i64, 0i64), |(x, y), step| {
+                let (dx, dy) = steps[*step].1;
+                (x + dx, y + dy)
+            });
+            if !black.insert(tile) {
+                black.remove(&tile);
+            }
+            paths.push(path);
+        }
+
+        let lines = paths
+            .iter()
+            .map(|path| path.iter().map(|step| steps[*step].0).collect::<String>());
+        Generated::new(generate::lines(lines)).with_part1(black.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+    use aoc_common::simulation::Stop;
+
+    const INPUT: &str = include_str!("./../../inputs/day-24.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-24.txt");
+
+    #[test]
+    fn invalid_paths() {
+        let err = parse_input("esew\nnwwxee\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "x"));
+        // Without any paths there is no largest one to size the floor by
+        for input in ["", "\n\n"] {
+            let err = parse_input(input).unwrap_err();
+            assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, ""));
+        }
+    }
+
+    #[test]
+    fn very_simple() {
+        let paths = parse_input("esew\nnwwswee").unwrap();
+        assert_eq!(star1(&paths), 2);
+    }
+
+    #[test]
+    fn simple_star1() {
+        let paths = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star1(&paths), answers::expected(24, "simple", 1));
+    }
+
+    #[test]
+    fn full_star1() {
+        let paths = parse_input(INPUT).unwrap();
+        assert_eq!(star1(&paths), answers::expected(24, "full", 1));
+    }
+
+    #[test]
+    fn simple_star2() {
+        let paths = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(star2(&paths, DAYS), answers::expected(24, "simple", 2));
+    }
+
+    #[test]
+    fn full_star2() {
+        let paths = parse_input(INPUT).unwrap();
+        assert_eq!(star2(&paths, DAYS), answers::expected(24, "full", 2));
+    }
+
+    #[test]
+    fn other_days() {
+        let paths = parse_input(SIMPLE_INPUT).unwrap();
+        // The counts the example lists for the first days
+        assert_eq!(star2(&paths, 0), 10);
+        assert_eq!(star2(&paths, 1), 15);
+        assert_eq!(star2(&paths, 10), 37);
+        assert_eq!(star2(&parse_input("e").unwrap(), 150), 0);
+    }
+
+    #[test]
+    fn lonely_tiles_disappear() {
+        let mut grid = HexGrid::new(10);
+        grid.init_from_paths(&parse_input("e\nwww").unwrap());
+        // Tiles without black neighbours turn white, and nothing happens after that
+        assert_eq!(
+            simulation::until_fixpoint(&mut grid, 10),
+            Stop::Fixpoint { steps: 1 }
+        );
+        assert_eq!(grid.count_black(), 0);
+    }
+
+    #[test]
+    fn frames_of_flips_and_days() {
+        let input = Day24::parse("e\nwww\nw").unwrap();
+        let frames: Vec<String> = Day24::frames(&input, 1, None)
+            .unwrap()
+            .iter()
+            .map(str::to_owned)
+            .collect();
+        assert_eq!(frames, vec!["", "#\n", "# . . . #\n", "# . # . #\n"]);
+        // Both tiles have no black neighbours, so they turn white and nothing happens after
+        let input = Day24::parse("e\nwww").unwrap();
+        assert_eq!(Day24::frames(&input, 2, None).unwrap().len(), 2);
+
+        let mut grid = HexGrid::new(2);
+        grid.init_from_paths(&parse_input("e\nse\nsw").unwrap());
+        assert_eq!(grid.to_string(), " . #\n# #\n");
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day24>(3, &[1, 600]);
+    }
+}
@@ -0,0 +1,151 @@
+use aoc_common::generate::{Generated, Generator};
+use aoc_common::math;
+use aoc_common::params::{self, Param, Params};
+use aoc_common::parse::{parse_at, ParseError};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+/// Parses the public keys of the card and the door, one per line.
+pub fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let trimmed = input.trim();
+    let mut lines = trimmed.lines().map(str::trim);
+    let mut next_key = || {
+        let line = lines.next().unwrap_or(&trimmed[trimmed.len()..]);
+        parse_at(input, line, "a public key")
+    };
+    Ok((next_key()?, next_key()?))
+}
+
+/// The subject number the public keys are transformed from.
+pub const SUBJECT: u64 = 7;
+/// The modulus the handshake transforms values by.
+pub const MODULUS: u64 = 20201227;
+
+/// The encryption key of the handshake between the card and the door. The loop sizes are
+/// discrete logarithms, finding them keeps about sqrt(modulus) values around, which is why the
+/// modulus can't be larger than 2^32.
+pub fn star1(keys: (u64, u64), subject: u64, modulus: u64) -> u64 {
+    let (card_key, door_key) = keys;
+    let loops_card = find_required_loops(card_key, subject, modulus);
+    let loops_door = find_required_loops(door_key, subject, modulus);
+    let card_encryption_key = math::pow_mod(door_key, loops_card, modulus);
+    let door_encryption_key = math::pow_mod(card_key, loops_door, modulus);
+    assert_eq!(card_encryption_key, door_encryption_key);
+    card_encryption_key
+}
+
+fn find_required_loops(key: u64, subject: u64, modulus: u64) -> u64 {
+    math::discrete_log(subject, key, modulus).unwrap_or_else(|| {
+        panic!(
+            "Key {} can't be reached with subject {} modulo {}!",
+            key, subject, modulus
+        )
+    })
+}
+
+/// Combo Breaker, cracking the door's handshake with the room key card.
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+
+    // Public keys of the card and the door, with the subject and modulus
+    type Input<'a> = ((u64, u64), u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Day25::parse_with(input, &Params::defaults(Day25::PARAMS))
+    }
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "subject",
+            default: "7",
+            description: "Subject number the public keys are transformed from",
+            check: |value| params::between(value, 1, u64::MAX),
+        },
+        Param {
+            name: "modulus",
+            default: "20201227",
+            description: "Modulus of every transformation step",
+            check: |value| params::between(value, 2u64, 1 << 32),
+        },
+    ];
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, ParseError> {
+        Ok((
+            parse_input(input)?,
+            params.get("subject"),
+            params.get("modulus"),
+        ))
+    }
+
+    fn part1((keys, subject, modulus): &Self::Input<'_>) -> Answer {
+        star1(*keys, *subject, *modulus).into()
+    }
+
+    const PARTS: u32 = 1;
+}
+
+impl Generator for Day25 {
+    const SIZE: &'static str = "largest loop size";
+    const DEFAULT_SIZE: usize = 10_000_000;
+    const MIN_SIZE: usize = 2;
+    // 7 generates every key, so loop sizes below this are the only ones for each key
+    const MAX_SIZE: usize = MODULUS as usize - 1;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let card_loops = rng.range(1, size as u64);
+        let door_loops = rng.range(1, size as u64);
+        let card_key = math::pow_mod(SUBJECT, card_loops, MODULUS);
+        let door_key = math::pow_mod(SUBJECT, door_loops, MODULUS);
+        Generated::new(format!("{}\n{}\n", card_key, door_key))
+            .with_part1(math::pow_mod(door_key, card_loops, MODULUS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers;
+    use aoc_common::generate;
+
+    const INPUT: &str = include_str!("./../../inputs/day-25.txt");
+    const SIMPLE_INPUT: &str = include_str!("./../../inputs/simple/day-25.txt");
+
+    #[test]
+    fn simple_star1() {
+        let numbers = parse_input(SIMPLE_INPUT).unwrap();
+        assert_eq!(
+            star1(numbers, SUBJECT, MODULUS),
+            answers::expected(25, "simple", 1)
+        );
+    }
+
+    #[test]
+    fn full_star1() {
+        let numbers = parse_input(INPUT).unwrap();
+        assert_eq!(
+            star1(numbers, SUBJECT, MODULUS),
+            answers::expected(25, "full", 1)
+        );
+    }
+
+    #[test]
+    fn other_subjects_and_moduli() {
+        // 3 generates every value modulo 7: 3, 2, 6, 4, 5, 1
+        assert_eq!(star1((6, 4), 3, 7), 1);
+        assert_eq!(star1((2, 5), 3, 7), 4);
+        assert_eq!(star1((8, 8), 2, 1 << 32), 1 << 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Key 3 can't be reached with subject 2 modulo 7!")]
+    fn unreachable_key() {
+        star1((3, 4), 2, 7);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        generate::assert_known_answers::<Day25>(20, &[2, 1000, 100_000]);
+    }
+}